@@ -0,0 +1,136 @@
+//! Benchmark comparing channel access through the typed slab storage of the channel store with
+//! the previous dynamically typed register design (`RefCell<Box<dyn Any>>` with a runtime type
+//! check on every access).
+//!
+//! Run with `cargo bench --bench channel_access`.
+
+use std::{hint::black_box, time::Instant};
+
+use comet::channel::store::{
+    ChannelReadBuilder, ChannelStore, ChannelWriteBuilder, RegViewProducer,
+};
+
+const ITERATIONS: u32 = 5_000_000;
+const LARGE_ITERATIONS: u32 = 100_000;
+
+/// Reproduction of the previous register design used as the benchmark baseline.
+mod dyn_reg {
+    use std::{
+        any::{self, Any, TypeId},
+        cell::RefCell,
+    };
+
+    pub struct DynReg {
+        reg_type: TypeId,
+        data: RefCell<Box<dyn Any>>,
+    }
+
+    impl DynReg {
+        pub fn new<T: 'static>(value: T) -> Self {
+            Self {
+                reg_type: TypeId::of::<T>(),
+                data: RefCell::new(Box::new(value)),
+            }
+        }
+
+        fn matches_type_panic<T: 'static>(&self) {
+            if TypeId::of::<T>() != self.reg_type {
+                panic!(
+                    "Requested type of [{}] does not match register type.",
+                    any::type_name::<T>()
+                )
+            }
+        }
+
+        pub fn get<T: 'static + Clone>(&self) -> T {
+            self.matches_type_panic::<T>();
+            self.data.borrow().downcast_ref::<T>().unwrap().clone()
+        }
+
+        pub fn set<T: 'static>(&self, value: T) {
+            self.matches_type_panic::<T>();
+            *self.data.borrow_mut().downcast_mut().unwrap() = value;
+        }
+    }
+}
+
+fn bench(name: &str, iterations: u32, mut f: impl FnMut(u32)) {
+    // Warm up before measuring.
+    for i in 0..(iterations / 10) {
+        f(i);
+    }
+
+    let start = Instant::now();
+    for i in 0..iterations {
+        f(i);
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{:<40} {:>10.2} ns/iter",
+        name,
+        elapsed.as_nanos() as f64 / iterations as f64
+    );
+}
+
+fn bench_dyn_reg() {
+    let regs: Vec<dyn_reg::DynReg> = (0..64).map(|i| dyn_reg::DynReg::new(i as u64)).collect();
+    bench("dyn_reg/get_u64", ITERATIONS, |i| {
+        black_box(regs[(i % 64) as usize].get::<u64>());
+    });
+    bench("dyn_reg/set_u64", ITERATIONS, |i| {
+        regs[(i % 64) as usize].set::<u64>(black_box(i as u64));
+    });
+
+    let large_reg = dyn_reg::DynReg::new([0f64; 4096]);
+    bench("dyn_reg/get_large", LARGE_ITERATIONS, |_| {
+        black_box(large_reg.get::<[f64; 4096]>());
+    });
+}
+
+fn bench_channel_store() {
+    let mut channel_store = ChannelStore::default();
+    let write_builder = ChannelWriteBuilder::new(0);
+    let read_builder = ChannelReadBuilder::new(1);
+
+    let owner_tokens: Vec<_> = (0..64)
+        .map(|i| {
+            write_builder.register_write_channel(
+                &mut channel_store,
+                format!("bench.channel.{}", i),
+                i as u64,
+            )
+        })
+        .collect();
+    let reader_tokens: Vec<_> = (0..64)
+        .map(|i| {
+            read_builder
+                .bind_read_channel::<u64>(&mut channel_store, format!("bench.channel.{}", i))
+        })
+        .collect();
+    let large_token = write_builder.register_write_channel(
+        &mut channel_store,
+        "bench.channel.large".to_string(),
+        [0f64; 4096],
+    );
+
+    bench("channel_store/get_u64", ITERATIONS, |i| {
+        black_box(channel_store.grab(&reader_tokens[(i % 64) as usize]).get());
+    });
+    bench("channel_store/set_u64", ITERATIONS, |i| {
+        channel_store
+            .grab(&owner_tokens[(i % 64) as usize])
+            .set(black_box(i as u64));
+    });
+    bench("channel_store/get_large", LARGE_ITERATIONS, |_| {
+        black_box(channel_store.grab(&large_token).get());
+    });
+    bench("channel_store/borrow_large", LARGE_ITERATIONS, |_| {
+        black_box(channel_store.grab(&large_token).borrow()[black_box(17)]);
+    });
+}
+
+fn main() {
+    bench_dyn_reg();
+    bench_channel_store();
+}
@@ -0,0 +1,327 @@
+// Storage of register values. Without the std and concurrent features channel stores are only
+// accessed by a single thread and registers hold their value within a RefCell. With std alone,
+// components of a runner may be dispatched on worker threads and registers track borrows with an
+// atomic flag. With the concurrent feature, channel stores may be observed by threads outside of
+// the runner and registers are triple buffered so that readers never stall the writer.
+#[cfg(not(any(feature = "std", feature = "concurrent")))]
+pub(crate) use core::cell::{Ref as CellRef, RefCell as RegCell, RefMut as CellRefMut};
+
+#[cfg(all(feature = "std", not(feature = "concurrent")))]
+pub(crate) use self::atomic::{CellRef, CellRefMut, RegCell};
+
+#[cfg(feature = "concurrent")]
+pub(crate) use self::triple::{CellRef, CellRefMut, RegCell};
+
+/// Returns true if the cell may be mutably borrowed, false while a conflicting borrow is held.
+#[cfg(not(any(feature = "std", feature = "concurrent")))]
+pub(crate) fn is_writable<T: Clone>(cell: &RegCell<T>) -> bool {
+    cell.try_borrow_mut().is_ok()
+}
+
+/// Returns true if the cell may be mutably borrowed, false while a conflicting borrow is held.
+#[cfg(any(feature = "std", feature = "concurrent"))]
+pub(crate) fn is_writable<T: Clone>(cell: &RegCell<T>) -> bool {
+    cell.is_writable()
+}
+
+// Counters of register metadata, such as write sequences and queue cursors. Counters are atomic
+// when registers may be accessed by several threads. Otherwise a plain cell is used, as targets
+// without 64 bit atomics are supported without the std and concurrent features.
+#[cfg(any(feature = "std", feature = "concurrent"))]
+pub(crate) use core::sync::atomic::AtomicU64 as U64Cell;
+
+#[cfg(not(any(feature = "std", feature = "concurrent")))]
+pub(crate) use self::local::U64Cell;
+
+#[cfg(not(any(feature = "std", feature = "concurrent")))]
+mod local {
+    use core::{cell::Cell, sync::atomic::Ordering};
+
+    /// Counter with the interface of an AtomicU64 for single threaded use, orderings are ignored.
+    pub(crate) struct U64Cell(Cell<u64>);
+
+    impl U64Cell {
+        pub(crate) const fn new(value: u64) -> Self {
+            Self(Cell::new(value))
+        }
+
+        pub(crate) fn load(&self, _order: Ordering) -> u64 {
+            self.0.get()
+        }
+
+        pub(crate) fn store(&self, value: u64, _order: Ordering) {
+            self.0.set(value);
+        }
+
+        pub(crate) fn fetch_add(&self, value: u64, _order: Ordering) -> u64 {
+            let previous = self.0.get();
+            self.0.set(previous.wrapping_add(value));
+            previous
+        }
+    }
+}
+
+#[cfg(all(feature = "std", not(feature = "concurrent")))]
+mod atomic {
+    use core::{
+        cell::UnsafeCell,
+        ops::{Deref, DerefMut},
+        sync::atomic::{AtomicIsize, Ordering},
+    };
+
+    /// Borrow flag value of a cell which is mutably borrowed.
+    const MUT_BORROWED: isize = -1;
+
+    /// Cell tracking borrows with an atomic flag following the same rules as a RefCell, any
+    /// number of shared borrows or a single mutable borrow. Conflicting borrows panic.
+    pub(crate) struct RegCell<T> {
+        /// Number of shared borrows, or MUT_BORROWED while mutably borrowed.
+        borrow: AtomicIsize,
+        data: UnsafeCell<T>,
+    }
+
+    // SAFETY: Access to the value is guarded by the atomic borrow flag, which only hands out
+    // either shared references (requiring T: Sync) or a single mutable reference (requiring
+    // T: Send).
+    unsafe impl<T: Send + Sync> Sync for RegCell<T> {}
+
+    impl<T> RegCell<T> {
+        pub(crate) fn new(value: T) -> Self {
+            Self {
+                borrow: AtomicIsize::new(0),
+                data: UnsafeCell::new(value),
+            }
+        }
+
+        pub(crate) fn borrow(&self) -> CellRef<'_, T> {
+            let mut count = self.borrow.load(Ordering::Relaxed);
+            loop {
+                if count == MUT_BORROWED {
+                    panic!("Register is already mutably borrowed.");
+                }
+                match self.borrow.compare_exchange_weak(
+                    count,
+                    count + 1,
+                    Ordering::Acquire,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => return CellRef { cell: self },
+                    Err(actual) => count = actual,
+                }
+            }
+        }
+
+        pub(crate) fn borrow_mut(&self) -> CellRefMut<'_, T> {
+            if self
+                .borrow
+                .compare_exchange(0, MUT_BORROWED, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                panic!("Register is already borrowed.");
+            }
+
+            CellRefMut { cell: self }
+        }
+
+        pub(crate) fn is_writable(&self) -> bool {
+            self.borrow.load(Ordering::Acquire) == 0
+        }
+    }
+
+    /// Shared borrow guard of a cell value.
+    pub(crate) struct CellRef<'a, T> {
+        cell: &'a RegCell<T>,
+    }
+
+    impl<T> Deref for CellRef<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            // SAFETY: The borrow flag holds a shared borrow for the lifetime of the guard.
+            unsafe { &*self.cell.data.get() }
+        }
+    }
+
+    impl<T> Drop for CellRef<'_, T> {
+        fn drop(&mut self) {
+            self.cell.borrow.fetch_sub(1, Ordering::Release);
+        }
+    }
+
+    /// Mutable borrow guard of a cell value.
+    pub(crate) struct CellRefMut<'a, T> {
+        cell: &'a RegCell<T>,
+    }
+
+    impl<T> Deref for CellRefMut<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            // SAFETY: The borrow flag holds the unique mutable borrow for the lifetime of the
+            // guard.
+            unsafe { &*self.cell.data.get() }
+        }
+    }
+
+    impl<T> DerefMut for CellRefMut<'_, T> {
+        fn deref_mut(&mut self) -> &mut T {
+            // SAFETY: The borrow flag holds the unique mutable borrow for the lifetime of the
+            // guard.
+            unsafe { &mut *self.cell.data.get() }
+        }
+    }
+
+    impl<T> Drop for CellRefMut<'_, T> {
+        fn drop(&mut self) {
+            self.cell.borrow.store(0, Ordering::Release);
+        }
+    }
+}
+
+#[cfg(feature = "concurrent")]
+mod triple {
+    use core::{
+        cell::UnsafeCell,
+        ops::{Deref, DerefMut},
+        sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    };
+
+    /// Number of buffers of a cell, the published buffer and two spare buffers.
+    const BUFFER_COUNT: usize = 3;
+
+    /// Triple buffered cell. Readers borrow the published buffer while the single writer modifies
+    /// a spare buffer, publishing it once the mutable borrow is released. As such readers always
+    /// observe a complete value and never stall the writer.
+    ///
+    /// The writer only waits if shared borrows are held on both spare buffers, which requires
+    /// borrows to be held across two publications. A second mutable borrow while the cell is
+    /// mutably borrowed is a breach of the single writer rule and panics.
+    ///
+    /// Each mutable borrow starts from a copy of the published value made with clone_from(), as
+    /// such a write costs a clone of the whole value even if only part of it is modified.
+    pub(crate) struct RegCell<T> {
+        buffers: [UnsafeCell<T>; BUFFER_COUNT],
+        /// Index of the published buffer, only modified by the writer.
+        published: AtomicUsize,
+        /// Number of shared borrows held on each buffer.
+        readers: [AtomicUsize; BUFFER_COUNT],
+        /// Set while the cell is mutably borrowed.
+        writing: AtomicBool,
+    }
+
+    // SAFETY: Readers only access the published buffer, which is never modified while published.
+    // The writer only modifies an unpublished buffer without readers, which is never borrowed
+    // until published. Shared references require T: Sync and values are written by whichever
+    // thread holds the mutable borrow, requiring T: Send.
+    unsafe impl<T: Send + Sync> Sync for RegCell<T> {}
+
+    impl<T: Clone> RegCell<T> {
+        pub(crate) fn new(value: T) -> Self {
+            Self {
+                buffers: [
+                    UnsafeCell::new(value.clone()),
+                    UnsafeCell::new(value.clone()),
+                    UnsafeCell::new(value),
+                ],
+                published: AtomicUsize::new(0),
+                readers: [const { AtomicUsize::new(0) }; BUFFER_COUNT],
+                writing: AtomicBool::new(false),
+            }
+        }
+
+        pub(crate) fn borrow(&self) -> CellRef<'_, T> {
+            loop {
+                let idx = self.published.load(Ordering::SeqCst);
+                self.readers[idx].fetch_add(1, Ordering::SeqCst);
+                // The writer may have selected the buffer before the borrow was registered, the
+                // borrow is only valid if the buffer is still published.
+                if self.published.load(Ordering::SeqCst) == idx {
+                    return CellRef { cell: self, idx };
+                }
+                self.readers[idx].fetch_sub(1, Ordering::Release);
+            }
+        }
+
+        pub(crate) fn borrow_mut(&self) -> CellRefMut<'_, T> {
+            if self.writing.swap(true, Ordering::Acquire) {
+                panic!("Register is already mutably borrowed.");
+            }
+
+            let published = self.published.load(Ordering::Relaxed);
+            let idx = loop {
+                let spare = (1..BUFFER_COUNT)
+                    .map(|offset| (published + offset) % BUFFER_COUNT)
+                    .find(|idx| self.readers[*idx].load(Ordering::SeqCst) == 0);
+                match spare {
+                    Some(idx) => break idx,
+                    None => {
+                        core::hint::spin_loop();
+                        #[cfg(feature = "std")]
+                        std::thread::yield_now();
+                    }
+                }
+            };
+
+            // SAFETY: The spare buffer is neither published nor borrowed, and the writing flag
+            // grants this guard exclusive access to every unpublished buffer.
+            unsafe { (*self.buffers[idx].get()).clone_from(&*self.buffers[published].get()) };
+            CellRefMut { cell: self, idx }
+        }
+
+        /// Shared borrows never conflict with the writer, only a held mutable borrow does.
+        pub(crate) fn is_writable(&self) -> bool {
+            !self.writing.load(Ordering::Acquire)
+        }
+    }
+
+    /// Shared borrow guard of the published buffer of a cell.
+    pub(crate) struct CellRef<'a, T> {
+        cell: &'a RegCell<T>,
+        idx: usize,
+    }
+
+    impl<T> Deref for CellRef<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            // SAFETY: The buffer was published when the borrow was registered, the writer does
+            // not select it while the borrow is held.
+            unsafe { &*self.cell.buffers[self.idx].get() }
+        }
+    }
+
+    impl<T> Drop for CellRef<'_, T> {
+        fn drop(&mut self) {
+            self.cell.readers[self.idx].fetch_sub(1, Ordering::Release);
+        }
+    }
+
+    /// Mutable borrow guard of a spare buffer of a cell, published when dropped.
+    pub(crate) struct CellRefMut<'a, T> {
+        cell: &'a RegCell<T>,
+        idx: usize,
+    }
+
+    impl<T> Deref for CellRefMut<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            // SAFETY: The guard has exclusive access to the spare buffer until dropped.
+            unsafe { &*self.cell.buffers[self.idx].get() }
+        }
+    }
+
+    impl<T> DerefMut for CellRefMut<'_, T> {
+        fn deref_mut(&mut self) -> &mut T {
+            // SAFETY: The guard has exclusive access to the spare buffer until dropped.
+            unsafe { &mut *self.cell.buffers[self.idx].get() }
+        }
+    }
+
+    impl<T> Drop for CellRefMut<'_, T> {
+        fn drop(&mut self) {
+            self.cell.published.store(self.idx, Ordering::SeqCst);
+            self.cell.writing.store(false, Ordering::Release);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use core::fmt;
+
+use alloc::string::{String, ToString};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{
+    reg::{AnyClone, RegReadView},
+    schema::derive_schema,
+    slab::{AnySlab, RegSlab},
+};
+
+/// Type erased encoder of the values of a channel registered with a serializable type.
+#[derive(Clone, Copy)]
+pub(crate) struct ChannelCodec {
+    encode: fn(&dyn AnySlab, usize) -> Result<Value, serde_json::Error>,
+    schema: fn(&dyn AnySlab, usize) -> Result<Value, serde_json::Error>,
+}
+
+impl ChannelCodec {
+    /// Codec of channels holding values of type T.
+    pub(crate) fn of<T: AnyClone + Serialize>() -> Self {
+        Self {
+            encode: encode_slot::<T>,
+            schema: schema_slot::<T>,
+        }
+    }
+
+    /// Encode the register at the given slot of a slab.
+    ///
+    /// ### Arguments
+    /// * 'slab' - Slab of the channel the codec was registered with.
+    /// * 'slot' - Slot of the register to be encoded.
+    ///
+    pub(crate) fn encode(
+        &self,
+        slab: &dyn AnySlab,
+        slot: usize,
+    ) -> Result<Value, serde_json::Error> {
+        (self.encode)(slab, slot)
+    }
+
+    /// Derive the JSON schema of the type of the register at the given slot of a slab.
+    ///
+    /// ### Arguments
+    /// * 'slab' - Slab of the channel the codec was registered with.
+    /// * 'slot' - Slot of the register whose type is described.
+    ///
+    pub(crate) fn schema(
+        &self,
+        slab: &dyn AnySlab,
+        slot: usize,
+    ) -> Result<Value, serde_json::Error> {
+        (self.schema)(slab, slot)
+    }
+}
+
+/// Downcast a slab to the slab of the codec type.
+fn typed_slab<T: AnyClone>(slab: &dyn AnySlab) -> &RegSlab<T> {
+    // Codecs are only stored with channels of type T, as such the slab always holds type T.
+    slab.as_any()
+        .downcast_ref::<RegSlab<T>>()
+        .expect("Channel codec does not match the channel type.")
+}
+
+fn encode_slot<T: AnyClone + Serialize>(
+    slab: &dyn AnySlab,
+    slot: usize,
+) -> Result<Value, serde_json::Error> {
+    serde_json::to_value(&*RegReadView::new(typed_slab::<T>(slab).reg(slot)).borrow())
+}
+
+fn schema_slot<T: AnyClone + Serialize>(
+    slab: &dyn AnySlab,
+    slot: usize,
+) -> Result<Value, serde_json::Error> {
+    derive_schema(&*RegReadView::new(typed_slab::<T>(slab).reg(slot)).borrow())
+}
+
+/// Error produced when encoding a channel by name.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum EncodeError {
+    /// No channel with the requested name exists within the channel store.
+    NotFound { name: String },
+    /// The channel was not registered with a serializable type.
+    NotSerializable { name: String },
+    /// The channel value failed to serialize.
+    Serialize { name: String, message: String },
+    /// The channel value failed to serialize while deriving its schema.
+    Schema { name: String, message: String },
+}
+
+impl EncodeError {
+    pub(crate) fn serialize(name: &str, err: serde_json::Error) -> Self {
+        EncodeError::Serialize {
+            name: String::from(name),
+            message: err.to_string(),
+        }
+    }
+
+    pub(crate) fn schema(name: &str, err: serde_json::Error) -> Self {
+        EncodeError::Schema {
+            name: String::from(name),
+            message: err.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::NotFound { name } => write!(f, "Channel [{}] does not exist.", name),
+            EncodeError::NotSerializable { name } => write!(
+                f,
+                "Channel [{}] was not registered with a serializable type.",
+                name
+            ),
+            EncodeError::Serialize { name, message } => {
+                write!(f, "Channel [{}] failed to serialize: {}", name, message)
+            }
+            EncodeError::Schema { name, message } => write!(
+                f,
+                "Schema of channel [{}] cannot be derived: {}",
+                name, message
+            ),
+        }
+    }
+}
+
+impl core::error::Error for EncodeError {}
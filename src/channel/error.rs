@@ -0,0 +1,121 @@
+use core::fmt;
+
+use alloc::string::String;
+
+/// Error produced by a fallible channel registration call.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ChannelError {
+    /// An empty string was provided as the channel name.
+    EmptyName { requester: usize },
+    /// A channel with the same name has already been registered.
+    DuplicateName { name: String, requester: usize },
+    /// No channel with the requested name exists within the channel store.
+    NotFound { name: String, requester: usize },
+    /// The channel exists but has not been picked up by an owner.
+    NoOwner { name: String, requester: usize },
+    /// The dangling channel has already been picked up by another owner.
+    AlreadyOwned {
+        name: String,
+        requester: usize,
+        owner: usize,
+    },
+    /// The type requested by the caller does not match the type the channel was registered with.
+    TypeMismatch {
+        name: String,
+        requester: usize,
+        expected: &'static str,
+        actual: &'static str,
+    },
+    /// A staleness limit was requested on a channel which the requester does not read.
+    NotRead { name: String, requester: usize },
+    /// A history channel was requested with a depth of zero past values.
+    ZeroHistoryDepth { name: String, requester: usize },
+}
+
+impl ChannelError {
+    /// Name of the channel the failed request was made against.
+    pub fn channel_name(&self) -> &str {
+        match self {
+            ChannelError::EmptyName { .. } => "",
+            ChannelError::DuplicateName { name, .. }
+            | ChannelError::NotFound { name, .. }
+            | ChannelError::NoOwner { name, .. }
+            | ChannelError::AlreadyOwned { name, .. }
+            | ChannelError::TypeMismatch { name, .. }
+            | ChannelError::NotRead { name, .. }
+            | ChannelError::ZeroHistoryDepth { name, .. } => name.as_str(),
+        }
+    }
+
+    /// Id of the component which made the failed request.
+    pub fn requester(&self) -> usize {
+        match self {
+            ChannelError::EmptyName { requester }
+            | ChannelError::DuplicateName { requester, .. }
+            | ChannelError::NotFound { requester, .. }
+            | ChannelError::NoOwner { requester, .. }
+            | ChannelError::AlreadyOwned { requester, .. }
+            | ChannelError::TypeMismatch { requester, .. }
+            | ChannelError::NotRead { requester, .. }
+            | ChannelError::ZeroHistoryDepth { requester, .. } => *requester,
+        }
+    }
+}
+
+impl fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChannelError::EmptyName { requester } => write!(
+                f,
+                "Channel name cannot be empty (requested by component [{}]).",
+                requester
+            ),
+            ChannelError::DuplicateName { name, requester } => write!(
+                f,
+                "Channel [{}] already exists (requested by component [{}]).",
+                name, requester
+            ),
+            ChannelError::NotFound { name, requester } => write!(
+                f,
+                "Channel [{}] does not exist (requested by component [{}]).",
+                name, requester
+            ),
+            ChannelError::NoOwner { name, requester } => write!(
+                f,
+                "Channel [{}] cannot bind as there is no owner for this channel (requested by component [{}]).",
+                name, requester
+            ),
+            ChannelError::AlreadyOwned {
+                name,
+                requester,
+                owner,
+            } => write!(
+                f,
+                "Channel [{}] already has an owner [{}] (requested by component [{}]).",
+                name, owner, requester
+            ),
+            ChannelError::TypeMismatch {
+                name,
+                requester,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Channel [{}] does not match the requested type, expected [{}] but found [{}] (requested by component [{}]).",
+                name, expected, actual, requester
+            ),
+            ChannelError::NotRead { name, requester } => write!(
+                f,
+                "Channel [{}] is not read by the requesting component [{}].",
+                name, requester
+            ),
+            ChannelError::ZeroHistoryDepth { name, requester } => write!(
+                f,
+                "Channel [{}] cannot bind a history of zero values (requested by component [{}]).",
+                name, requester
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ChannelError {}
@@ -0,0 +1,73 @@
+use super::{
+    reg::{AnyClone, RegReadView, RegRef},
+    slab::RegSlab,
+};
+
+/// View used to access the past values of a channel with read only permissions. Values are
+/// indexed by the number of ticks behind the current tick, the value one tick behind is the value
+/// the channel held at the end of the previous tick. Until enough ticks were recorded older
+/// entries hold the initial channel value.
+pub struct HistoryView<'a, T: AnyClone> {
+    slab: &'a RegSlab<T>,
+    /// First slot of the history ring within the slab.
+    base_slot: usize,
+    /// Number of slots of the history ring.
+    ring_len: usize,
+    /// Ring index the next value is written into.
+    head: usize,
+    /// Depth requested by the reader.
+    depth: usize,
+}
+
+impl<'a, T: AnyClone> HistoryView<'a, T> {
+    pub(crate) fn new(
+        slab: &'a RegSlab<T>,
+        base_slot: usize,
+        ring_len: usize,
+        head: usize,
+        depth: usize,
+    ) -> Self {
+        Self {
+            slab,
+            base_slot,
+            ring_len,
+            head,
+            depth,
+        }
+    }
+
+    /// Number of past values accessible through this view.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn view(&self, ticks_behind: usize) -> RegReadView<'a, T> {
+        assert!(
+            (1..=self.depth).contains(&ticks_behind),
+            "History index [{}] is out of range, the history depth is [{}].",
+            ticks_behind,
+            self.depth
+        );
+        let ring_idx = (self.head + self.ring_len - ticks_behind) % self.ring_len;
+        RegReadView::new(self.slab.reg(self.base_slot + ring_idx))
+    }
+
+    /// Accessor method for the value the channel held the given number of ticks ago. This method
+    /// clones the value out to the caller.
+    ///
+    /// ### Arguments
+    /// * 'ticks_behind' - Age of the value in ticks, between one and the depth of the view.
+    ///
+    pub fn get(&self, ticks_behind: usize) -> T {
+        self.view(ticks_behind).get()
+    }
+
+    /// Borrow the value the channel held the given number of ticks ago without cloning it.
+    ///
+    /// ### Arguments
+    /// * 'ticks_behind' - Age of the value in ticks, between one and the depth of the view.
+    ///
+    pub fn borrow(&self, ticks_behind: usize) -> RegRef<'a, T> {
+        self.view(ticks_behind).borrow()
+    }
+}
@@ -0,0 +1,126 @@
+use alloc::{rc::Rc, vec::Vec};
+
+/// Initial slot count of the index, must be a power of two.
+const INITIAL_CAPACITY: usize = 16;
+
+struct IndexSlot {
+    hash: u64,
+    name: Rc<str>,
+    accessor_id: usize,
+}
+
+/// Open addressing hash index mapping channel names to channel accessor ids. Names inserted
+/// into the index are interned, the same allocation is shared with the channel entry.
+#[derive(Default)]
+pub(crate) struct ChannelIndex {
+    slots: Vec<Option<IndexSlot>>,
+    len: usize,
+}
+
+/// FNV-1a hash of a channel name.
+fn hash_name(name: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in name.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    hash
+}
+
+impl ChannelIndex {
+    /// Locate the slot for a given name, returning either the slot holding the name or the
+    /// first empty slot in its probe sequence.
+    fn probe(&self, hash: u64, name: &str) -> usize {
+        let mask = self.slots.len() - 1;
+        let mut slot_idx = (hash as usize) & mask;
+        loop {
+            match &self.slots[slot_idx] {
+                Some(slot) if slot.hash == hash && &*slot.name == name => return slot_idx,
+                Some(_) => slot_idx = (slot_idx + 1) & mask,
+                None => return slot_idx,
+            }
+        }
+    }
+
+    fn grow(&mut self) {
+        let new_capacity = (self.slots.len() * 2).max(INITIAL_CAPACITY);
+        let old_slots = core::mem::take(&mut self.slots);
+        self.slots.resize_with(new_capacity, || None);
+        for slot in old_slots.into_iter().flatten() {
+            let slot_idx = self.probe(slot.hash, &slot.name);
+            self.slots[slot_idx] = Some(slot);
+        }
+    }
+
+    /// Obtain the accessor id of the channel with the given name.
+    pub(crate) fn get(&self, name: &str) -> Option<usize> {
+        if self.slots.is_empty() {
+            return None;
+        }
+
+        self.slots[self.probe(hash_name(name), name)]
+            .as_ref()
+            .map(|slot| slot.accessor_id)
+    }
+
+    /// Insert a new channel name into the index. Returns the interned name on success or None
+    /// if the name is already present.
+    pub(crate) fn insert(&mut self, name: &str, accessor_id: usize) -> Option<Rc<str>> {
+        // Keep the load factor at or below one half to keep probe sequences short.
+        if (self.len + 1) * 2 > self.slots.len() {
+            self.grow();
+        }
+
+        let hash = hash_name(name);
+        let slot_idx = self.probe(hash, name);
+        if self.slots[slot_idx].is_some() {
+            return None;
+        }
+
+        let interned_name: Rc<str> = Rc::from(name);
+        self.slots[slot_idx] = Some(IndexSlot {
+            hash,
+            name: interned_name.clone(),
+            accessor_id,
+        });
+        self.len += 1;
+
+        Some(interned_name)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use alloc::{format, string::String, vec::Vec};
+
+    use super::ChannelIndex;
+
+    #[test]
+    fn test_index_insert_get() {
+        let mut index = ChannelIndex::default();
+        assert_eq!(index.get("test.test1"), None);
+
+        assert_eq!(&*index.insert("test.test1", 0).unwrap(), "test.test1");
+        assert_eq!(&*index.insert("test.test2", 1).unwrap(), "test.test2");
+        assert!(index.insert("test.test1", 2).is_none());
+
+        assert_eq!(index.get("test.test1"), Some(0));
+        assert_eq!(index.get("test.test2"), Some(1));
+        assert_eq!(index.get("test.test3"), None);
+    }
+
+    #[test]
+    fn test_index_grow() {
+        let mut index = ChannelIndex::default();
+        let names: Vec<String> = (0..1000).map(|i| format!("test.channel.{}", i)).collect();
+        for (i, name) in names.iter().enumerate() {
+            assert!(index.insert(name, i).is_some());
+        }
+
+        for (i, name) in names.iter().enumerate() {
+            assert_eq!(index.get(name), Some(i));
+        }
+        assert_eq!(index.len, 1000);
+    }
+}
@@ -1,3 +1,15 @@
+mod cell;
+#[cfg(feature = "serde")]
+pub mod codec;
+pub mod error;
+pub mod history;
+mod index;
+pub mod queue;
+pub mod record;
 pub mod reg;
+#[cfg(feature = "serde")]
+mod schema;
+mod slab;
+pub mod snapshot;
 pub mod store;
 pub mod token;
@@ -0,0 +1,271 @@
+use core::sync::atomic::Ordering;
+
+use alloc::{collections::VecDeque, vec::Vec};
+
+use super::cell::U64Cell;
+use super::reg::{AnyClone, ChannelClock, Reg, RegMutView, RegReadView};
+
+/// Action taken when a message is published into a full queue channel.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DropPolicy {
+    /// The oldest queued message is discarded to make room for the new message.
+    DropOldest,
+    /// The new message is discarded and the queued messages are kept.
+    DropNewest,
+}
+
+/// Read position of a single queue channel reader.
+struct QueueCursor {
+    /// Id of the component owning the cursor.
+    reader_id: usize,
+    /// Sequence number of the next message to be read. Held in a cell so that readers only
+    /// require a shared borrow of the queue and may drain concurrently.
+    next_seq: U64Cell,
+}
+
+/// Bounded message queue stored within the register of a queue channel. Every published message
+/// is assigned a sequence number, each reader keeps a cursor to the next message it has not read.
+/// Messages are retained until every reader has read them or they are dropped by the drop policy.
+pub struct EventQueue<T> {
+    messages: VecDeque<T>,
+    capacity: usize,
+    policy: DropPolicy,
+    /// Sequence number of the front message.
+    head_seq: u64,
+    cursors: Vec<QueueCursor>,
+    /// Number of messages discarded by the drop policy.
+    dropped: u64,
+}
+
+impl<T: Clone> Clone for EventQueue<T> {
+    fn clone(&self) -> Self {
+        Self {
+            messages: self.messages.clone(),
+            capacity: self.capacity,
+            policy: self.policy,
+            head_seq: self.head_seq,
+            cursors: self
+                .cursors
+                .iter()
+                .map(|cursor| QueueCursor {
+                    reader_id: cursor.reader_id,
+                    next_seq: U64Cell::new(cursor.next_seq.load(Ordering::Acquire)),
+                })
+                .collect(),
+            dropped: self.dropped,
+        }
+    }
+}
+
+impl<T: Clone> EventQueue<T> {
+    pub(crate) fn new(capacity: usize, policy: DropPolicy) -> Self {
+        assert!(capacity > 0, "Queue channel capacity must be non-zero.");
+        Self {
+            messages: VecDeque::with_capacity(capacity),
+            capacity,
+            policy,
+            head_seq: 0,
+            cursors: Vec::new(),
+            dropped: 0,
+        }
+    }
+
+    /// Sequence number assigned to the next published message.
+    fn tail_seq(&self) -> u64 {
+        self.head_seq + self.messages.len() as u64
+    }
+
+    /// Add a cursor for the given reader starting at the next published message, returning the
+    /// index of the cursor. Repeated binds by the same reader share a cursor.
+    pub(crate) fn add_cursor(&mut self, reader_id: usize) -> usize {
+        if let Some(cursor_idx) = self
+            .cursors
+            .iter()
+            .position(|cursor| cursor.reader_id == reader_id)
+        {
+            return cursor_idx;
+        }
+
+        self.cursors.push(QueueCursor {
+            reader_id,
+            next_seq: U64Cell::new(self.tail_seq()),
+        });
+        self.cursors.len() - 1
+    }
+
+    /// Discard the messages every reader has read.
+    fn evict_read(&mut self) {
+        let read_seq = self
+            .cursors
+            .iter()
+            .map(|cursor| cursor.next_seq.load(Ordering::Acquire))
+            .min()
+            .unwrap_or(self.tail_seq());
+        while self.head_seq < read_seq && self.messages.pop_front().is_some() {
+            self.head_seq += 1;
+        }
+    }
+
+    fn push(&mut self, value: T) -> bool {
+        self.evict_read();
+        if self.messages.len() == self.capacity {
+            self.dropped += 1;
+            match self.policy {
+                DropPolicy::DropNewest => return false,
+                DropPolicy::DropOldest => {
+                    self.messages.pop_front();
+                    self.head_seq += 1;
+                }
+            }
+        }
+
+        self.messages.push_back(value);
+        true
+    }
+
+    /// Clone out every message the reader has not read and advance its cursor past them.
+    fn drain(&self, cursor_idx: usize) -> Vec<T> {
+        let cursor = &self.cursors[cursor_idx].next_seq;
+        let start = cursor.load(Ordering::Acquire).max(self.head_seq);
+        let messages = self
+            .messages
+            .iter()
+            .skip((start - self.head_seq) as usize)
+            .cloned()
+            .collect();
+        cursor.store(self.tail_seq(), Ordering::Release);
+        messages
+    }
+
+    fn pending(&self, cursor_idx: usize) -> usize {
+        let start = self.cursors[cursor_idx]
+            .next_seq
+            .load(Ordering::Acquire)
+            .max(self.head_seq);
+        (self.tail_seq() - start) as usize
+    }
+}
+
+/// View used to publish messages into a queue channel.
+pub struct QueuePublishView<'a, T: AnyClone> {
+    reg: &'a Reg<EventQueue<T>>,
+    /// Clock of the channel store used to stamp every published message as a channel write.
+    clock: &'a ChannelClock,
+}
+
+impl<'a, T: AnyClone> QueuePublishView<'a, T> {
+    pub(crate) fn new(reg: &'a Reg<EventQueue<T>>, clock: &'a ChannelClock) -> Self {
+        Self { reg, clock }
+    }
+
+    /// Publish a message to every reader of the queue channel. Returns false if the message was
+    /// discarded as the queue is full and the drop policy is DropNewest.
+    ///
+    /// With the concurrent feature the queue is triple buffered like other registers, every push
+    /// clones the retained messages into a spare buffer. Publishing costs O(capacity) rather than
+    /// O(1), large messages are best published behind an Arc.
+    pub fn push(&self, value: T) -> bool {
+        RegMutView::with_clock(self.reg, self.clock).update(|queue| queue.push(value))
+    }
+
+    /// Number of messages retained as not every reader has read them yet.
+    pub fn len(&self) -> usize {
+        RegReadView::new(self.reg).borrow().messages.len()
+    }
+
+    /// Returns true if every retained message has been read by every reader.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of messages discarded by the drop policy since the channel was registered.
+    pub fn dropped(&self) -> u64 {
+        RegReadView::new(self.reg).borrow().dropped
+    }
+}
+
+/// View used to read the messages of a queue channel through the cursor of a single reader.
+pub struct QueueSubscribeView<'a, T: AnyClone> {
+    reg: &'a Reg<EventQueue<T>>,
+    cursor_idx: usize,
+}
+
+impl<'a, T: AnyClone> QueueSubscribeView<'a, T> {
+    pub(crate) fn new(reg: &'a Reg<EventQueue<T>>, cursor_idx: usize) -> Self {
+        Self { reg, cursor_idx }
+    }
+
+    /// Clone out every message published since the previous drain of this reader, oldest first.
+    /// Messages discarded by the drop policy before being read are skipped.
+    pub fn drain(&self) -> Vec<T> {
+        RegReadView::new(self.reg).borrow().drain(self.cursor_idx)
+    }
+
+    /// Number of messages which the next drain of this reader would return.
+    pub fn pending(&self) -> usize {
+        RegReadView::new(self.reg).borrow().pending(self.cursor_idx)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use alloc::vec::Vec;
+
+    use super::{DropPolicy, EventQueue};
+
+    #[test]
+    fn test_queue_cursors() {
+        let mut queue = EventQueue::new(4, DropPolicy::DropNewest);
+        let fast = queue.add_cursor(1);
+        let slow = queue.add_cursor(2);
+        assert_eq!(queue.add_cursor(1), fast);
+
+        assert!(queue.push(1u8));
+        assert!(queue.push(2));
+        assert_eq!(queue.drain(fast), Vec::from([1, 2]));
+        assert!(queue.drain(fast).is_empty());
+
+        assert!(queue.push(3));
+        assert_eq!(queue.pending(slow), 3);
+        assert_eq!(queue.drain(slow), Vec::from([1, 2, 3]));
+        assert_eq!(queue.drain(fast), Vec::from([3]));
+
+        // Messages read by every reader are evicted on the next push.
+        assert!(queue.push(4));
+        assert_eq!(queue.messages.len(), 1);
+    }
+
+    #[test]
+    fn test_queue_drop_newest() {
+        let mut queue = EventQueue::new(2, DropPolicy::DropNewest);
+        let cursor = queue.add_cursor(1);
+        assert!(queue.push(1u8));
+        assert!(queue.push(2));
+        assert!(!queue.push(3));
+        assert_eq!(queue.dropped, 1);
+        assert_eq!(queue.drain(cursor), Vec::from([1, 2]));
+        assert!(queue.push(4));
+        assert_eq!(queue.drain(cursor), Vec::from([4]));
+    }
+
+    #[test]
+    fn test_queue_drop_oldest() {
+        let mut queue = EventQueue::new(2, DropPolicy::DropOldest);
+        let cursor = queue.add_cursor(1);
+        assert!(queue.push(1u8));
+        assert!(queue.push(2));
+        assert!(queue.push(3));
+        assert_eq!(queue.dropped, 1);
+        assert_eq!(queue.pending(cursor), 2);
+        assert_eq!(queue.drain(cursor), Vec::from([2, 3]));
+    }
+
+    #[test]
+    fn test_queue_without_readers() {
+        let mut queue = EventQueue::new(2, DropPolicy::DropNewest);
+        assert!(queue.push(1u8));
+        assert!(queue.push(2));
+        assert!(queue.push(3));
+        assert_eq!(queue.dropped, 0);
+    }
+}
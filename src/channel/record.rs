@@ -0,0 +1,414 @@
+use core::any::{self, TypeId};
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::{
+    reg::{AnyClone, ChannelClock, RegMutView, RegReadView},
+    slab::{AnySlab, RegSlab},
+};
+
+/// Channel value type which may be written into and read back from a compact binary record log.
+/// Values are encoded little endian without any type information, as such a value must be
+/// decoded as the type it was encoded from.
+pub trait Recordable: AnyClone {
+    /// Append the encoded value to the output buffer.
+    fn encode(&self, output: &mut Vec<u8>);
+
+    /// Decode a value from the front of the input, advancing the input past the decoded bytes.
+    /// Returns None if the input is too short or does not hold a valid value.
+    fn decode(input: &mut &[u8]) -> Option<Self>;
+
+    /// Append the type tag of the value type to the output. Record logs store the tag of each
+    /// channel to check that it is replayed as the type it was recorded from, as such the tag must
+    /// remain the same between builds, unlike core::any::type_name(). Generic types combine the
+    /// tags of their parameters, such as "Option<u32>".
+    fn type_tag(output: &mut String);
+}
+
+/// Append the type tag of a tuple with the given element tags to the output.
+fn tuple_tag(output: &mut String, elements: &[fn(&mut String)]) {
+    output.push('(');
+    for (idx, element) in elements.iter().enumerate() {
+        if idx > 0 {
+            output.push_str(", ");
+        }
+        element(output);
+    }
+    if elements.len() == 1 {
+        output.push(',');
+    }
+    output.push(')');
+}
+
+/// Split the first 'len' bytes off the front of the input.
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if input.len() < len {
+        return None;
+    }
+    let (head, tail) = input.split_at(len);
+    *input = tail;
+    Some(head)
+}
+
+macro_rules! impl_recordable_num {
+    ($($num:ty),*) => {
+        $(
+            impl Recordable for $num {
+                fn encode(&self, output: &mut Vec<u8>) {
+                    output.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(input: &mut &[u8]) -> Option<Self> {
+                    let bytes = take(input, size_of::<$num>())?;
+                    Some(<$num>::from_le_bytes(bytes.try_into().ok()?))
+                }
+
+                fn type_tag(output: &mut String) {
+                    output.push_str(stringify!($num));
+                }
+            }
+        )*
+    };
+}
+
+impl_recordable_num!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+// Pointer sized integers are recorded as 64 bit values so that logs are portable between targets.
+impl Recordable for usize {
+    fn encode(&self, output: &mut Vec<u8>) {
+        (*self as u64).encode(output);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        u64::decode(input)?.try_into().ok()
+    }
+
+    fn type_tag(output: &mut String) {
+        output.push_str("usize");
+    }
+}
+
+impl Recordable for isize {
+    fn encode(&self, output: &mut Vec<u8>) {
+        (*self as i64).encode(output);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        i64::decode(input)?.try_into().ok()
+    }
+
+    fn type_tag(output: &mut String) {
+        output.push_str("isize");
+    }
+}
+
+impl Recordable for bool {
+    fn encode(&self, output: &mut Vec<u8>) {
+        output.push(*self as u8);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        match u8::decode(input)? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    fn type_tag(output: &mut String) {
+        output.push_str("bool");
+    }
+}
+
+impl Recordable for () {
+    fn encode(&self, _output: &mut Vec<u8>) {}
+
+    fn decode(_input: &mut &[u8]) -> Option<Self> {
+        Some(())
+    }
+
+    fn type_tag(output: &mut String) {
+        output.push_str("()");
+    }
+}
+
+impl<T: Recordable> Recordable for Option<T> {
+    fn encode(&self, output: &mut Vec<u8>) {
+        self.is_some().encode(output);
+        if let Some(value) = self {
+            value.encode(output);
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        match bool::decode(input)? {
+            true => Some(Some(T::decode(input)?)),
+            false => Some(None),
+        }
+    }
+
+    fn type_tag(output: &mut String) {
+        output.push_str("Option<");
+        T::type_tag(output);
+        output.push('>');
+    }
+}
+
+impl<T: Recordable, const N: usize> Recordable for [T; N] {
+    fn encode(&self, output: &mut Vec<u8>) {
+        for value in self.iter() {
+            value.encode(output);
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let values = (0..N)
+            .map(|_| T::decode(input))
+            .collect::<Option<Vec<T>>>()?;
+        values.try_into().ok()
+    }
+
+    fn type_tag(output: &mut String) {
+        output.push('[');
+        T::type_tag(output);
+        output.push_str("; ");
+        output.push_str(&N.to_string());
+        output.push(']');
+    }
+}
+
+impl<T: Recordable> Recordable for Vec<T> {
+    fn encode(&self, output: &mut Vec<u8>) {
+        (self.len() as u32).encode(output);
+        for value in self.iter() {
+            value.encode(output);
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let len = u32::decode(input)? as usize;
+        // Every element occupies at least one byte unless it is zero sized, bound the allocation
+        // by the remaining input so that malformed lengths cannot exhaust memory.
+        let mut values = Vec::with_capacity(len.min(input.len()));
+        for _ in 0..len {
+            values.push(T::decode(input)?);
+        }
+        Some(values)
+    }
+
+    fn type_tag(output: &mut String) {
+        output.push_str("Vec<");
+        T::type_tag(output);
+        output.push('>');
+    }
+}
+
+impl Recordable for String {
+    fn encode(&self, output: &mut Vec<u8>) {
+        (self.len() as u32).encode(output);
+        output.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let len = u32::decode(input)? as usize;
+        String::from_utf8(take(input, len)?.to_vec()).ok()
+    }
+
+    fn type_tag(output: &mut String) {
+        output.push_str("String");
+    }
+}
+
+macro_rules! impl_recordable_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: Recordable),*> Recordable for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn encode(&self, output: &mut Vec<u8>) {
+                let ($($name,)*) = self;
+                $($name.encode(output);)*
+            }
+
+            fn decode(input: &mut &[u8]) -> Option<Self> {
+                Some(($($name::decode(input)?,)*))
+            }
+
+            fn type_tag(output: &mut String) {
+                tuple_tag(output, &[$($name::type_tag),*]);
+            }
+        }
+    };
+}
+
+impl_recordable_tuple!(A);
+impl_recordable_tuple!(A, B);
+impl_recordable_tuple!(A, B, C);
+impl_recordable_tuple!(A, B, C, D);
+
+/// Type erased encoder and decoder of the values of a channel holding a recordable type.
+#[derive(Clone, Copy)]
+pub(crate) struct RecordCodec {
+    type_id: TypeId,
+    type_name: &'static str,
+    type_tag: fn(&mut String),
+    encode: fn(&dyn AnySlab, usize, &mut Vec<u8>),
+    decode: fn(&dyn AnySlab, usize, &[u8], &ChannelClock) -> bool,
+    validate: fn(&[u8]) -> bool,
+}
+
+impl RecordCodec {
+    /// Codec of channels holding values of type T.
+    pub(crate) fn of<T: Recordable>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: any::type_name::<T>(),
+            type_tag: T::type_tag,
+            encode: encode_slot::<T>,
+            decode: decode_slot::<T>,
+            validate: validate_value::<T>,
+        }
+    }
+
+    /// Type id of the values handled by the codec.
+    pub(crate) fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Name of the type of the values handled by the codec.
+    pub(crate) fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Stable type tag of the values handled by the codec, see Recordable::type_tag().
+    pub(crate) fn type_tag(&self) -> String {
+        let mut tag = String::new();
+        (self.type_tag)(&mut tag);
+        tag
+    }
+
+    /// Append the encoded value of the register at the given slot of a slab to the output.
+    ///
+    /// ### Arguments
+    /// * 'slab' - Slab holding values of the codec type.
+    /// * 'slot' - Slot of the register to be encoded.
+    /// * 'output' - Buffer the encoded value is appended to.
+    ///
+    pub(crate) fn encode(&self, slab: &dyn AnySlab, slot: usize, output: &mut Vec<u8>) {
+        (self.encode)(slab, slot, output)
+    }
+
+    /// Decode a value into the register at the given slot of a slab, stamping the write with
+    /// the given clock. Returns false without writing if the input does not hold exactly one value.
+    ///
+    /// ### Arguments
+    /// * 'slab' - Slab holding values of the codec type.
+    /// * 'slot' - Slot of the register to be written.
+    /// * 'input' - Encoded value.
+    /// * 'clock' - Clock used to stamp the write.
+    ///
+    pub(crate) fn decode(
+        &self,
+        slab: &dyn AnySlab,
+        slot: usize,
+        input: &[u8],
+        clock: &ChannelClock,
+    ) -> bool {
+        (self.decode)(slab, slot, input, clock)
+    }
+
+    /// Returns true if the input holds exactly one value of the codec type, in which case
+    /// decode() succeeds on the same input.
+    ///
+    /// ### Argument
+    /// * 'input' - Encoded value.
+    ///
+    pub(crate) fn validate(&self, input: &[u8]) -> bool {
+        (self.validate)(input)
+    }
+}
+
+/// Downcast a type erased slab into the slab of the codec type.
+fn typed_slab<T: AnyClone>(slab: &dyn AnySlab) -> &RegSlab<T> {
+    // Codecs are only used with channels whose type was checked against T.
+    slab.as_any()
+        .downcast_ref::<RegSlab<T>>()
+        .expect("Record codec does not match the channel type.")
+}
+
+fn encode_slot<T: Recordable>(slab: &dyn AnySlab, slot: usize, output: &mut Vec<u8>) {
+    RegReadView::new(typed_slab::<T>(slab).reg(slot))
+        .borrow()
+        .encode(output);
+}
+
+/// Decode a value of type T which must span the whole input.
+fn decode_value<T: Recordable>(mut input: &[u8]) -> Option<T> {
+    T::decode(&mut input).filter(|_| input.is_empty())
+}
+
+fn validate_value<T: Recordable>(input: &[u8]) -> bool {
+    decode_value::<T>(input).is_some()
+}
+
+fn decode_slot<T: Recordable>(
+    slab: &dyn AnySlab,
+    slot: usize,
+    input: &[u8],
+    clock: &ChannelClock,
+) -> bool {
+    match decode_value::<T>(input) {
+        Some(value) => {
+            RegMutView::with_clock(typed_slab::<T>(slab).reg(slot), clock).set(value);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use alloc::{string::String, vec, vec::Vec};
+
+    use super::{RecordCodec, Recordable};
+
+    fn round_trip<T: Recordable + PartialEq + core::fmt::Debug>(value: T) {
+        let mut output = Vec::new();
+        value.encode(&mut output);
+        let mut input = output.as_slice();
+        assert_eq!(T::decode(&mut input), Some(value));
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn test_recordable_round_trip() {
+        round_trip(-3i16);
+        round_trip(1.5f64);
+        round_trip(usize::MAX);
+        round_trip(true);
+        round_trip([1u8, 2, 3]);
+        round_trip(Some((2u32, -1.25f32)));
+        round_trip(None::<u8>);
+        round_trip(vec![String::from("a"), String::from("bc")]);
+    }
+
+    #[test]
+    fn test_recordable_malformed() {
+        assert_eq!(u32::decode(&mut [1u8, 2].as_slice()), None);
+        assert_eq!(bool::decode(&mut [2u8].as_slice()), None);
+        assert_eq!(Vec::<u8>::decode(&mut [3u8, 0, 0, 0, 1].as_slice()), None);
+        assert_eq!(String::decode(&mut [1u8, 0, 0, 0, 0xff].as_slice()), None);
+    }
+
+    #[test]
+    fn test_recordable_type_tag() {
+        assert_eq!(RecordCodec::of::<u32>().type_tag(), "u32");
+        assert_eq!(
+            RecordCodec::of::<Option<Vec<(f64, bool)>>>().type_tag(),
+            "Option<Vec<(f64, bool)>>"
+        );
+        assert_eq!(RecordCodec::of::<[u8; 3]>().type_tag(), "[u8; 3]");
+        assert_eq!(RecordCodec::of::<(String,)>().type_tag(), "(String,)");
+    }
+}
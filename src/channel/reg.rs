@@ -1,111 +1,383 @@
 use core::{
-    any::{self, TypeId},
-    cell::RefCell,
-    marker::PhantomData,
-    mem,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+    sync::atomic::Ordering,
+    time::Duration,
 };
 
-use alloc::boxed::Box;
-use downcast;
-use dyn_clone::DynClone;
+use super::cell::{self, CellRef, CellRefMut, RegCell, U64Cell};
 
-pub trait AnyClone: downcast::Any + DynClone {}
-dyn_clone::clone_trait_object!(AnyClone);
-downcast::downcast!(dyn AnyClone);
+/// Bound required of every value stored within a channel.
+pub trait AnyClone: Clone + 'static {}
 
-impl<T: Clone + downcast::Any> AnyClone for T {}
+impl<T: Clone + 'static> AnyClone for T {}
 
-#[derive(Clone)]
-pub struct Reg {
-    reg_type: TypeId,
-    data: RefCell<Box<dyn AnyClone>>,
+/// Tick and timestamp of the dispatch in progress, shared by every register view produced by a
+/// channel store. Writes through a register view are stamped with the clock of the view.
+pub struct ChannelClock {
+    tick: U64Cell,
+    timestamp_nanos: U64Cell,
 }
 
-impl Reg {
-    pub fn new<T: 'static + AnyClone>(value: T) -> Self {
+impl Default for ChannelClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChannelClock {
+    pub(crate) const fn new() -> Self {
         Self {
-            reg_type: TypeId::of::<T>(),
-            data: RefCell::new(Box::new(value)),
+            tick: U64Cell::new(0),
+            timestamp_nanos: U64Cell::new(0),
         }
     }
 
-    pub fn matches_type<T: 'static>(&self) -> Result<(), ()> {
-        if TypeId::of::<T>() != self.reg_type {
-            return Err(());
+    /// Move the clock to the dispatch of the given tick.
+    pub(crate) fn advance(&self, tick: u64, timestamp: Duration) {
+        self.timestamp_nanos
+            .store(timestamp.as_nanos() as u64, Ordering::Relaxed);
+        self.tick.store(tick, Ordering::Release);
+    }
+
+    /// Tick of the dispatch in progress.
+    pub fn tick(&self) -> u64 {
+        self.tick.load(Ordering::Acquire)
+    }
+
+    /// Timestamp of the dispatch in progress.
+    pub fn timestamp(&self) -> Duration {
+        Duration::from_nanos(self.timestamp_nanos.load(Ordering::Relaxed))
+    }
+}
+
+/// Tick of the clock of a register view, views which were not produced by a channel store have
+/// no clock and remain at tick zero.
+fn view_tick(clock: Option<&ChannelClock>) -> u64 {
+    clock.map_or(0, ChannelClock::tick)
+}
+
+/// Write metadata of a register. Stamps are held in cells so that they can be read while the
+/// register value is borrowed by another view.
+struct WriteStamp {
+    /// Number of writes since the register was created.
+    sequence: U64Cell,
+    /// Tick of the last write.
+    tick: U64Cell,
+    /// Timestamp of the last write.
+    timestamp_nanos: U64Cell,
+}
+
+impl WriteStamp {
+    fn new() -> Self {
+        Self {
+            sequence: U64Cell::new(0),
+            tick: U64Cell::new(0),
+            timestamp_nanos: U64Cell::new(0),
         }
+    }
+
+    fn record(&self, clock: Option<&ChannelClock>) {
+        self.tick.store(view_tick(clock), Ordering::Relaxed);
+        self.timestamp_nanos.store(
+            clock.map_or(0, |clock| clock.timestamp_nanos.load(Ordering::Relaxed)),
+            Ordering::Relaxed,
+        );
+        self.sequence.fetch_add(1, Ordering::Release);
+    }
 
-        Ok(())
+    fn copy_from(&self, other: &WriteStamp) {
+        self.tick
+            .store(other.tick.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.timestamp_nanos.store(
+            other.timestamp_nanos.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+        self.sequence
+            .store(other.sequence.load(Ordering::Acquire), Ordering::Release);
     }
+}
 
-    fn matches_type_panic<T: 'static>(&self) {
-        if self.matches_type::<T>().is_err() {
-            panic!(
-                "Requested type of [{}] does not match register type.",
-                any::type_name::<T>()
-            )
+/// Captured value and write metadata of a register.
+pub(crate) struct RegSnapshot<T> {
+    value: T,
+    sequence: u64,
+    tick: u64,
+    timestamp_nanos: u64,
+}
+
+/// Register holding a single channel value. Registers are stored by value within typed
+/// slabs of the channel store, as such accessing a register requires no type check.
+///
+/// Borrows follow the same rules as a RefCell, any number of shared borrows or a single mutable
+/// borrow, conflicting borrows panic. With the concurrent feature registers are triple buffered,
+/// shared borrows observe the last published value while the register is mutably borrowed and
+/// only a second mutable borrow panics.
+pub struct Reg<T> {
+    data: RegCell<T>,
+    stamp: WriteStamp,
+}
+
+impl<T: AnyClone> Reg<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            data: RegCell::new(value),
+            stamp: WriteStamp::new(),
         }
     }
+}
 
-    /// Accessor method for the value contained within the register. This method
-    /// clones the contained value out to the caller.
-    fn get<T: 'static + AnyClone + Clone>(&self) -> T {
-        self.matches_type_panic::<T>();
-        self.data.borrow().downcast_ref::<T>().unwrap().clone()
+impl<T: Clone> Reg<T> {
+    fn borrow(&self) -> RegRef<'_, T> {
+        RegRef {
+            guard: self.data.borrow(),
+        }
     }
 
-    /// Set method for the value contained within the register. The contained value is
-    /// overwritten with the value specified by the caller.
-    fn set<T: 'static>(&self, value: T) {
-        self.matches_type_panic::<T>();
-        let _ = mem::replace(self.data.borrow_mut().downcast_mut().unwrap(), value);
+    fn borrow_mut(&self) -> RegRefMut<'_, T> {
+        RegRefMut {
+            guard: ManuallyDrop::new(self.data.borrow_mut()),
+            write: None,
+        }
+    }
+
+    /// Returns true if the register may be mutably borrowed, false while a conflicting borrow is
+    /// held.
+    pub(crate) fn is_writable(&self) -> bool {
+        cell::is_writable(&self.data)
+    }
+
+    /// Number of writes since the register was created.
+    pub(crate) fn write_sequence(&self) -> u64 {
+        self.stamp.sequence.load(Ordering::Acquire)
+    }
+
+    /// Tick of the last write, None while the register holds its initial value.
+    pub(crate) fn last_write_tick(&self) -> Option<u64> {
+        match self.write_sequence() {
+            0 => None,
+            _ => Some(self.stamp.tick.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Capture the value and write metadata of the register.
+    pub(crate) fn snapshot(&self) -> RegSnapshot<T> {
+        RegSnapshot {
+            value: self.borrow().clone(),
+            sequence: self.stamp.sequence.load(Ordering::Acquire),
+            tick: self.stamp.tick.load(Ordering::Relaxed),
+            timestamp_nanos: self.stamp.timestamp_nanos.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Overwrite the value and write metadata of the register with a previously captured state.
+    pub(crate) fn restore(&self, snapshot: &RegSnapshot<T>) {
+        self.borrow_mut().clone_from(&snapshot.value);
+        self.stamp.tick.store(snapshot.tick, Ordering::Relaxed);
+        self.stamp
+            .timestamp_nanos
+            .store(snapshot.timestamp_nanos, Ordering::Relaxed);
+        self.stamp
+            .sequence
+            .store(snapshot.sequence, Ordering::Release);
+    }
+
+    /// Overwrite the write metadata of this register with that of another register.
+    fn copy_stamp_from(&self, other: &Reg<T>) {
+        self.stamp.copy_from(&other.stamp);
+    }
+
+    /// Overwrite the value and write metadata of this register with those of another register,
+    /// used by registers holding copies of a channel value such as behind and history registers.
+    /// The copy is not recorded as a write.
+    pub(crate) fn copy_from(&self, other: &Reg<T>) {
+        self.borrow_mut().clone_from(&other.borrow());
+        self.copy_stamp_from(other);
+    }
+
+    /// Modify the value in place without recording a write, used for bookkeeping of readers such
+    /// as queue cursors.
+    pub(crate) fn update_unstamped<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.borrow_mut())
+    }
+}
+
+impl<T: AnyClone> Clone for Reg<T> {
+    fn clone(&self) -> Self {
+        let reg = Reg::new(self.borrow().clone());
+        reg.copy_stamp_from(self);
+        reg
+    }
+}
+
+/// Shared borrow guard of a register value.
+pub struct RegRef<'a, T> {
+    guard: CellRef<'a, T>,
+}
+
+impl<T> Deref for RegRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+/// Mutable borrow guard of a register value.
+pub struct RegRefMut<'a, T> {
+    guard: ManuallyDrop<CellRefMut<'a, T>>,
+    /// Stamp of the register and clock recording the write once the guard is released, None for
+    /// modifications which are not writes of the channel.
+    write: Option<(&'a WriteStamp, Option<&'a ChannelClock>)>,
+}
+
+impl<T> Deref for RegRefMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for RegRefMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<T> Drop for RegRefMut<'_, T> {
+    fn drop(&mut self) {
+        // SAFETY: The guard is not accessed after being dropped.
+        unsafe { ManuallyDrop::drop(&mut self.guard) };
+        // The write is recorded once the value is published, as such a reader observing the new
+        // stamp also observes the new value.
+        if let Some((stamp, clock)) = self.write {
+            stamp.record(clock);
+        }
     }
 }
 
 /// View used to access the register with read only permissions.
 pub struct RegReadView<'a, T: 'static + AnyClone + Clone> {
-    reg: &'a Reg,
-    phantom_marker: PhantomData<T>,
+    reg: &'a Reg<T>,
+    clock: Option<&'a ChannelClock>,
 }
 
 impl<'a, T: 'static + AnyClone + Clone> RegReadView<'a, T> {
-    pub fn new(reg: &'a Reg) -> Self {
+    pub fn new(reg: &'a Reg<T>) -> Self {
+        Self { reg, clock: None }
+    }
+
+    pub(crate) fn with_clock(reg: &'a Reg<T>, clock: &'a ChannelClock) -> Self {
         Self {
             reg,
-            phantom_marker: PhantomData,
+            clock: Some(clock),
         }
     }
 
+    /// Number of writes made to the register, zero while it holds its initial value.
+    pub fn sequence(&self) -> u64 {
+        self.reg.write_sequence()
+    }
+
+    /// Number of ticks since the last write, None while the register holds its initial value.
+    pub fn age_ticks(&self) -> Option<u64> {
+        self.reg
+            .last_write_tick()
+            .map(|tick| view_tick(self.clock).saturating_sub(tick))
+    }
+
+    /// Returns true if the register was written during the tick in progress.
+    pub fn is_fresh(&self) -> bool {
+        self.age_ticks() == Some(0)
+    }
+
+    /// Timestamp of the dispatch which last wrote the register, None while the register holds
+    /// its initial value.
+    pub fn last_write_timestamp(&self) -> Option<Duration> {
+        if self.sequence() == 0 {
+            return None;
+        }
+
+        Some(Duration::from_nanos(
+            self.reg.stamp.timestamp_nanos.load(Ordering::Relaxed),
+        ))
+    }
+
+    /// Accessor method for the value contained within the register. This method
+    /// clones the contained value out to the caller.
     pub fn get(&self) -> T {
-        self.reg.get()
+        self.reg.borrow().clone()
+    }
+
+    /// Borrow the value contained within the register without cloning it. The register
+    /// cannot be written while the returned guard is held, unless the concurrent feature is
+    /// enabled in which case the guard keeps the value published when it was borrowed.
+    pub fn borrow(&self) -> RegRef<'a, T> {
+        self.reg.borrow()
     }
 }
 
 /// View used to access the register with read and write permissions.
 pub struct RegMutView<'a, T: 'static + AnyClone + Clone> {
-    reg: &'a Reg,
-    phantom_marker: PhantomData<T>,
+    reg: &'a Reg<T>,
+    clock: Option<&'a ChannelClock>,
 }
 
 impl<'a, T: 'static + AnyClone + Clone> RegMutView<'a, T> {
-    pub fn new(reg: &'a Reg) -> Self {
+    pub fn new(reg: &'a Reg<T>) -> Self {
+        Self { reg, clock: None }
+    }
+
+    pub(crate) fn with_clock(reg: &'a Reg<T>, clock: &'a ChannelClock) -> Self {
         Self {
             reg,
-            phantom_marker: PhantomData,
+            clock: Some(clock),
         }
     }
 
+    /// Accessor method for the value contained within the register. This method
+    /// clones the contained value out to the caller.
     pub fn get(&self) -> T {
-        self.reg.get()
+        self.reg.borrow().clone()
     }
 
+    /// Set method for the value contained within the register. The contained value is
+    /// overwritten with the value specified by the caller.
     pub fn set(&self, value: T) {
-        self.reg.set(value)
+        *self.borrow_mut() = value;
+    }
+
+    /// Borrow the value contained within the register without cloning it. The register
+    /// cannot be written while the returned guard is held, unless the concurrent feature is
+    /// enabled in which case the guard keeps the value published when it was borrowed.
+    pub fn borrow(&self) -> RegRef<'a, T> {
+        self.reg.borrow()
+    }
+
+    /// Mutably borrow the value contained within the register for in place modification.
+    /// The register cannot be accessed through any other view while the returned guard is held,
+    /// unless the concurrent feature is enabled in which case other views observe the previous
+    /// value until the guard is dropped. Every mutable borrow counts as a write of the register,
+    /// recorded when the guard is dropped.
+    pub fn borrow_mut(&self) -> RegRefMut<'a, T> {
+        let mut guard = self.reg.borrow_mut();
+        guard.write = Some((&self.reg.stamp, self.clock));
+        guard
+    }
+
+    /// Modify the value contained within the register in place using the provided closure.
+    pub fn update<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.borrow_mut())
     }
 }
 
 #[cfg(test)]
 mod unit_tests {
-    use super::Reg;
+    use alloc::vec::Vec;
+    use core::time::Duration;
+
+    use super::{ChannelClock, Reg, RegMutView, RegReadView};
 
     #[derive(Clone, PartialEq, Debug)]
     struct TestStruct(u8);
@@ -113,36 +385,211 @@ mod unit_tests {
     #[test]
     fn test_reg_normal() {
         let reg = Reg::new(true);
-        let mut get_reg: bool = reg.get();
+        let mut get_reg: bool = RegReadView::new(&reg).get();
         assert!(get_reg);
-        reg.set(false);
-        get_reg = reg.get();
+        RegMutView::new(&reg).set(false);
+        get_reg = RegReadView::new(&reg).get();
         assert!(!get_reg);
     }
 
     #[test]
     fn test_reg_struct() {
         let reg = Reg::new(TestStruct(90));
-        let mut get_reg: TestStruct = reg.get();
+        let mut get_reg: TestStruct = RegReadView::new(&reg).get();
         assert_eq!(get_reg, TestStruct(90));
-        reg.set(TestStruct(100));
-        get_reg = reg.get();
+        RegMutView::new(&reg).set(TestStruct(100));
+        get_reg = RegReadView::new(&reg).get();
         assert_eq!(get_reg, TestStruct(100));
     }
 
     #[test]
-    #[should_panic(expected = "Requested type of [i32] does not match register type.")]
-    fn test_set_reg_type_mismatch() {
-        let reg = Reg::new(true);
-        let get_reg: bool = reg.get();
-        assert!(get_reg);
-        reg.set(0);
+    fn test_reg_borrow() {
+        let reg = Reg::new(Vec::from([1u32, 2, 3]));
+        let read_view = RegReadView::new(&reg);
+        let write_view = RegMutView::new(&reg);
+
+        assert_eq!(read_view.borrow().len(), 3);
+        write_view.borrow_mut().push(4);
+        assert_eq!(*read_view.borrow(), Vec::from([1u32, 2, 3, 4]));
+
+        let sum: u32 = write_view.update(|value| {
+            value[0] = 10;
+            value.iter().sum()
+        });
+        assert_eq!(sum, 19);
+        assert_eq!(write_view.borrow()[0], 10);
     }
 
     #[test]
-    #[should_panic(expected = "Requested type of [u8] does not match register type.")]
-    fn test_get_reg_type_mismatch() {
-        let reg = Reg::new(true);
-        let _get_reg: u8 = reg.get();
+    #[cfg(not(feature = "concurrent"))]
+    #[should_panic(expected = "already borrowed")]
+    fn test_reg_borrow_conflict() {
+        let reg = Reg::new(TestStruct(90));
+        let read_view = RegReadView::new(&reg);
+        let write_view = RegMutView::new(&reg);
+
+        let _guard = read_view.borrow();
+        write_view.set(TestStruct(100));
+    }
+
+    #[test]
+    #[cfg(not(feature = "concurrent"))]
+    #[should_panic(expected = "already mutably borrowed")]
+    fn test_reg_borrow_mut_conflict() {
+        let reg = Reg::new(TestStruct(90));
+        let read_view = RegReadView::new(&reg);
+        let write_view = RegMutView::new(&reg);
+
+        let _guard = write_view.borrow_mut();
+        read_view.get();
+    }
+
+    #[test]
+    fn test_reg_write_stamp() {
+        let clock = ChannelClock::new();
+        let reg = Reg::new(TestStruct(90));
+        let read_view = RegReadView::with_clock(&reg, &clock);
+        let write_view = RegMutView::with_clock(&reg, &clock);
+        assert_eq!(read_view.sequence(), 0);
+        assert_eq!(read_view.age_ticks(), None);
+        assert!(!read_view.is_fresh());
+        assert_eq!(read_view.last_write_timestamp(), None);
+
+        clock.advance(3, Duration::from_millis(30));
+        write_view.set(TestStruct(100));
+        write_view.update(|value| value.0 += 1);
+        assert_eq!(read_view.sequence(), 2);
+        assert!(read_view.is_fresh());
+        assert_eq!(
+            read_view.last_write_timestamp(),
+            Some(Duration::from_millis(30))
+        );
+
+        clock.advance(5, Duration::from_millis(50));
+        assert_eq!(read_view.age_ticks(), Some(2));
+        assert!(!read_view.is_fresh());
+
+        // Clones carry the write metadata of the original register.
+        let clone = reg.clone();
+        assert_eq!(RegReadView::with_clock(&clone, &clock).sequence(), 2);
+
+        // Writes through a mutable borrow are recorded once the borrow is released.
+        let mut guard = write_view.borrow_mut();
+        guard.0 = 110;
+        assert_eq!(read_view.sequence(), 2);
+        drop(guard);
+        assert_eq!(read_view.sequence(), 3);
+        assert!(read_view.is_fresh());
+    }
+
+    #[test]
+    fn test_reg_local_value() {
+        use alloc::rc::Rc;
+        use core::cell::Cell;
+
+        // Channel values need not be Send or Sync.
+        let shared = Rc::new(Cell::new(1u32));
+        let reg = Reg::new(shared.clone());
+        RegReadView::new(&reg).get().set(2);
+        assert_eq!(shared.get(), 2);
+    }
+
+    #[test]
+    fn test_reg_borrow_release() {
+        let reg = Reg::new(TestStruct(90));
+        let read_view = RegReadView::new(&reg);
+        let write_view = RegMutView::new(&reg);
+
+        {
+            let _guard_1 = read_view.borrow();
+            let _guard_2 = read_view.borrow();
+        }
+        write_view.borrow_mut().0 = 100;
+        assert_eq!(read_view.get(), TestStruct(100));
+    }
+
+    #[test]
+    #[cfg(feature = "concurrent")]
+    fn test_reg_concurrent_borrow() {
+        let reg = Reg::new(Vec::from([1u32]));
+        let read_view = RegReadView::new(&reg);
+        let write_view = RegMutView::new(&reg);
+
+        // Writes progress while a shared borrow is held, the borrow keeps its value.
+        let guard = read_view.borrow();
+        for value in 2..=100 {
+            write_view.set(Vec::from([value; 4]));
+        }
+        assert_eq!(*guard, Vec::from([1u32]));
+        drop(guard);
+
+        // Shared borrows observe the published value while a mutable borrow is held.
+        let mut write_guard = write_view.borrow_mut();
+        write_guard.push(0);
+        assert_eq!(read_view.get(), Vec::from([100u32; 4]));
+        drop(write_guard);
+        assert_eq!(read_view.get(), Vec::from([100u32, 100, 100, 100, 0]));
+    }
+
+    #[test]
+    #[cfg(feature = "concurrent")]
+    #[should_panic(expected = "already mutably borrowed")]
+    fn test_reg_concurrent_borrow_mut_conflict() {
+        let reg = Reg::new(TestStruct(90));
+        let write_view = RegMutView::new(&reg);
+
+        let _guard = write_view.borrow_mut();
+        write_view.set(TestStruct(100));
+    }
+
+    #[test]
+    #[cfg(all(feature = "concurrent", feature = "std"))]
+    fn test_reg_concurrent_access() {
+        use std::sync::mpsc;
+
+        let reg = &Reg::new(Vec::from([0u64]));
+        let (held_sender, held_receiver) = mpsc::channel();
+        let (written_sender, written_receiver) = mpsc::channel();
+        std::thread::scope(|scope| {
+            scope.spawn(move || {
+                // The writer completes every write while the reader holds a borrow.
+                held_receiver.recv().unwrap();
+                let write_view = RegMutView::new(reg);
+                for value in 1..=1000u64 {
+                    write_view.set(Vec::from_iter(core::iter::repeat_n(
+                        value,
+                        value as usize % 64 + 1,
+                    )));
+                }
+                written_sender.send(()).unwrap();
+            });
+            scope.spawn(move || {
+                let read_view = RegReadView::new(reg);
+                let guard = read_view.borrow();
+                held_sender.send(()).unwrap();
+                written_receiver
+                    .recv_timeout(std::time::Duration::from_secs(10))
+                    .expect("Writer stalled by a held borrow.");
+                assert_eq!(*guard, Vec::from([0u64]));
+                drop(guard);
+                assert_eq!(read_view.get().len(), 1000 % 64 + 1);
+            });
+            scope.spawn(move || {
+                let read_view = RegReadView::new(reg);
+                let mut last = 0;
+                while last < 1000 {
+                    let sequence = read_view.sequence();
+                    let value = read_view.borrow();
+                    // Writes are stamped once published, the value is never older than the
+                    // sequence observed before it.
+                    assert!(value[0] >= sequence);
+                    // Reads never observe a partially written value.
+                    assert_eq!(value.len(), value[0] as usize % 64 + 1);
+                    assert!(value.iter().all(|elem| *elem == value[0]));
+                    assert!(value[0] >= last);
+                    last = value[0];
+                }
+            });
+        });
     }
 }
@@ -0,0 +1,463 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Serialize, ser};
+use serde_json::{Error, Map, Value, json};
+
+/// Derive a JSON schema describing the type of a serializable value.
+///
+/// The schema follows the serde data model of the type rather than the JSON encoding of the
+/// value, as such tuples and fixed-size arrays are described element by element and optional
+/// values accept null. Sequences and maps are described by their first element.
+///
+/// Some values do not describe their whole type, they are described by a permissive schema
+/// rather than one which would reject other values of the same type:
+/// * Options holding None accept any value.
+/// * Empty sequences and maps accept any array or object.
+/// * Enum values, which only describe a single variant, accept any value.
+///
+/// An error is only returned if the Serialize implementation of the value fails.
+///
+/// ### Argument
+/// * 'value' - Value whose type is described.
+///
+pub(crate) fn derive_schema<T: Serialize + ?Sized>(value: &T) -> Result<Value, Error> {
+    value.serialize(SchemaSerializer)
+}
+
+/// Schema accepting any value.
+fn any_schema() -> Value {
+    Value::Object(Map::new())
+}
+
+/// Serializer producing the JSON schema of the serialized value.
+struct SchemaSerializer;
+
+impl ser::Serializer for SchemaSerializer {
+    type Ok = Value;
+    type Error = Error;
+    type SerializeSeq = SeqSchema;
+    type SerializeTuple = TupleSchema;
+    type SerializeTupleStruct = TupleSchema;
+    type SerializeTupleVariant = AnySchema;
+    type SerializeMap = MapSchema;
+    type SerializeStruct = StructSchema;
+    type SerializeStructVariant = AnySchema;
+
+    fn serialize_bool(self, _v: bool) -> Result<Value, Error> {
+        Ok(json!({ "type": "boolean" }))
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<Value, Error> {
+        Ok(json!({ "type": "integer" }))
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<Value, Error> {
+        Ok(json!({ "type": "integer" }))
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<Value, Error> {
+        Ok(json!({ "type": "integer" }))
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<Value, Error> {
+        Ok(json!({ "type": "integer" }))
+    }
+
+    fn serialize_i128(self, _v: i128) -> Result<Value, Error> {
+        Ok(json!({ "type": "integer" }))
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<Value, Error> {
+        Ok(json!({ "type": "integer" }))
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<Value, Error> {
+        Ok(json!({ "type": "integer" }))
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<Value, Error> {
+        Ok(json!({ "type": "integer" }))
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<Value, Error> {
+        Ok(json!({ "type": "integer" }))
+    }
+
+    fn serialize_u128(self, _v: u128) -> Result<Value, Error> {
+        Ok(json!({ "type": "integer" }))
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<Value, Error> {
+        Ok(json!({ "type": "number" }))
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<Value, Error> {
+        Ok(json!({ "type": "number" }))
+    }
+
+    fn serialize_char(self, _v: char) -> Result<Value, Error> {
+        Ok(json!({ "type": "string" }))
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<Value, Error> {
+        Ok(json!({ "type": "string" }))
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Value, Error> {
+        // Bytes are encoded as an array of integers.
+        Ok(json!({ "type": "array", "items": { "type": "integer" } }))
+    }
+
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(any_schema())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, Error> {
+        Ok(json!({ "anyOf": [derive_schema(value)?, { "type": "null" }] }))
+    }
+
+    fn serialize_unit(self) -> Result<Value, Error> {
+        Ok(json!({ "type": "null" }))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, Error> {
+        Ok(json!({ "type": "null" }))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Value, Error> {
+        Ok(any_schema())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        derive_schema(value)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Value, Error> {
+        Ok(any_schema())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SeqSchema, Error> {
+        Ok(SeqSchema { items: None })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<TupleSchema, Error> {
+        Ok(TupleSchema {
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<TupleSchema, Error> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<AnySchema, Error> {
+        Ok(AnySchema)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSchema, Error> {
+        Ok(MapSchema { values: None })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<StructSchema, Error> {
+        Ok(StructSchema {
+            properties: Map::new(),
+            required: Vec::new(),
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<AnySchema, Error> {
+        Ok(AnySchema)
+    }
+}
+
+/// Schema of an enum variant with fields, which accepts any value as the other variants of the
+/// enum are unknown.
+struct AnySchema;
+
+impl ser::SerializeTupleVariant for AnySchema {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, _value: &T) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(any_schema())
+    }
+}
+
+impl ser::SerializeStructVariant for AnySchema {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        _value: &T,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(any_schema())
+    }
+}
+
+/// Schema of a sequence, described by its first element.
+struct SeqSchema {
+    items: Option<Value>,
+}
+
+impl ser::SerializeSeq for SeqSchema {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        if self.items.is_none() {
+            self.items = Some(derive_schema(value)?);
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        match self.items {
+            Some(items) => Ok(json!({ "type": "array", "items": items })),
+            None => Ok(json!({ "type": "array" })),
+        }
+    }
+}
+
+/// Schema of a tuple or fixed-size array, described element by element.
+struct TupleSchema {
+    items: Vec<Value>,
+}
+
+impl TupleSchema {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.items.push(derive_schema(value)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Value {
+        let len = self.items.len();
+        json!({
+            "type": "array",
+            "prefixItems": self.items,
+            "minItems": len,
+            "maxItems": len,
+        })
+    }
+}
+
+impl ser::SerializeTuple for TupleSchema {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for TupleSchema {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(self.finish())
+    }
+}
+
+/// Schema of a map, keys are encoded as strings and values are described by the first value.
+struct MapSchema {
+    values: Option<Value>,
+}
+
+impl ser::SerializeMap for MapSchema {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, _key: &T) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        if self.values.is_none() {
+            self.values = Some(derive_schema(value)?);
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        match self.values {
+            Some(values) => Ok(json!({ "type": "object", "additionalProperties": values })),
+            None => Ok(json!({ "type": "object" })),
+        }
+    }
+}
+
+/// Schema of a struct, every serialized field is required.
+struct StructSchema {
+    properties: Map<String, Value>,
+    required: Vec<String>,
+}
+
+impl ser::SerializeStruct for StructSchema {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.properties
+            .insert(key.to_string(), derive_schema(value)?);
+        self.required.push(key.to_string());
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(json!({
+            "type": "object",
+            "properties": self.properties,
+            "required": self.required,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+
+    use serde::{Serialize, Serializer, ser::SerializeStruct};
+    use serde_json::json;
+
+    use super::derive_schema;
+
+    struct TestPose {
+        position: [f32; 2],
+        valid: Option<bool>,
+    }
+    impl Serialize for TestPose {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("TestPose", 2)?;
+            state.serialize_field("position", &self.position)?;
+            state.serialize_field("valid", &self.valid)?;
+            state.end()
+        }
+    }
+
+    struct TestMode;
+    impl Serialize for TestMode {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_unit_variant("TestMode", 0, "Idle")
+        }
+    }
+
+    struct TestFailing;
+    impl Serialize for TestFailing {
+        fn serialize<S: Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("test failure"))
+        }
+    }
+
+    #[test]
+    fn test_derive_schema() {
+        assert_eq!(
+            derive_schema(&(0.5f64, 1u32)).unwrap(),
+            json!({
+                "type": "array",
+                "prefixItems": [{ "type": "number" }, { "type": "integer" }],
+                "minItems": 2,
+                "maxItems": 2,
+            })
+        );
+        assert_eq!(
+            derive_schema(&TestPose {
+                position: [1.0, 2.0],
+                valid: Some(true),
+            })
+            .unwrap()["properties"]["valid"],
+            json!({ "anyOf": [{ "type": "boolean" }, { "type": "null" }] })
+        );
+        assert_eq!(
+            derive_schema(&Vec::from([1u8, 2])).unwrap(),
+            json!({ "type": "array", "items": { "type": "integer" } })
+        );
+        assert_eq!(
+            derive_schema(&BTreeMap::from([("x".to_string(), 1.5f64)])).unwrap(),
+            json!({ "type": "object", "additionalProperties": { "type": "number" } })
+        );
+    }
+
+    #[test]
+    fn test_derive_schema_permissive() {
+        assert_eq!(derive_schema(&None::<u32>).unwrap(), json!({}));
+        assert_eq!(
+            derive_schema(&Vec::<u32>::new()).unwrap(),
+            json!({ "type": "array" })
+        );
+        assert_eq!(
+            derive_schema(&BTreeMap::<u32, u32>::new()).unwrap(),
+            json!({ "type": "object" })
+        );
+        assert_eq!(derive_schema(&TestMode).unwrap(), json!({}));
+        // Permissive schemas are also used when nested.
+        assert_eq!(
+            derive_schema(&TestPose {
+                position: [1.0, 2.0],
+                valid: None,
+            })
+            .unwrap()["properties"]["valid"],
+            json!({})
+        );
+    }
+
+    #[test]
+    fn test_derive_schema_serialize_error() {
+        assert!(
+            derive_schema(&TestFailing)
+                .unwrap_err()
+                .to_string()
+                .contains("test failure")
+        );
+    }
+}
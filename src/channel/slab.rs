@@ -0,0 +1,189 @@
+use core::any::{self, Any, TypeId};
+
+use alloc::{boxed::Box, vec::Vec};
+
+use super::reg::{AnyClone, Reg, RegSnapshot};
+
+/// Captured register state of a slab.
+pub(crate) type SlabSnapshot = Box<dyn Any>;
+
+/// Type erased operations on a register slab. These operations are used by the channel store
+/// when the concrete type of a slab is not known, such as when updating behind registers.
+pub(crate) trait AnySlab {
+    /// Type id of the values held within the slab.
+    fn reg_type_id(&self) -> TypeId;
+
+    /// Name of the type of the values held within the slab.
+    fn reg_type_name(&self) -> &'static str;
+
+    /// Obtain the slab as Any for downcasting into its concrete type.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Push a clone of the register at the source slot into the slab, returning the new slot.
+    fn push_clone(&mut self, src_slot: usize) -> usize;
+
+    /// Overwrite the register at the destination slot with a clone of the source slot, including
+    /// its write metadata.
+    fn clone_slot(&self, src_slot: usize, dst_slot: usize);
+
+    /// Capture the value and write metadata of every register within the slab.
+    fn snapshot(&self) -> SlabSnapshot;
+
+    /// Returns true if a state captured by snapshot() matches the slab layout.
+    fn matches_snapshot(&self, snapshot: &dyn Any) -> bool;
+
+    /// Slot of a register which cannot be written as it is borrowed, None if every register of
+    /// the slab may be written.
+    fn borrowed_slot(&self) -> Option<usize>;
+
+    /// Overwrite every register within the slab with a state captured by snapshot(). Returns
+    /// false without modifying any register if the state does not match the slab layout.
+    fn restore(&self, snapshot: &dyn Any) -> bool;
+
+    /// Number of writes of the register at the given slot.
+    fn write_sequence(&self, slot: usize) -> u64;
+
+    /// Tick of the last write of the register at the given slot, None if it was never written.
+    fn last_write_tick(&self, slot: usize) -> Option<u64>;
+}
+
+/// Contiguous storage of all registers holding values of a single type.
+pub(crate) struct RegSlab<T> {
+    regs: Vec<Reg<T>>,
+}
+
+impl<T: AnyClone> Default for RegSlab<T> {
+    fn default() -> Self {
+        Self { regs: Vec::new() }
+    }
+}
+
+impl<T: AnyClone> RegSlab<T> {
+    /// Push a new register into the slab, returning the slot of the register.
+    pub(crate) fn push(&mut self, value: T) -> usize {
+        self.regs.push(Reg::new(value));
+        self.regs.len() - 1
+    }
+
+    pub(crate) fn reg(&self, slot: usize) -> &Reg<T> {
+        &self.regs[slot]
+    }
+}
+
+impl<T: AnyClone> AnySlab for RegSlab<T> {
+    fn reg_type_id(&self) -> TypeId {
+        TypeId::of::<T>()
+    }
+
+    fn reg_type_name(&self) -> &'static str {
+        any::type_name::<T>()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn push_clone(&mut self, src_slot: usize) -> usize {
+        let reg = self.regs[src_slot].clone();
+        self.regs.push(reg);
+        self.regs.len() - 1
+    }
+
+    fn clone_slot(&self, src_slot: usize, dst_slot: usize) {
+        self.regs[dst_slot].copy_from(&self.regs[src_slot]);
+    }
+
+    fn snapshot(&self) -> SlabSnapshot {
+        Box::new(
+            self.regs
+                .iter()
+                .map(|reg| reg.snapshot())
+                .collect::<Vec<RegSnapshot<T>>>(),
+        )
+    }
+
+    fn matches_snapshot(&self, snapshot: &dyn Any) -> bool {
+        snapshot
+            .downcast_ref::<Vec<RegSnapshot<T>>>()
+            .is_some_and(|regs| regs.len() == self.regs.len())
+    }
+
+    fn borrowed_slot(&self) -> Option<usize> {
+        self.regs.iter().position(|reg| !reg.is_writable())
+    }
+
+    fn restore(&self, snapshot: &dyn Any) -> bool {
+        match snapshot.downcast_ref::<Vec<RegSnapshot<T>>>() {
+            Some(regs) if regs.len() == self.regs.len() => {
+                for (reg, reg_snapshot) in self.regs.iter().zip(regs.iter()) {
+                    reg.restore(reg_snapshot);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn write_sequence(&self, slot: usize) -> u64 {
+        self.regs[slot].write_sequence()
+    }
+
+    fn last_write_tick(&self, slot: usize) -> Option<u64> {
+        self.regs[slot].last_write_tick()
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use core::any::TypeId;
+
+    use crate::channel::reg::{RegMutView, RegReadView};
+
+    use super::{AnySlab, RegSlab};
+
+    #[test]
+    fn test_slab_clone_slot() {
+        let mut slab = RegSlab::<u32>::default();
+        assert_eq!(slab.push(10), 0);
+        assert_eq!(slab.push(20), 1);
+        assert_eq!(slab.push_clone(0), 2);
+        assert_eq!(RegReadView::new(slab.reg(2)).get(), 10);
+
+        slab.clone_slot(1, 2);
+        assert_eq!(RegReadView::new(slab.reg(2)).get(), 20);
+        assert_eq!(slab.reg_type_id(), TypeId::of::<u32>());
+        assert_eq!(slab.reg_type_name(), "u32");
+    }
+
+    #[test]
+    fn test_slab_snapshot() {
+        let mut slab = RegSlab::<u32>::default();
+        slab.push(10);
+        let snapshot = slab.snapshot();
+        RegMutView::new(slab.reg(0)).set(20);
+        assert_eq!(RegReadView::new(slab.reg(0)).sequence(), 1);
+
+        assert!(slab.restore(snapshot.as_ref()));
+        assert_eq!(RegReadView::new(slab.reg(0)).get(), 10);
+        assert_eq!(RegReadView::new(slab.reg(0)).sequence(), 0);
+
+        slab.push(30);
+        assert!(!slab.matches_snapshot(snapshot.as_ref()));
+        assert!(!slab.restore(snapshot.as_ref()));
+        assert!(!slab.restore(RegSlab::<u8>::default().snapshot().as_ref()));
+    }
+
+    #[test]
+    fn test_slab_borrowed_slot() {
+        let mut slab = RegSlab::<u32>::default();
+        slab.push(10);
+        slab.push(20);
+        assert_eq!(slab.borrowed_slot(), None);
+
+        let write_view = RegMutView::new(slab.reg(1));
+        let guard = write_view.borrow_mut();
+        assert_eq!(slab.borrowed_slot(), Some(1));
+        drop(guard);
+        assert_eq!(slab.borrowed_slot(), None);
+    }
+}
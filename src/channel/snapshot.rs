@@ -0,0 +1,100 @@
+use core::{any::TypeId, fmt, time::Duration};
+
+use alloc::{string::String, vec::Vec};
+
+use super::slab::SlabSnapshot;
+
+/// Name and value type of a channel captured within a snapshot.
+pub(crate) struct SnapshotChannel {
+    pub name: String,
+    pub type_id: TypeId,
+    pub type_name: &'static str,
+}
+
+/// Opaque capture of every register of a channel store, behind and history registers included,
+/// produced by ChannelStore::snapshot(). A snapshot may be restored into any store holding the
+/// same channels, such as the store of a runner rebuilt with the same components.
+pub struct StoreSnapshot {
+    pub(crate) channels: Vec<SnapshotChannel>,
+    /// Captured registers of every typed slab.
+    pub(crate) slabs: Vec<SlabSnapshot>,
+    /// Ring index of every history ring.
+    pub(crate) history_heads: Vec<usize>,
+    /// Tick and timestamp of the channel store clock.
+    pub(crate) clock: (u64, Duration),
+    /// Stale flag of every staleness limit.
+    pub(crate) stale_flags: Vec<bool>,
+    /// Reported flag of every behind register trailing a channel which was never written.
+    pub(crate) unwritten_reported: Vec<bool>,
+}
+
+impl StoreSnapshot {
+    /// Names of the captured channels, in registration order.
+    pub fn channel_names(&self) -> impl Iterator<Item = &str> {
+        self.channels.iter().map(|channel| channel.name.as_str())
+    }
+}
+
+/// Error produced when a snapshot does not match the channel store it is restored into.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SnapshotError {
+    /// The number of channels within the snapshot and the store differ.
+    ChannelCount { expected: usize, actual: usize },
+    /// A channel of the store is registered under a different name within the snapshot.
+    ChannelName { expected: String, actual: String },
+    /// A channel of the store holds a different type within the snapshot.
+    ChannelType {
+        name: String,
+        expected: &'static str,
+        actual: &'static str,
+    },
+    /// Behind, history or queue registers of the store differ from the snapshot, such as when
+    /// components bound a different set of behind channels.
+    RegisterLayout,
+    /// A register of the channel is borrowed, as such it cannot be restored.
+    RegisterBorrowed { name: String },
+    /// The number of components within the snapshot and the runner differ.
+    ComponentCount { expected: usize, actual: usize },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::ChannelCount { expected, actual } => write!(
+                f,
+                "Snapshot holds [{}] channels but the store holds [{}].",
+                actual, expected
+            ),
+            SnapshotError::ChannelName { expected, actual } => write!(
+                f,
+                "Snapshot channel [{}] does not match store channel [{}].",
+                actual, expected
+            ),
+            SnapshotError::ChannelType {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Snapshot channel [{}] holds type [{}] but the store channel holds type [{}].",
+                name, actual, expected
+            ),
+            SnapshotError::RegisterLayout => write!(
+                f,
+                "Snapshot registers do not match the registers of the store."
+            ),
+            SnapshotError::RegisterBorrowed { name } => write!(
+                f,
+                "Register of channel [{}] is borrowed and cannot be restored.",
+                name
+            ),
+            SnapshotError::ComponentCount { expected, actual } => write!(
+                f,
+                "Snapshot holds [{}] components but the runner holds [{}].",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl core::error::Error for SnapshotError {}
@@ -1,15 +1,51 @@
-use alloc::{string::String, vec::Vec};
+#[cfg(feature = "concurrent")]
+use core::mem::ManuallyDrop;
+use core::{
+    any::{self, TypeId},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    rc::Rc,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(feature = "concurrent")]
+use alloc::sync::Arc;
 
 use crate::{
     channel::token::ChannelTokenOps,
     system::order::{NodeDependency, NodeGraph},
 };
 
+#[cfg(feature = "serde")]
+use super::codec::{ChannelCodec, EncodeError};
+use super::record::RecordCodec;
+
 use super::{
-    reg::{AnyClone, Reg, RegMutView, RegReadView},
-    token::{ChannelBehindToken, ChannelOwnerToken, ChannelReaderToken},
+    error::ChannelError,
+    history::HistoryView,
+    index::ChannelIndex,
+    queue::{DropPolicy, EventQueue, QueuePublishView, QueueSubscribeView},
+    reg::{AnyClone, ChannelClock, Reg, RegMutView, RegReadView},
+    slab::{AnySlab, RegSlab},
+    snapshot::{SnapshotChannel, SnapshotError, StoreSnapshot},
+    token::{
+        ChannelBehindToken, ChannelHistoryToken, ChannelOwnerToken, ChannelPublisherToken,
+        ChannelReaderToken, ChannelSubscriberToken, RegLocation,
+    },
 };
 
+/// Requester id reported within channel errors raised while binding an observer.
+pub const OBSERVER_ID: usize = usize::MAX;
+
+/// Counter used to assign each channel store a unique non-zero id. Tokens carry the id of the
+/// store which produced them so that they cannot be used to access another store.
+static NEXT_STORE_ID: AtomicUsize = AtomicUsize::new(1);
+
 #[derive(PartialEq, Debug)]
 enum IdType {
     Owner(usize),
@@ -18,61 +54,386 @@ enum IdType {
 }
 
 struct Channel {
-    /// Channel name which is used to as unique id for a given channel.
-    pub name: String,
+    /// Channel name which is used to as unique id for a given channel. The name is interned
+    /// and shared with the channel index.
+    pub name: Rc<str>,
     /// Owner id of this channel used for associating readers with channel writers (or owners).
     pub owner_id: IdType,
-    /// Contained channel value.
-    pub reg: Reg,
-    /// Previous channel value updated by runners.
-    pub behind_reg: Option<Reg>,
+    /// Index of the typed slab holding the channel registers.
+    pub slab: usize,
+    /// Slot of the register holding the channel value.
+    pub slot: usize,
+    /// True if the channel was registered as a dangling channel by a reader.
+    pub dangling: bool,
+    /// Every component reading the channel, in bind order.
+    pub readers: Vec<ChannelReader>,
+    /// Writes the owner is required to make during each of its dispatches.
+    pub write_policy: WritePolicy,
+    /// Encoder of channels registered with a serializable type.
+    #[cfg(feature = "serde")]
+    pub codec: Option<ChannelCodec>,
+}
+
+/// Number of writes the owner of a channel is required to make during each of its dispatches.
+/// Every mutable access through a RegMutView (set(), update(), borrow_mut()) counts as a write.
+/// Policies are enforced by the runner after each dispatch of the owner.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum WritePolicy {
+    /// The owner may write the channel any number of times.
+    #[default]
+    Optional,
+    /// The owner must write the channel at least once during every dispatch.
+    EveryDispatch,
+    /// The owner may write the channel at most once during a dispatch.
+    AtMostOnce,
+}
+
+/// Manner in which a component reads a channel.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReaderKind {
+    /// The reader observes the value written during the current tick.
+    Current,
+    /// The reader observes the value of the previous dispatch (bind_read_behind_channel()).
+    Behind,
+    /// The reader observes the values of past ticks (bind_read_history_channel()).
+    History,
+}
+
+/// Component reading a channel.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ChannelReader {
+    /// Id of the reading component.
+    pub id: usize,
+    pub kind: ReaderKind,
+}
+
+/// Read only description of a channel registered within a channel store.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ChannelInfo<'a> {
+    pub name: &'a str,
+    /// Id of the component owning the channel, None for unowned dangling channels.
+    pub owner: Option<usize>,
+    /// Every component reading the channel, in bind order. A component binding the channel both
+    /// as a current and a behind reader is listed once per kind.
+    pub readers: &'a [ChannelReader],
+    /// Name of the type of the channel value.
+    pub type_name: &'static str,
+    /// True if the channel was registered as a dangling channel by a reader.
+    pub dangling: bool,
+}
+
+/// Register holding the previous value of a channel for a single behind channel reader.
+pub(crate) struct BehindReg {
+    /// Accessor id of the channel the register trails.
+    pub channel_idx: usize,
+    /// Slot of the register within the slab of the channel.
+    pub slot: usize,
+    /// Id of the component which bound the behind channel.
+    pub reader_id: usize,
+    /// True once the channel was reported as never written to the reader.
+    pub unwritten_reported: AtomicBool,
+}
+
+/// Ring of registers holding the past values of a channel for every history channel reader.
+pub(crate) struct HistoryReg {
+    /// Accessor id of the channel the ring records.
+    pub channel_idx: usize,
+    /// First slot of the ring within the slab of the channel, the ring slots are contiguous.
+    pub base_slot: usize,
+    /// Number of slots of the ring, the largest depth requested by any reader.
+    pub depth: usize,
+    /// Ring index the next value is recorded into. Atomic as the ring is recorded through a
+    /// shared channel store reference.
+    pub head: AtomicUsize,
+}
+
+/// Largest age a reader accepts for the value of a channel.
+pub(crate) struct StalenessLimit {
+    /// Accessor id of the limited channel.
+    pub channel_idx: usize,
+    /// Id of the component which set the limit.
+    pub reader_id: usize,
+    pub max_age_ticks: u64,
+    /// True while the limit is exceeded, used to report each stale period once.
+    pub stale: AtomicBool,
 }
 
 pub struct ChannelStore {
+    /// Unique id of this store, used to validate tokens.
+    store_id: usize,
     channels: Vec<Channel>,
+    /// Typed register storage, one slab per channel value type.
+    slabs: Vec<Box<dyn AnySlab>>,
+    /// Lookup from value type to slab index, only used during registration.
+    slab_lookup: BTreeMap<TypeId, usize>,
+    /// Index used to resolve channel names into accessor ids.
+    channel_index: ChannelIndex,
     pub(crate) node_graph: Option<NodeGraph>,
-    pub(crate) active_behind_channels_idx: Vec<usize>,
+    /// Behind registers which are updated by runners after each dispatch.
+    pub(crate) active_behind_regs: Vec<BehindReg>,
+    /// History rings which are recorded by runners at the end of each tick.
+    pub(crate) active_history_regs: Vec<HistoryReg>,
+    /// Staleness limits which are checked by runners at the end of each tick.
+    pub(crate) staleness_limits: Vec<StalenessLimit>,
+    /// Tick and timestamp of the dispatch in progress, advanced by runners.
+    pub(crate) clock: ChannelClock,
+    /// When Some(), registration errors raised through the panicking API are collected here
+    /// instead of panicking. Used by the runner to report every wiring error at once.
+    pub(crate) collected_errors: Option<Vec<ChannelError>>,
 }
 
 impl Default for ChannelStore {
     fn default() -> Self {
         Self {
+            store_id: NEXT_STORE_ID.fetch_add(1, Ordering::Relaxed),
             channels: Vec::default(),
+            slabs: Vec::default(),
+            slab_lookup: BTreeMap::default(),
+            channel_index: ChannelIndex::default(),
             node_graph: Some(NodeGraph::default()),
-            active_behind_channels_idx: Vec::default(),
+            active_behind_regs: Vec::default(),
+            active_history_regs: Vec::default(),
+            staleness_limits: Vec::default(),
+            clock: ChannelClock::default(),
+            collected_errors: None,
         }
     }
 }
 
-impl ChannelStore {
-    fn get_existing_channel_idx(&self, name: &str) -> Result<usize, ()> {
-        for (i, channel) in self.channels.iter().enumerate() {
-            if channel.name == name {
-                return Ok(i);
+impl ChannelInfo<'_> {
+    /// Number of distinct components reading the channel.
+    pub fn fan_out(&self) -> usize {
+        let mut fan_out = 0;
+        for (reader_idx, reader) in self.readers.iter().enumerate() {
+            if !self.readers[..reader_idx]
+                .iter()
+                .any(|prev_reader| prev_reader.id == reader.id)
+            {
+                fan_out += 1;
             }
         }
 
-        Err(())
+        fan_out
+    }
+
+    /// Readers of the channel observing values of the given kind.
+    pub fn readers_of_kind(&self, kind: ReaderKind) -> impl Iterator<Item = usize> + '_ {
+        self.readers
+            .iter()
+            .filter(move |reader| reader.kind == kind)
+            .map(|reader| reader.id)
+    }
+
+    /// Returns true if no component reads the channel.
+    pub fn is_unread(&self) -> bool {
+        self.readers.is_empty()
+    }
+}
+
+impl ChannelStore {
+    fn get_existing_channel_idx(&self, name: &str) -> Option<usize> {
+        self.channel_index.get(name)
+    }
+
+    fn find_channel_idx(&self, name: &str, requester: usize) -> Result<usize, ChannelError> {
+        self.get_existing_channel_idx(name)
+            .ok_or_else(|| ChannelError::NotFound {
+                name: String::from(name),
+                requester,
+            })
+    }
+
+    fn check_channel_type<T: 'static>(
+        &self,
+        accessor_idx: usize,
+        requester: usize,
+    ) -> Result<(), ChannelError> {
+        let channel = &self.channels[accessor_idx];
+        let slab = &self.slabs[channel.slab];
+        if slab.reg_type_id() != TypeId::of::<T>() {
+            return Err(ChannelError::TypeMismatch {
+                name: channel.name.to_string(),
+                requester,
+                expected: any::type_name::<T>(),
+                actual: slab.reg_type_name(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn get_channel_owner_id(
+        &self,
+        accessor_idx: usize,
+        requester: usize,
+    ) -> Result<usize, ChannelError> {
+        let channel = &self.channels[accessor_idx];
+        match channel.owner_id {
+            IdType::Owner(id) => Ok(id),
+            IdType::ReaderReq(_) => Err(ChannelError::NoOwner {
+                name: channel.name.to_string(),
+                requester,
+            }),
+        }
+    }
+
+    /// Obtain the index of the slab holding values of type T, creating the slab if required.
+    fn get_or_insert_slab<T: AnyClone>(&mut self) -> usize {
+        let slabs = &mut self.slabs;
+        *self
+            .slab_lookup
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| {
+                slabs.push(Box::new(RegSlab::<T>::default()));
+                slabs.len() - 1
+            })
+    }
+
+    /// Obtain a typed reference to the slab at the given index.
+    ///
+    /// ### Safety
+    /// The slab at the given index must hold values of type T.
+    unsafe fn typed_slab<T: AnyClone>(&self, slab_idx: usize) -> &RegSlab<T> {
+        // SAFETY: The slab holds values of type T as guaranteed by the caller.
+        unsafe { &*(self.slabs[slab_idx].as_ref() as *const dyn AnySlab as *const RegSlab<T>) }
+    }
+
+    /// Obtain a typed mutable reference to the slab at the given index.
+    ///
+    /// ### Safety
+    /// The slab at the given index must hold values of type T.
+    unsafe fn typed_slab_mut<T: AnyClone>(&mut self, slab_idx: usize) -> &mut RegSlab<T> {
+        // SAFETY: The slab holds values of type T as guaranteed by the caller.
+        unsafe { &mut *(self.slabs[slab_idx].as_mut() as *mut dyn AnySlab as *mut RegSlab<T>) }
+    }
+
+    /// Resolve a token register location into the slab it refers to.
+    fn slab_at<T: AnyClone>(&self, location: RegLocation) -> &RegSlab<T> {
+        if location.store_id != self.store_id {
+            panic!("Invalid accessor token.");
+        }
+
+        // SAFETY: The location was produced by this store for a token of type T. Token producing
+        // registration calls only hand out locations within a slab holding values of type T.
+        unsafe { self.typed_slab::<T>(location.slab) }
     }
 
-    fn is_unique_channel_name(&self, name: &str) -> bool {
-        let query_result = self.get_existing_channel_idx(name);
+    /// Resolve a token register location into the register it refers to.
+    fn reg_at<T: AnyClone>(&self, location: RegLocation) -> &Reg<T> {
+        self.slab_at::<T>(location).reg(location.slot)
+    }
 
-        query_result.is_err()
+    fn location(&self, slab: usize, slot: usize) -> RegLocation {
+        RegLocation {
+            store_id: self.store_id,
+            slab,
+            slot,
+        }
     }
 
-    fn register_channel(&mut self, name: String, owner_id: IdType, reg: Reg) -> usize {
-        assert!(self.is_unique_channel_name(name.as_str()));
-        assert!(!name.is_empty());
+    fn try_register_channel<T: AnyClone>(
+        &mut self,
+        name: String,
+        owner_id: IdType,
+        value: T,
+    ) -> Result<usize, ChannelError> {
+        let requester = match owner_id {
+            IdType::Owner(id) | IdType::ReaderReq(id) => id,
+        };
+        if name.is_empty() {
+            return Err(ChannelError::EmptyName { requester });
+        }
+
+        let (dangling, readers) = match owner_id {
+            IdType::Owner(_) => (false, Vec::new()),
+            IdType::ReaderReq(reader_id) => (
+                true,
+                Vec::from([ChannelReader {
+                    id: reader_id,
+                    kind: ReaderKind::Current,
+                }]),
+            ),
+        };
         let accessor_id = self.channels.len();
+        let Some(interned_name) = self.channel_index.insert(name.as_str(), accessor_id) else {
+            return Err(ChannelError::DuplicateName { name, requester });
+        };
+        let slab = self.get_or_insert_slab::<T>();
+        // SAFETY: The slab was obtained for values of type T.
+        let slot = unsafe { self.typed_slab_mut::<T>(slab) }.push(value);
         self.channels.push(Channel {
-            name,
+            name: interned_name,
             owner_id,
-            reg,
-            behind_reg: None,
+            slab,
+            slot,
+            dangling,
+            readers,
+            write_policy: WritePolicy::Optional,
+            #[cfg(feature = "serde")]
+            codec: None,
         });
 
-        accessor_id
+        Ok(accessor_id)
+    }
+
+    pub(self) fn try_register_write_channel<T: AnyClone>(
+        &mut self,
+        name: String,
+        owner_id: usize,
+        initial_value: T,
+    ) -> Result<ChannelOwnerToken<T>, ChannelError> {
+        let accessor_idx =
+            self.try_register_channel(name, IdType::Owner(owner_id), initial_value)?;
+        Ok(ChannelOwnerToken::new(self.channel_location(accessor_idx)))
+    }
+
+    pub(self) fn try_register_write_channel_with_policy<T: AnyClone>(
+        &mut self,
+        name: String,
+        owner_id: usize,
+        initial_value: T,
+        policy: WritePolicy,
+    ) -> Result<ChannelOwnerToken<T>, ChannelError> {
+        let accessor_idx =
+            self.try_register_channel(name, IdType::Owner(owner_id), initial_value)?;
+        self.channels[accessor_idx].write_policy = policy;
+        Ok(ChannelOwnerToken::new(self.channel_location(accessor_idx)))
+    }
+
+    pub(self) fn register_write_channel_with_policy<T: AnyClone>(
+        &mut self,
+        name: String,
+        owner_id: usize,
+        initial_value: T,
+        policy: WritePolicy,
+    ) -> ChannelOwnerToken<T> {
+        let result =
+            self.try_register_write_channel_with_policy(name, owner_id, initial_value, policy);
+        self.resolve_channel_result(result)
+    }
+
+    #[cfg(feature = "serde")]
+    pub(self) fn try_register_write_channel_serde<T: AnyClone + serde::Serialize>(
+        &mut self,
+        name: String,
+        owner_id: usize,
+        initial_value: T,
+    ) -> Result<ChannelOwnerToken<T>, ChannelError> {
+        let accessor_idx =
+            self.try_register_channel(name, IdType::Owner(owner_id), initial_value)?;
+        self.channels[accessor_idx].codec = Some(ChannelCodec::of::<T>());
+        Ok(ChannelOwnerToken::new(self.channel_location(accessor_idx)))
+    }
+
+    #[cfg(feature = "serde")]
+    pub(self) fn register_write_channel_serde<T: AnyClone + serde::Serialize>(
+        &mut self,
+        name: String,
+        owner_id: usize,
+        initial_value: T,
+    ) -> ChannelOwnerToken<T> {
+        let result = self.try_register_write_channel_serde(name, owner_id, initial_value);
+        self.resolve_channel_result(result)
     }
 
     pub(self) fn register_write_channel<T: AnyClone>(
@@ -81,9 +442,47 @@ impl ChannelStore {
         owner_id: usize,
         initial_value: T,
     ) -> ChannelOwnerToken<T> {
+        let result = self.try_register_write_channel(name, owner_id, initial_value);
+        self.resolve_channel_result(result)
+    }
+
+    pub(self) fn try_register_queue_channel<T: AnyClone>(
+        &mut self,
+        name: String,
+        owner_id: usize,
+        capacity: usize,
+        policy: DropPolicy,
+    ) -> Result<ChannelPublisherToken<T>, ChannelError> {
+        let accessor_idx = self.try_register_channel(
+            name,
+            IdType::Owner(owner_id),
+            EventQueue::<T>::new(capacity, policy),
+        )?;
+        Ok(ChannelPublisherToken::new(
+            self.channel_location(accessor_idx),
+        ))
+    }
+
+    pub(self) fn register_queue_channel<T: AnyClone>(
+        &mut self,
+        name: String,
+        owner_id: usize,
+        capacity: usize,
+        policy: DropPolicy,
+    ) -> ChannelPublisherToken<T> {
+        let result = self.try_register_queue_channel(name, owner_id, capacity, policy);
+        self.resolve_channel_result(result)
+    }
+
+    pub(self) fn try_register_dangling_channel<T: AnyClone>(
+        &mut self,
+        name: String,
+        reader_id: usize,
+        default_value: T,
+    ) -> Result<ChannelReaderToken<T>, ChannelError> {
         let accessor_idx =
-            self.register_channel(name, IdType::Owner(owner_id), Reg::new(initial_value));
-        ChannelOwnerToken::new(accessor_idx)
+            self.try_register_channel(name, IdType::ReaderReq(reader_id), default_value)?;
+        Ok(ChannelReaderToken::new(self.channel_location(accessor_idx)))
     }
 
     pub(self) fn register_dangling_channel<T: AnyClone>(
@@ -92,79 +491,61 @@ impl ChannelStore {
         reader_id: usize,
         default_value: T,
     ) -> ChannelReaderToken<T> {
-        let accessor_idx: usize =
-            self.register_channel(name, IdType::ReaderReq(reader_id), Reg::new(default_value));
-        ChannelReaderToken::new(accessor_idx)
+        let result = self.try_register_dangling_channel(name, reader_id, default_value);
+        self.resolve_channel_result(result)
     }
 
-    pub(self) fn try_obtain_channel_ownership<T: 'static>(
+    pub(self) fn try_acquire_channel_ownership<T: 'static>(
         &mut self,
         name: String,
         owner_id: usize,
-    ) -> ChannelOwnerToken<T> {
-        let query_result = self.get_existing_channel_idx(name.as_str());
-        let accessor_idx =
-            query_result.unwrap_or_else(|_| panic!("Channel [{}] does not exist.", name));
+    ) -> Result<ChannelOwnerToken<T>, ChannelError> {
+        let accessor_idx = self.find_channel_idx(name.as_str(), owner_id)?;
         let channel_reader_id = {
-            match self.channels.get(accessor_idx).unwrap().owner_id {
-                IdType::Owner(_) => panic!("Channel [{}] already has an owner.", name),
+            match self.channels[accessor_idx].owner_id {
+                IdType::Owner(id) => {
+                    return Err(ChannelError::AlreadyOwned {
+                        name,
+                        requester: owner_id,
+                        owner: id,
+                    });
+                }
                 IdType::ReaderReq(id) => id,
             }
         };
 
-        if self
-            .channels
-            .get(accessor_idx)
-            .unwrap()
-            .reg
-            .matches_type::<T>()
-            .is_err()
-        {
-            panic!("Channel [{}] does match the requested type.", name);
-        }
+        self.check_channel_type::<T>(accessor_idx, owner_id)?;
 
-        self.channels.get_mut(accessor_idx).unwrap().owner_id = IdType::Owner(owner_id);
+        self.channels[accessor_idx].owner_id = IdType::Owner(owner_id);
         self.node_graph
             .as_mut()
             .unwrap()
             .insert_node_dependency(NodeDependency {
                 owner: owner_id,
                 consumer: channel_reader_id,
+                channel: accessor_idx,
             });
-        ChannelOwnerToken::new(accessor_idx)
+        Ok(ChannelOwnerToken::new(self.channel_location(accessor_idx)))
     }
 
-    pub(self) fn bind_read_channel<T: 'static>(
+    pub(self) fn obtain_channel_ownership<T: 'static>(
+        &mut self,
+        name: String,
+        owner_id: usize,
+    ) -> ChannelOwnerToken<T> {
+        let result = self.try_acquire_channel_ownership(name, owner_id);
+        self.resolve_channel_result(result)
+    }
+
+    pub(self) fn try_bind_read_channel<T: 'static>(
         &mut self,
         name: String,
         read_owner_id: usize,
-    ) -> ChannelReaderToken<T> {
-        let query_result = self.get_existing_channel_idx(name.as_str());
-        let accessor_idx =
-            query_result.unwrap_or_else(|_| panic!("Channel [{}] does not exist.", name));
+    ) -> Result<ChannelReaderToken<T>, ChannelError> {
+        let accessor_idx = self.find_channel_idx(name.as_str(), read_owner_id)?;
         // Associate the consumer (caller) with the owner of the channel for generating the execution ordering of components.
-        let channel_owner_id = {
-            match self.channels.get(accessor_idx).unwrap().owner_id {
-                IdType::Owner(id) => id,
-                IdType::ReaderReq(_) => {
-                    panic!(
-                        "Channel [{}] cannot bind as there is no owner for this channel.",
-                        name
-                    )
-                }
-            }
-        };
-
-        if self
-            .channels
-            .get(accessor_idx)
-            .unwrap()
-            .reg
-            .matches_type::<T>()
-            .is_err()
-        {
-            panic!("Channel [{}] does match the requested type.", name);
-        }
+        let channel_owner_id = self.get_channel_owner_id(accessor_idx, read_owner_id)?;
+        self.check_channel_type::<T>(accessor_idx, read_owner_id)?;
 
         // Unchecked call to unwrap() is okay here as register calls are only allowed when node_graph is Some().
         self.node_graph
@@ -173,182 +554,1340 @@ impl ChannelStore {
             .insert_node_dependency(NodeDependency {
                 owner: channel_owner_id,
                 consumer: read_owner_id,
+                channel: accessor_idx,
             });
+        self.record_channel_reader(accessor_idx, read_owner_id, ReaderKind::Current);
 
-        ChannelReaderToken::new(accessor_idx)
+        Ok(ChannelReaderToken::new(self.channel_location(accessor_idx)))
     }
 
-    pub(self) fn bind_read_behind_channel<T: 'static>(
+    pub(self) fn bind_read_channel<T: 'static>(
         &mut self,
         name: String,
-    ) -> ChannelBehindToken<T> {
-        let query_result = self.get_existing_channel_idx(name.as_str());
-        let accessor_idx =
-            query_result.unwrap_or_else(|_| panic!("Channel [{}] does not exist.", name));
-
-        let channel = self.channels.get_mut(accessor_idx).unwrap();
-        if let IdType::ReaderReq(_) = channel.owner_id {
-            panic!(
-                "Channel [{}] cannot bind as there is no owner for this channel.",
-                name
-            )
-        }
+        read_owner_id: usize,
+    ) -> ChannelReaderToken<T> {
+        let result = self.try_bind_read_channel(name, read_owner_id);
+        self.resolve_channel_result(result)
+    }
 
-        if channel.reg.matches_type::<T>().is_err() {
-            panic!("Channel [{}] does match the requested type.", name);
-        }
+    pub(self) fn try_bind_read_behind_channel<T: 'static>(
+        &mut self,
+        name: String,
+        read_owner_id: usize,
+    ) -> Result<ChannelBehindToken<T>, ChannelError> {
+        let accessor_idx = self.find_channel_idx(name.as_str(), read_owner_id)?;
+        self.get_channel_owner_id(accessor_idx, read_owner_id)?;
+        self.check_channel_type::<T>(accessor_idx, read_owner_id)?;
+
+        // Each reader obtains its own behind register as readers may be dispatched at different
+        // rates, the register is updated whenever its reader is dispatched.
+        let channel = &self.channels[accessor_idx];
+        let (slab, slot) = (channel.slab, channel.slot);
+        let behind_slot = match self
+            .active_behind_regs
+            .iter()
+            .find(|reg| reg.channel_idx == accessor_idx && reg.reader_id == read_owner_id)
+        {
+            Some(behind_reg) => behind_reg.slot,
+            None => {
+                // Behind register which is contained by the channel should contain a clone of
+                // the initial reg value. This allows behind channel access across all stages of execution.
+                let behind_slot = self.slabs[slab].push_clone(slot);
+                self.active_behind_regs.push(BehindReg {
+                    channel_idx: accessor_idx,
+                    slot: behind_slot,
+                    reader_id: read_owner_id,
+                    unwritten_reported: AtomicBool::new(false),
+                });
+                behind_slot
+            }
+        };
 
-        // Mark the channel as operating as an active behind channel.
-        if !self.active_behind_channels_idx.contains(&accessor_idx) {
-            self.active_behind_channels_idx.push(accessor_idx);
-        }
+        self.record_channel_reader(accessor_idx, read_owner_id, ReaderKind::Behind);
 
-        // Behind register which is contained by the channel should contain a clone of
-        // the initial reg value. This allows behind channel access across all stages of execution.
-        channel.behind_reg = Some(channel.reg.clone());
+        Ok(ChannelBehindToken::new(self.location(slab, behind_slot)))
+    }
 
-        ChannelBehindToken::new(accessor_idx)
+    pub(self) fn bind_read_behind_channel<T: 'static>(
+        &mut self,
+        name: String,
+        read_owner_id: usize,
+    ) -> ChannelBehindToken<T> {
+        let result = self.try_bind_read_behind_channel(name, read_owner_id);
+        self.resolve_channel_result(result)
     }
 
-    /// Obtain a vector of all currently unowned dangling channels within the channel store.
-    pub(self) fn query_unowned_dangling_channel_names(&self) -> Vec<String> {
-        self.channels
+    pub(self) fn try_bind_read_history_channel<T: 'static>(
+        &mut self,
+        name: String,
+        read_owner_id: usize,
+        depth: usize,
+    ) -> Result<ChannelHistoryToken<T>, ChannelError> {
+        if depth == 0 {
+            return Err(ChannelError::ZeroHistoryDepth {
+                name,
+                requester: read_owner_id,
+            });
+        }
+        let accessor_idx = self.find_channel_idx(name.as_str(), read_owner_id)?;
+        self.get_channel_owner_id(accessor_idx, read_owner_id)?;
+        self.check_channel_type::<T>(accessor_idx, read_owner_id)?;
+
+        // A single ring is shared by every reader of the channel and sized for the deepest
+        // reader. Rings are only grown during initialization before any value was recorded, a
+        // grown ring is moved into new contiguous slots holding clones of the channel value.
+        let channel = &self.channels[accessor_idx];
+        let (slab, slot) = (channel.slab, channel.slot);
+        let history_idx = match self
+            .active_history_regs
             .iter()
-            .filter(|channel| matches!(channel.owner_id, IdType::ReaderReq(_)))
-            .map(|channel| channel.name.clone())
-            .collect()
+            .position(|reg| reg.channel_idx == accessor_idx)
+        {
+            Some(history_idx) => {
+                if self.active_history_regs[history_idx].depth < depth {
+                    let base_slot = self.push_history_ring(slab, slot, depth);
+                    let history_reg = &mut self.active_history_regs[history_idx];
+                    history_reg.base_slot = base_slot;
+                    history_reg.depth = depth;
+                    history_reg.head = AtomicUsize::new(0);
+                }
+                history_idx
+            }
+            None => {
+                let base_slot = self.push_history_ring(slab, slot, depth);
+                self.active_history_regs.push(HistoryReg {
+                    channel_idx: accessor_idx,
+                    base_slot,
+                    depth,
+                    head: AtomicUsize::new(0),
+                });
+                self.active_history_regs.len() - 1
+            }
+        };
+
+        self.record_channel_reader(accessor_idx, read_owner_id, ReaderKind::History);
+
+        Ok(ChannelHistoryToken::with_history(
+            self.location(slab, slot),
+            history_idx,
+            depth,
+        ))
     }
 
-    pub(crate) fn update_active_behind_registers(&mut self) {
-        for idx in self.active_behind_channels_idx.iter() {
-            let channel = self.channels.get_mut(*idx).unwrap();
+    pub(self) fn bind_read_history_channel<T: 'static>(
+        &mut self,
+        name: String,
+        read_owner_id: usize,
+        depth: usize,
+    ) -> ChannelHistoryToken<T> {
+        let result = self.try_bind_read_history_channel(name, read_owner_id, depth);
+        self.resolve_channel_result(result)
+    }
 
-            match channel.behind_reg.as_mut() {
-                Some(reg) => reg.clone_from(&channel.reg),
-                None => panic!("Behind register for channel [{}] is None, this register should contain Some() value.", channel.name),
-            }
+    /// Push a ring of contiguous clones of a channel register, returning the first ring slot.
+    fn push_history_ring(&mut self, slab: usize, slot: usize, depth: usize) -> usize {
+        let base_slot = self.slabs[slab].push_clone(slot);
+        for _ in 1..depth {
+            self.slabs[slab].push_clone(slot);
         }
+
+        base_slot
     }
-}
 
-pub trait RegViewProducer<'a, C, K> {
-    /// Produce a register accessor view following a given token type.
-    fn grab(&'a self, token: &C) -> K;
-}
+    pub(self) fn try_bind_queue_channel<T: AnyClone>(
+        &mut self,
+        name: String,
+        read_owner_id: usize,
+    ) -> Result<ChannelSubscriberToken<T>, ChannelError> {
+        let accessor_idx = self.find_channel_idx(name.as_str(), read_owner_id)?;
+        let channel_owner_id = self.get_channel_owner_id(accessor_idx, read_owner_id)?;
+        self.check_channel_type::<EventQueue<T>>(accessor_idx, read_owner_id)?;
 
-impl<'a, T: AnyClone + Clone> RegViewProducer<'a, ChannelOwnerToken<T>, RegMutView<'a, T>>
-    for ChannelStore
-{
-    fn grab(&'a self, token: &ChannelOwnerToken<T>) -> RegMutView<'a, T> {
-        assert!(token.is_valid());
-        let accessor_id = token.get_accessor_id();
+        // Queue readers are ordered after the publisher like readers of register channels.
+        self.node_graph
+            .as_mut()
+            .unwrap()
+            .insert_node_dependency(NodeDependency {
+                owner: channel_owner_id,
+                consumer: read_owner_id,
+                channel: accessor_idx,
+            });
+        self.record_channel_reader(accessor_idx, read_owner_id, ReaderKind::Current);
+
+        let location = self.channel_location(accessor_idx);
+        // Adding a cursor is reader bookkeeping and does not count as a write of the queue.
+        let cursor = self
+            .reg_at::<EventQueue<T>>(location)
+            .update_unstamped(|queue| queue.add_cursor(read_owner_id));
+        Ok(ChannelSubscriberToken::with_cursor(location, cursor))
+    }
 
-        if let Some(channel) = self.channels.get(accessor_id) {
-            return RegMutView::new(&channel.reg);
-        } else {
-            panic!("Invalid accessor token.");
+    pub(self) fn bind_queue_channel<T: AnyClone>(
+        &mut self,
+        name: String,
+        read_owner_id: usize,
+    ) -> ChannelSubscriberToken<T> {
+        let result = self.try_bind_queue_channel(name, read_owner_id);
+        self.resolve_channel_result(result)
+    }
+
+    pub(self) fn try_set_staleness_limit(
+        &mut self,
+        name: String,
+        read_owner_id: usize,
+        max_age_ticks: u64,
+    ) -> Result<(), ChannelError> {
+        let accessor_idx = self.find_channel_idx(name.as_str(), read_owner_id)?;
+        if !self.channels[accessor_idx]
+            .readers
+            .iter()
+            .any(|reader| reader.id == read_owner_id)
+        {
+            return Err(ChannelError::NotRead {
+                name,
+                requester: read_owner_id,
+            });
         }
+        self.staleness_limits
+            .retain(|limit| limit.channel_idx != accessor_idx || limit.reader_id != read_owner_id);
+        self.staleness_limits.push(StalenessLimit {
+            channel_idx: accessor_idx,
+            reader_id: read_owner_id,
+            max_age_ticks,
+            stale: AtomicBool::new(false),
+        });
+
+        Ok(())
     }
-}
 
-impl<'a, T: AnyClone + Clone> RegViewProducer<'a, ChannelReaderToken<T>, RegReadView<'a, T>>
-    for ChannelStore
-{
-    fn grab(&'a self, token: &ChannelReaderToken<T>) -> RegReadView<'a, T> {
-        assert!(token.is_valid());
-        let accessor_id = token.get_accessor_id();
+    pub(self) fn set_staleness_limit(
+        &mut self,
+        name: String,
+        read_owner_id: usize,
+        max_age_ticks: u64,
+    ) {
+        if let Err(err) = self.try_set_staleness_limit(name, read_owner_id, max_age_ticks) {
+            self.resolve_channel_error(err);
+        }
+    }
 
-        if let Some(channel) = self.channels.get(accessor_id) {
-            return RegReadView::new(&channel.reg);
-        } else {
-            panic!("Invalid accessor token.");
+    /// Add a reader to the metadata of a channel, ignoring repeated binds of the same kind.
+    fn record_channel_reader(&mut self, accessor_idx: usize, reader_id: usize, kind: ReaderKind) {
+        let reader = ChannelReader {
+            id: reader_id,
+            kind,
+        };
+        let readers = &mut self.channels[accessor_idx].readers;
+        if !readers.contains(&reader) {
+            readers.push(reader);
         }
     }
-}
 
-impl<'a, T: AnyClone + Clone> RegViewProducer<'a, ChannelBehindToken<T>, RegReadView<'a, T>>
-    for ChannelStore
-{
-    fn grab(&'a self, token: &ChannelBehindToken<T>) -> RegReadView<'a, T> {
-        assert!(token.is_valid());
-        let accessor_id = token.get_accessor_id();
+    /// Unwrap the result of a fallible channel registration. On failure the error is either
+    /// collected (returning an invalid token) or raised as a panic with the error description.
+    fn resolve_channel_result<K: ChannelTokenOps>(&mut self, result: Result<K, ChannelError>) -> K {
+        match result {
+            Ok(token) => token,
+            Err(err) => {
+                self.resolve_channel_error(err);
+                K::invalid()
+            }
+        }
+    }
 
-        if let Some(channel) = self.channels.get(accessor_id) {
-            return RegReadView::new(channel.behind_reg.as_ref().unwrap());
-        } else {
-            panic!("Invalid accessor token.");
+    /// Either collect a channel registration error or raise it as a panic.
+    fn resolve_channel_error(&mut self, err: ChannelError) {
+        match self.collected_errors.as_mut() {
+            Some(errors) => errors.push(err),
+            None => panic!("{}", err),
         }
     }
-}
 
-pub struct ChannelWriteBuilder {
-    owner_id: usize,
-}
+    /// Register location of the current value of the channel referenced by the given accessor id.
+    fn channel_location(&self, accessor_id: usize) -> RegLocation {
+        let channel = &self.channels[accessor_id];
+        self.location(channel.slab, channel.slot)
+    }
 
-impl ChannelWriteBuilder {
-    pub fn new(owner_id: usize) -> ChannelWriteBuilder {
-        ChannelWriteBuilder { owner_id }
+    /// Obtain a reader token for a channel from outside of a component, such as a telemetry
+    /// thread sharing the channel store of a runner. Observers are not recorded as channel
+    /// readers and do not take part in the execution ordering. Observers can never write to a
+    /// channel, the owner remains the single writer.
+    ///
+    /// ### Arguments
+    /// * 'name' - Name of the channel to be observed.
+    ///
+    pub fn try_bind_observer<T: 'static>(
+        &self,
+        name: &str,
+    ) -> Result<ChannelReaderToken<T>, ChannelError> {
+        let accessor_idx = self.find_channel_idx(name, OBSERVER_ID)?;
+        self.check_channel_type::<T>(accessor_idx, OBSERVER_ID)?;
+
+        Ok(ChannelReaderToken::new(self.channel_location(accessor_idx)))
+    }
+
+    /// Accessor id of the channel with the given name, None if no such channel exists. Used by
+    /// telemetry sinks.
+    #[cfg_attr(not(all(feature = "std", feature = "serde")), allow(dead_code))]
+    pub(crate) fn channel_accessor_id(&self, name: &str) -> Option<usize> {
+        self.get_existing_channel_idx(name)
+    }
+
+    /// Name of the channel referenced by the given accessor id.
+    pub(crate) fn channel_name(&self, accessor_id: usize) -> &str {
+        &self.channels[accessor_id].name
+    }
+
+    /// Readers of the channel with the given accessor id, in bind order.
+    pub(crate) fn channel_readers(&self, accessor_id: usize) -> &[ChannelReader] {
+        &self.channels[accessor_id].readers
+    }
+
+    /// Describe every channel registered within the store, in registration order.
+    pub fn channels(&self) -> impl Iterator<Item = ChannelInfo<'_>> {
+        self.channels
+            .iter()
+            .map(|channel| self.channel_info(channel))
+    }
+
+    /// Describe the channel with the given name, None if no such channel exists.
+    pub fn channel(&self, name: &str) -> Option<ChannelInfo<'_>> {
+        self.get_existing_channel_idx(name)
+            .map(|accessor_idx| self.channel_info(&self.channels[accessor_idx]))
+    }
+
+    /// Describe every channel which has no readers, such as write channels whose output is never used.
+    pub fn unread_channels(&self) -> impl Iterator<Item = ChannelInfo<'_>> {
+        self.channels().filter(|channel| channel.is_unread())
+    }
+
+    fn channel_info<'a>(&'a self, channel: &'a Channel) -> ChannelInfo<'a> {
+        ChannelInfo {
+            name: &channel.name,
+            owner: match channel.owner_id {
+                IdType::Owner(id) => Some(id),
+                IdType::ReaderReq(_) => None,
+            },
+            readers: &channel.readers,
+            type_name: self.slabs[channel.slab].reg_type_name(),
+            dangling: channel.dangling,
+        }
+    }
+
+    /// Returns true if the channel referenced by the given accessor id was registered as a
+    /// dangling channel.
+    pub(crate) fn is_dangling_channel(&self, accessor_id: usize) -> bool {
+        self.channels[accessor_id].dangling
+    }
+
+    /// Obtain the owner to reader dependency of every bound behind and history channel. These
+    /// dependencies are not part of the execution ordering.
+    pub(crate) fn behind_dependencies(&self) -> Vec<NodeDependency> {
+        let mut dependencies = Vec::new();
+        for (accessor_idx, channel) in self.channels.iter().enumerate() {
+            let IdType::Owner(owner) = channel.owner_id else {
+                continue;
+            };
+            dependencies.extend(
+                channel
+                    .readers
+                    .iter()
+                    .filter(|reader| reader.kind != ReaderKind::Current)
+                    .map(|reader| NodeDependency {
+                        owner,
+                        consumer: reader.id,
+                        channel: accessor_idx,
+                    }),
+            );
+        }
+
+        dependencies
+    }
+
+    /// Obtain a vector of all currently unowned dangling channels within the channel store.
+    pub(self) fn query_unowned_dangling_channel_names(&self) -> Vec<String> {
+        self.channels
+            .iter()
+            .filter(|channel| matches!(channel.owner_id, IdType::ReaderReq(_)))
+            .map(|channel| channel.name.to_string())
+            .collect()
+    }
+
+    /// Obtain the name and requesting reader id of all currently unowned dangling channels.
+    pub(crate) fn query_unowned_dangling_channels(&self) -> Vec<(String, usize)> {
+        self.channels
+            .iter()
+            .filter_map(|channel| match channel.owner_id {
+                IdType::ReaderReq(reader_id) => Some((channel.name.to_string(), reader_id)),
+                IdType::Owner(_) => None,
+            })
+            .collect()
+    }
+
+    /// Update the behind registers of every reader which was dispatched, copying in the
+    /// current value of the trailed channel.
+    ///
+    /// ### Arguments
+    /// * 'is_reader_dispatched' - Returns true if the component with the given id was dispatched.
+    ///
+    pub(crate) fn update_active_behind_registers(
+        &self,
+        is_reader_dispatched: impl Fn(usize) -> bool,
+    ) {
+        for behind_reg in self.active_behind_regs.iter() {
+            if is_reader_dispatched(behind_reg.reader_id) {
+                let channel = &self.channels[behind_reg.channel_idx];
+                self.slabs[channel.slab].clone_slot(channel.slot, behind_reg.slot);
+            }
+        }
+    }
+
+    /// Check the staleness limits of every reader which was dispatched, returning the channel
+    /// accessor id and reader id of each limit which became exceeded. A limit is reported once
+    /// per stale period. Channels which were never written are aged from before the first tick.
+    ///
+    /// ### Arguments
+    /// * 'is_reader_dispatched' - Returns true if the component with the given id was dispatched.
+    ///
+    pub(crate) fn check_staleness_limits(
+        &self,
+        is_reader_dispatched: impl Fn(usize) -> bool,
+    ) -> Vec<(usize, usize)> {
+        let tick = self.clock.tick();
+        let mut exceeded = Vec::new();
+        for limit in self.staleness_limits.iter() {
+            if !is_reader_dispatched(limit.reader_id) {
+                continue;
+            }
+
+            let channel = &self.channels[limit.channel_idx];
+            let age = match self.slabs[channel.slab].last_write_tick(channel.slot) {
+                Some(write_tick) => tick.saturating_sub(write_tick),
+                None => tick + 1,
+            };
+            let is_stale = age > limit.max_age_ticks;
+            if is_stale && !limit.stale.swap(true, Ordering::Relaxed) {
+                exceeded.push((limit.channel_idx, limit.reader_id));
+            } else if !is_stale {
+                limit.stale.store(false, Ordering::Relaxed);
+            }
+        }
+
+        exceeded
+    }
+
+    /// Check the behind channels of every reader which was dispatched, returning the channel
+    /// accessor id and reader id of each behind channel whose owner was dispatched while the
+    /// channel was never written. Each behind channel is reported once.
+    ///
+    /// ### Arguments
+    /// * 'is_reader_dispatched' - Returns true if the component with the given id was dispatched.
+    /// * 'was_owner_dispatched' - Returns true if the component with the given id was dispatched
+    ///   during this or any previous tick.
+    ///
+    pub(crate) fn check_unwritten_behind_channels(
+        &self,
+        is_reader_dispatched: impl Fn(usize) -> bool,
+        was_owner_dispatched: impl Fn(usize) -> bool,
+    ) -> Vec<(usize, usize)> {
+        let mut unwritten = Vec::new();
+        for behind_reg in self.active_behind_regs.iter() {
+            if !is_reader_dispatched(behind_reg.reader_id)
+                || behind_reg.unwritten_reported.load(Ordering::Relaxed)
+            {
+                continue;
+            }
+
+            let channel = &self.channels[behind_reg.channel_idx];
+            let IdType::Owner(owner) = channel.owner_id else {
+                continue;
+            };
+            if self.slabs[channel.slab].write_sequence(channel.slot) == 0
+                && was_owner_dispatched(owner)
+            {
+                behind_reg.unwritten_reported.store(true, Ordering::Relaxed);
+                unwritten.push((behind_reg.channel_idx, behind_reg.reader_id));
+            }
+        }
+
+        unwritten
+    }
+
+    /// Obtain the accessor id, owner id and write policy of every owned channel whose write
+    /// policy is not optional.
+    pub(crate) fn write_policies(&self) -> impl Iterator<Item = (usize, usize, WritePolicy)> + '_ {
+        self.channels
+            .iter()
+            .enumerate()
+            .filter_map(|(accessor_idx, channel)| match channel.owner_id {
+                IdType::Owner(owner) if channel.write_policy != WritePolicy::Optional => {
+                    Some((accessor_idx, owner, channel.write_policy))
+                }
+                _ => None,
+            })
+    }
+
+    /// Number of writes made to the channel referenced by the given accessor id.
+    pub(crate) fn channel_write_sequence(&self, accessor_id: usize) -> u64 {
+        let channel = &self.channels[accessor_id];
+        self.slabs[channel.slab].write_sequence(channel.slot)
+    }
+
+    /// Resolve a channel selected for recording or replay into its accessor id, checking that it
+    /// holds values of the codec type.
+    ///
+    /// ### Arguments
+    /// * 'name' - Name of the selected channel.
+    /// * 'codec' - Codec the channel values are recorded with.
+    ///
+    pub(crate) fn find_record_channel(
+        &self,
+        name: &str,
+        codec: &RecordCodec,
+    ) -> Result<usize, ChannelError> {
+        let accessor_idx = self.find_channel_idx(name, OBSERVER_ID)?;
+        let channel = &self.channels[accessor_idx];
+        let slab = &self.slabs[channel.slab];
+        if slab.reg_type_id() != codec.type_id() {
+            return Err(ChannelError::TypeMismatch {
+                name: channel.name.to_string(),
+                requester: OBSERVER_ID,
+                expected: codec.type_name(),
+                actual: slab.reg_type_name(),
+            });
+        }
+
+        Ok(accessor_idx)
+    }
+
+    /// Id of the owner of the channel referenced by the given accessor id, None for unowned
+    /// dangling channels.
+    pub(crate) fn channel_owner(&self, accessor_id: usize) -> Option<usize> {
+        match self.channels[accessor_id].owner_id {
+            IdType::Owner(owner) => Some(owner),
+            IdType::ReaderReq(_) => None,
+        }
+    }
+
+    /// Append the encoded value of the channel referenced by the given accessor id to the output.
+    pub(crate) fn encode_record(
+        &self,
+        accessor_id: usize,
+        codec: &RecordCodec,
+        output: &mut Vec<u8>,
+    ) {
+        let channel = &self.channels[accessor_id];
+        codec.encode(self.slabs[channel.slab].as_ref(), channel.slot, output);
+    }
+
+    /// Write an encoded value into the channel referenced by the given accessor id, returning
+    /// false if the input does not hold a value of the codec type.
+    pub(crate) fn decode_record(
+        &self,
+        accessor_id: usize,
+        codec: &RecordCodec,
+        input: &[u8],
+    ) -> bool {
+        let channel = &self.channels[accessor_id];
+        codec.decode(
+            self.slabs[channel.slab].as_ref(),
+            channel.slot,
+            input,
+            &self.clock,
+        )
+    }
+
+    /// Encode the current value of a channel registered with a serializable type into a self
+    /// describing JSON value, without knowledge of the channel type.
+    ///
+    /// ### Argument
+    /// * 'name' - Name of the channel to be encoded.
+    ///
+    #[cfg(feature = "serde")]
+    pub fn encode_channel(&self, name: &str) -> Result<serde_json::Value, EncodeError> {
+        let accessor_idx =
+            self.get_existing_channel_idx(name)
+                .ok_or_else(|| EncodeError::NotFound {
+                    name: String::from(name),
+                })?;
+        let channel = &self.channels[accessor_idx];
+        let codec = channel.codec.ok_or_else(|| EncodeError::NotSerializable {
+            name: String::from(name),
+        })?;
+
+        codec
+            .encode(self.slabs[channel.slab].as_ref(), channel.slot)
+            .map_err(|err| EncodeError::serialize(name, err))
+    }
+
+    /// Derive a JSON schema describing the type of a channel registered with a serializable type.
+    /// The schema is derived from the current value of the channel. Parts of the value which do
+    /// not describe their whole type, such as None, empty collections or enum values, are described
+    /// by a permissive schema.
+    ///
+    /// ### Argument
+    /// * 'name' - Name of the channel to be described.
+    ///
+    #[cfg(feature = "serde")]
+    pub fn channel_schema(&self, name: &str) -> Result<serde_json::Value, EncodeError> {
+        let accessor_idx =
+            self.get_existing_channel_idx(name)
+                .ok_or_else(|| EncodeError::NotFound {
+                    name: String::from(name),
+                })?;
+        let channel = &self.channels[accessor_idx];
+        let codec = channel.codec.ok_or_else(|| EncodeError::NotSerializable {
+            name: String::from(name),
+        })?;
+
+        codec
+            .schema(self.slabs[channel.slab].as_ref(), channel.slot)
+            .map_err(|err| EncodeError::schema(name, err))
+    }
+
+    /// Names of every channel registered with a serializable type, in registration order.
+    #[cfg(feature = "serde")]
+    pub fn serializable_channels(&self) -> impl Iterator<Item = &str> {
+        self.channels
+            .iter()
+            .filter(|channel| channel.codec.is_some())
+            .map(|channel| &*channel.name)
+    }
+
+    /// Capture the value of every register within the store, including behind registers,
+    /// history rings and queue channels.
+    pub fn snapshot(&self) -> StoreSnapshot {
+        StoreSnapshot {
+            channels: self
+                .channels
+                .iter()
+                .map(|channel| SnapshotChannel {
+                    name: channel.name.to_string(),
+                    type_id: self.slabs[channel.slab].reg_type_id(),
+                    type_name: self.slabs[channel.slab].reg_type_name(),
+                })
+                .collect(),
+            slabs: self.slabs.iter().map(|slab| slab.snapshot()).collect(),
+            history_heads: self
+                .active_history_regs
+                .iter()
+                .map(|history_reg| history_reg.head.load(Ordering::Acquire))
+                .collect(),
+            clock: (self.clock.tick(), self.clock.timestamp()),
+            stale_flags: self
+                .staleness_limits
+                .iter()
+                .map(|limit| limit.stale.load(Ordering::Relaxed))
+                .collect(),
+            unwritten_reported: self
+                .active_behind_regs
+                .iter()
+                .map(|behind_reg| behind_reg.unwritten_reported.load(Ordering::Relaxed))
+                .collect(),
+        }
+    }
+
+    /// Overwrite every register within the store with the values captured by a snapshot,
+    /// together with the store clock and the staleness and unwritten channel diagnostics state.
+    /// The snapshot is validated against the channel names, channel types and register layout
+    /// of the store, and every register is checked not to be borrowed, before any state is
+    /// modified.
+    ///
+    /// ### Argument
+    /// * 'snapshot' - Snapshot produced by snapshot() on this or an identically wired store.
+    ///
+    pub fn restore(&self, snapshot: &StoreSnapshot) -> Result<(), SnapshotError> {
+        if snapshot.channels.len() != self.channels.len() {
+            return Err(SnapshotError::ChannelCount {
+                expected: self.channels.len(),
+                actual: snapshot.channels.len(),
+            });
+        }
+        for (channel, snapshot_channel) in self.channels.iter().zip(snapshot.channels.iter()) {
+            if *channel.name != *snapshot_channel.name {
+                return Err(SnapshotError::ChannelName {
+                    expected: channel.name.to_string(),
+                    actual: snapshot_channel.name.clone(),
+                });
+            }
+            let slab = &self.slabs[channel.slab];
+            if slab.reg_type_id() != snapshot_channel.type_id {
+                return Err(SnapshotError::ChannelType {
+                    name: channel.name.to_string(),
+                    expected: slab.reg_type_name(),
+                    actual: snapshot_channel.type_name,
+                });
+            }
+        }
+
+        let layout_matches = snapshot.slabs.len() == self.slabs.len()
+            && snapshot.history_heads.len() == self.active_history_regs.len()
+            && snapshot.stale_flags.len() == self.staleness_limits.len()
+            && snapshot.unwritten_reported.len() == self.active_behind_regs.len()
+            && self
+                .slabs
+                .iter()
+                .zip(snapshot.slabs.iter())
+                .all(|(slab, snapshot_slab)| slab.matches_snapshot(snapshot_slab.as_ref()));
+        if !layout_matches {
+            return Err(SnapshotError::RegisterLayout);
+        }
+        for (slab_idx, slab) in self.slabs.iter().enumerate() {
+            if let Some(slot) = slab.borrowed_slot() {
+                return Err(SnapshotError::RegisterBorrowed {
+                    name: self.register_channel_name(slab_idx, slot).to_string(),
+                });
+            }
+        }
+
+        for (slab, snapshot_slab) in self.slabs.iter().zip(snapshot.slabs.iter()) {
+            if !slab.restore(snapshot_slab.as_ref()) {
+                return Err(SnapshotError::RegisterLayout);
+            }
+        }
+        for (history_reg, head) in self
+            .active_history_regs
+            .iter()
+            .zip(snapshot.history_heads.iter())
+        {
+            history_reg.head.store(*head, Ordering::Release);
+        }
+        let (tick, timestamp) = snapshot.clock;
+        self.clock.advance(tick, timestamp);
+        for (limit, stale) in self
+            .staleness_limits
+            .iter()
+            .zip(snapshot.stale_flags.iter())
+        {
+            limit.stale.store(*stale, Ordering::Relaxed);
+        }
+        for (behind_reg, reported) in self
+            .active_behind_regs
+            .iter()
+            .zip(snapshot.unwritten_reported.iter())
+        {
+            behind_reg
+                .unwritten_reported
+                .store(*reported, Ordering::Relaxed);
+        }
+
+        Ok(())
+    }
+
+    /// Name of the channel a register belongs to, including the behind and history registers
+    /// trailing a channel.
+    fn register_channel_name(&self, slab_idx: usize, slot: usize) -> &str {
+        let channel_idx = self
+            .channels
+            .iter()
+            .position(|channel| channel.slab == slab_idx && channel.slot == slot)
+            .or_else(|| {
+                self.active_behind_regs
+                    .iter()
+                    .find(|behind_reg| {
+                        self.channels[behind_reg.channel_idx].slab == slab_idx
+                            && behind_reg.slot == slot
+                    })
+                    .map(|behind_reg| behind_reg.channel_idx)
+            })
+            .or_else(|| {
+                self.active_history_regs
+                    .iter()
+                    .find(|history_reg| {
+                        self.channels[history_reg.channel_idx].slab == slab_idx
+                            && (history_reg.base_slot..history_reg.base_slot + history_reg.depth)
+                                .contains(&slot)
+                    })
+                    .map(|history_reg| history_reg.channel_idx)
+            })
+            .expect("Every register belongs to a channel.");
+
+        &self.channels[channel_idx].name
+    }
+
+    /// Record the current value of every channel with history readers into its history ring.
+    pub(crate) fn update_history_registers(&self) {
+        for history_reg in self.active_history_regs.iter() {
+            let channel = &self.channels[history_reg.channel_idx];
+            let head = history_reg.head.load(Ordering::Relaxed);
+            self.slabs[channel.slab].clone_slot(channel.slot, history_reg.base_slot + head);
+            history_reg
+                .head
+                .store((head + 1) % history_reg.depth, Ordering::Release);
+        }
+    }
+}
+
+pub trait RegViewProducer<'a, C, K> {
+    /// Produce a register accessor view following a given token type.
+    fn grab(&'a self, token: &C) -> K;
+}
+
+impl<'a, T: AnyClone + Clone> RegViewProducer<'a, ChannelOwnerToken<T>, RegMutView<'a, T>>
+    for ChannelStore
+{
+    fn grab(&'a self, token: &ChannelOwnerToken<T>) -> RegMutView<'a, T> {
+        assert!(token.is_valid());
+        RegMutView::with_clock(self.reg_at(token.get_location()), &self.clock)
+    }
+}
+
+impl<'a, T: AnyClone + Clone> RegViewProducer<'a, ChannelReaderToken<T>, RegReadView<'a, T>>
+    for ChannelStore
+{
+    fn grab(&'a self, token: &ChannelReaderToken<T>) -> RegReadView<'a, T> {
+        assert!(token.is_valid());
+        RegReadView::with_clock(self.reg_at(token.get_location()), &self.clock)
+    }
+}
+
+impl<'a, T: AnyClone + Clone> RegViewProducer<'a, ChannelBehindToken<T>, RegReadView<'a, T>>
+    for ChannelStore
+{
+    fn grab(&'a self, token: &ChannelBehindToken<T>) -> RegReadView<'a, T> {
+        assert!(token.is_valid());
+        RegReadView::with_clock(self.reg_at(token.get_location()), &self.clock)
+    }
+}
+
+impl<'a, T: AnyClone> RegViewProducer<'a, ChannelPublisherToken<T>, QueuePublishView<'a, T>>
+    for ChannelStore
+{
+    fn grab(&'a self, token: &ChannelPublisherToken<T>) -> QueuePublishView<'a, T> {
+        assert!(token.is_valid());
+        QueuePublishView::new(self.reg_at(token.get_location()), &self.clock)
+    }
+}
+
+impl<'a, T: AnyClone> RegViewProducer<'a, ChannelSubscriberToken<T>, QueueSubscribeView<'a, T>>
+    for ChannelStore
+{
+    fn grab(&'a self, token: &ChannelSubscriberToken<T>) -> QueueSubscribeView<'a, T> {
+        assert!(token.is_valid());
+        QueueSubscribeView::new(self.reg_at(token.get_location()), token.get_cursor())
+    }
+}
+
+impl<'a, T: AnyClone> RegViewProducer<'a, ChannelHistoryToken<T>, HistoryView<'a, T>>
+    for ChannelStore
+{
+    fn grab(&'a self, token: &ChannelHistoryToken<T>) -> HistoryView<'a, T> {
+        assert!(token.is_valid());
+        let history_reg = &self.active_history_regs[token.get_history()];
+        HistoryView::new(
+            self.slab_at(token.get_location()),
+            history_reg.base_slot,
+            history_reg.depth,
+            history_reg.head.load(Ordering::Acquire),
+            token.get_depth(),
+        )
+    }
+}
+
+/// Channel store restricted to channels holding Send and Sync values, as such it may be shared
+/// between threads. Parallel components access their channels through it when dispatched on
+/// worker threads by Runner::dispatch_components_parallel().
+#[cfg(any(feature = "std", feature = "concurrent"))]
+#[derive(Clone, Copy)]
+pub struct SyncChannelStore<'s> {
+    store: &'s ChannelStore,
+}
+
+// SAFETY: Only registers of Send and Sync values can be grabbed through the store, which are held
+// within thread safe cells with the std or concurrent feature. Every other state read by grab()
+// is immutable or atomic while components are dispatched.
+#[cfg(any(feature = "std", feature = "concurrent"))]
+unsafe impl Send for SyncChannelStore<'_> {}
+#[cfg(any(feature = "std", feature = "concurrent"))]
+unsafe impl Sync for SyncChannelStore<'_> {}
+
+#[cfg(any(feature = "std", feature = "concurrent"))]
+impl<'s> SyncChannelStore<'s> {
+    /// Restrict access to a channel store to channels holding Send and Sync values.
+    ///
+    /// ### Argument
+    /// * 'store' - Channel store to be accessed.
+    ///
+    pub fn new(store: &'s ChannelStore) -> Self {
+        Self { store }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "concurrent"))]
+impl<'a, 's: 'a, T: AnyClone + Send + Sync>
+    RegViewProducer<'a, ChannelOwnerToken<T>, RegMutView<'a, T>> for SyncChannelStore<'s>
+{
+    fn grab(&'a self, token: &ChannelOwnerToken<T>) -> RegMutView<'a, T> {
+        self.store.grab(token)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "concurrent"))]
+impl<'a, 's: 'a, T: AnyClone + Send + Sync>
+    RegViewProducer<'a, ChannelReaderToken<T>, RegReadView<'a, T>> for SyncChannelStore<'s>
+{
+    fn grab(&'a self, token: &ChannelReaderToken<T>) -> RegReadView<'a, T> {
+        self.store.grab(token)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "concurrent"))]
+impl<'a, 's: 'a, T: AnyClone + Send + Sync>
+    RegViewProducer<'a, ChannelBehindToken<T>, RegReadView<'a, T>> for SyncChannelStore<'s>
+{
+    fn grab(&'a self, token: &ChannelBehindToken<T>) -> RegReadView<'a, T> {
+        self.store.grab(token)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "concurrent"))]
+impl<'a, 's: 'a, T: AnyClone + Send + Sync>
+    RegViewProducer<'a, ChannelPublisherToken<T>, QueuePublishView<'a, T>>
+    for SyncChannelStore<'s>
+{
+    fn grab(&'a self, token: &ChannelPublisherToken<T>) -> QueuePublishView<'a, T> {
+        self.store.grab(token)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "concurrent"))]
+impl<'a, 's: 'a, T: AnyClone + Send + Sync>
+    RegViewProducer<'a, ChannelSubscriberToken<T>, QueueSubscribeView<'a, T>>
+    for SyncChannelStore<'s>
+{
+    fn grab(&'a self, token: &ChannelSubscriberToken<T>) -> QueueSubscribeView<'a, T> {
+        self.store.grab(token)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "concurrent"))]
+impl<'a, 's: 'a, T: AnyClone + Send + Sync>
+    RegViewProducer<'a, ChannelHistoryToken<T>, HistoryView<'a, T>> for SyncChannelStore<'s>
+{
+    fn grab(&'a self, token: &ChannelHistoryToken<T>) -> HistoryView<'a, T> {
+        self.store.grab(token)
+    }
+}
+
+/// Handle to the channel store of an initialized runner which may be sent to other threads,
+/// obtained with Runner::shared_channel_store(). Only channels holding Send and Sync values may
+/// be observed through it.
+///
+/// The store may hold values which must not leave the thread of the runner. As such, when the
+/// last handle is dropped after the runner, the store is leaked rather than dropped.
+#[cfg(feature = "concurrent")]
+#[derive(Clone)]
+pub struct SharedChannelStore {
+    store: ManuallyDrop<Arc<ChannelStore>>,
+}
+
+// SAFETY: See SyncChannelStore, the store is never dropped through the handle.
+#[cfg(feature = "concurrent")]
+unsafe impl Send for SharedChannelStore {}
+#[cfg(feature = "concurrent")]
+unsafe impl Sync for SharedChannelStore {}
+
+#[cfg(feature = "concurrent")]
+impl SharedChannelStore {
+    pub(crate) fn new(store: Arc<ChannelStore>) -> Self {
+        Self {
+            store: ManuallyDrop::new(store),
+        }
+    }
+
+    /// Obtain a reader token for a channel holding Send and Sync values. See
+    /// ChannelStore::try_bind_observer().
+    ///
+    /// ### Arguments
+    /// * 'name' - Name of the channel to be observed.
+    ///
+    pub fn try_bind_observer<T: AnyClone + Send + Sync>(
+        &self,
+        name: &str,
+    ) -> Result<ChannelReaderToken<T>, ChannelError> {
+        self.store.try_bind_observer(name)
+    }
+
+    /// Access the store restricted to channels holding Send and Sync values.
+    pub fn view(&self) -> SyncChannelStore<'_> {
+        SyncChannelStore::new(&self.store)
+    }
+}
+
+#[cfg(feature = "concurrent")]
+impl Drop for SharedChannelStore {
+    fn drop(&mut self) {
+        // SAFETY: The store is not accessed after being taken.
+        let store = unsafe { ManuallyDrop::take(&mut self.store) };
+        if let Some(store) = Arc::into_inner(store) {
+            core::mem::forget(store);
+        }
+    }
+}
+
+#[cfg(feature = "concurrent")]
+impl<'a, T: AnyClone + Send + Sync> RegViewProducer<'a, ChannelReaderToken<T>, RegReadView<'a, T>>
+    for SharedChannelStore
+{
+    fn grab(&'a self, token: &ChannelReaderToken<T>) -> RegReadView<'a, T> {
+        self.store.grab(token)
+    }
+}
+
+pub struct ChannelWriteBuilder {
+    owner_id: usize,
+}
+
+impl ChannelWriteBuilder {
+    pub fn new(owner_id: usize) -> ChannelWriteBuilder {
+        ChannelWriteBuilder { owner_id }
+    }
+
+    /// Register a new owned channel into the channels list.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Unique channel name in string form.
+    /// * 'initial_value' - Value to be assigned into this channel on init.
+    ///
+    pub fn register_write_channel<T: AnyClone>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+        initial_value: T,
+    ) -> ChannelOwnerToken<T> {
+        channel_store.register_write_channel(name, self.owner_id, initial_value)
+    }
+
+    /// Fallible variant of register_write_channel() which returns an error instead of panicking
+    /// when the name is empty or already in use.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Unique channel name in string form.
+    /// * 'initial_value' - Value to be assigned into this channel on init.
+    ///
+    pub fn try_register_write_channel<T: AnyClone>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+        initial_value: T,
+    ) -> Result<ChannelOwnerToken<T>, ChannelError> {
+        channel_store.try_register_write_channel(name, self.owner_id, initial_value)
+    }
+
+    /// Register a new owned channel into the channels list with a write policy. The runner
+    /// reports a WriteViolation whenever a dispatch of the owner breaks the policy.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Unique channel name in string form.
+    /// * 'initial_value' - Value to be assigned into this channel on init.
+    /// * 'policy' - Writes the owner is required to make during each of its dispatches.
+    ///
+    pub fn register_write_channel_with_policy<T: AnyClone>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+        initial_value: T,
+        policy: WritePolicy,
+    ) -> ChannelOwnerToken<T> {
+        channel_store.register_write_channel_with_policy(name, self.owner_id, initial_value, policy)
+    }
+
+    /// Fallible variant of register_write_channel_with_policy() which returns an error instead
+    /// of panicking when the name is empty or already in use.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Unique channel name in string form.
+    /// * 'initial_value' - Value to be assigned into this channel on init.
+    /// * 'policy' - Writes the owner is required to make during each of its dispatches.
+    ///
+    pub fn try_register_write_channel_with_policy<T: AnyClone>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+        initial_value: T,
+        policy: WritePolicy,
+    ) -> Result<ChannelOwnerToken<T>, ChannelError> {
+        channel_store.try_register_write_channel_with_policy(
+            name,
+            self.owner_id,
+            initial_value,
+            policy,
+        )
+    }
+
+    /// Register a new owned channel holding a serializable type. The channel value may be encoded
+    /// by name with ChannelStore::encode_channel().
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Unique channel name in string form.
+    /// * 'initial_value' - Value to be assigned into this channel on init.
+    ///
+    #[cfg(feature = "serde")]
+    pub fn register_write_channel_serde<T: AnyClone + serde::Serialize>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+        initial_value: T,
+    ) -> ChannelOwnerToken<T> {
+        channel_store.register_write_channel_serde(name, self.owner_id, initial_value)
+    }
+
+    /// Fallible variant of register_write_channel_serde() which returns an error instead of
+    /// panicking when the name is empty or already in use.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Unique channel name in string form.
+    /// * 'initial_value' - Value to be assigned into this channel on init.
+    ///
+    #[cfg(feature = "serde")]
+    pub fn try_register_write_channel_serde<T: AnyClone + serde::Serialize>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+        initial_value: T,
+    ) -> Result<ChannelOwnerToken<T>, ChannelError> {
+        channel_store.try_register_write_channel_serde(name, self.owner_id, initial_value)
+    }
+
+    /// Register a new owned queue channel into the channels list. Unlike a write channel which
+    /// holds a single overwritable value, a queue channel carries every message published into it
+    /// and each reader drains the messages it has not read yet.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Unique channel name in string form.
+    /// * 'capacity' - Maximum number of messages retained for readers, must be non-zero.
+    /// * 'policy' - Action taken when a message is published into a full queue.
+    ///
+    pub fn register_queue_channel<T: AnyClone>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+        capacity: usize,
+        policy: DropPolicy,
+    ) -> ChannelPublisherToken<T> {
+        channel_store.register_queue_channel(name, self.owner_id, capacity, policy)
+    }
+
+    /// Fallible variant of register_queue_channel() which returns an error instead of panicking
+    /// when the name is empty or already in use.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Unique channel name in string form.
+    /// * 'capacity' - Maximum number of messages retained for readers, must be non-zero.
+    /// * 'policy' - Action taken when a message is published into a full queue.
+    ///
+    pub fn try_register_queue_channel<T: AnyClone>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+        capacity: usize,
+        policy: DropPolicy,
+    ) -> Result<ChannelPublisherToken<T>, ChannelError> {
+        channel_store.try_register_queue_channel(name, self.owner_id, capacity, policy)
+    }
+
+    /// Method used to obtain ownership of a dangling channel.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Name of the channel to be owned by the caller.
+    ///
+    pub fn obtain_channel_ownership<T: 'static>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+    ) -> ChannelOwnerToken<T> {
+        channel_store.obtain_channel_ownership::<T>(name, self.owner_id)
+    }
+
+    /// Former name of obtain_channel_ownership(), which panics on failure despite its try prefix.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Name of the channel to be owned by the caller.
+    ///
+    #[deprecated(
+        note = "renamed to obtain_channel_ownership(), use try_acquire_channel_ownership() to handle errors"
+    )]
+    pub fn try_obtain_channel_ownership<T: 'static>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+    ) -> ChannelOwnerToken<T> {
+        self.obtain_channel_ownership(channel_store, name)
+    }
+
+    /// Fallible variant of obtain_channel_ownership() which returns an error instead of panicking
+    /// when the channel does not exist, is already owned or holds a different type.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Name of the channel to be owned by the caller.
+    ///
+    pub fn try_acquire_channel_ownership<T: 'static>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+    ) -> Result<ChannelOwnerToken<T>, ChannelError> {
+        channel_store.try_acquire_channel_ownership::<T>(name, self.owner_id)
+    }
+
+    pub fn query_unowned_dangling_channel_names(
+        &self,
+        channel_store: &ChannelStore,
+    ) -> Vec<String> {
+        channel_store.query_unowned_dangling_channel_names()
+    }
+}
+
+pub struct ChannelReadBuilder {
+    owner_id: usize,
+}
+
+impl ChannelReadBuilder {
+    pub fn new(owner_id: usize) -> ChannelReadBuilder {
+        ChannelReadBuilder { owner_id }
+    }
+
+    /// Bind a caller to an owned channel for access.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Name of the channel requested for access.
+    ///
+    pub fn bind_read_channel<T: 'static>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+    ) -> ChannelReaderToken<T> {
+        channel_store.bind_read_channel(name, self.owner_id)
+    }
+
+    /// Fallible variant of bind_read_channel() which returns an error instead of panicking
+    /// when the channel does not exist, has no owner or holds a different type.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Name of the channel requested for access.
+    ///
+    pub fn try_bind_read_channel<T: 'static>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+    ) -> Result<ChannelReaderToken<T>, ChannelError> {
+        channel_store.try_bind_read_channel(name, self.owner_id)
     }
 
-    /// Register a new owned channel into the channels list.
+    /// Limit the age of the values the caller accepts from a channel. When the caller is
+    /// dispatched while the channel was last written more than 'max_age_ticks' ticks ago, the
+    /// runner reports a stale channel diagnostic (LintRule::StaleChannel). The caller must have
+    /// bound the channel within the same channel store beforehand.
     ///
     /// ### Arguments
     /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
-    /// * 'name' - Unique channel name in string form.
-    /// * 'initial_value' - Value to be assigned into this channel on init.
+    /// * 'name' - Name of the channel to be limited.
+    /// * 'max_age_ticks' - Largest accepted number of ticks since the last channel write.
     ///
-    pub fn register_write_channel<T: AnyClone>(
+    pub fn set_staleness_limit(
         &self,
         channel_store: &mut ChannelStore,
         name: String,
-        initial_value: T,
-    ) -> ChannelOwnerToken<T> {
-        channel_store.register_write_channel(name, self.owner_id, initial_value)
+        max_age_ticks: u64,
+    ) {
+        channel_store.set_staleness_limit(name, self.owner_id, max_age_ticks)
     }
 
-    /// Method used to obtain ownership of a dangling channel.
+    /// Fallible variant of set_staleness_limit() which returns an error instead of panicking
+    /// when the channel does not exist or is not read by the caller.
     ///
     /// ### Arguments
     /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
-    /// * 'name' - Name of the channel to be owned by the caller.
+    /// * 'name' - Name of the channel to be limited.
+    /// * 'max_age_ticks' - Largest accepted number of ticks since the last channel write.
     ///
-    pub fn try_obtain_channel_ownership<T: 'static>(
+    pub fn try_set_staleness_limit(
         &self,
         channel_store: &mut ChannelStore,
         name: String,
-    ) -> ChannelOwnerToken<T> {
-        channel_store.try_obtain_channel_ownership::<T>(name, self.owner_id)
+        max_age_ticks: u64,
+    ) -> Result<(), ChannelError> {
+        channel_store.try_set_staleness_limit(name, self.owner_id, max_age_ticks)
     }
 
-    pub fn query_unowned_dangling_channel_names(
+    /// Bind a caller to a channel for accessing the values the channel held over the past ticks.
+    /// Unlike a behind channel which holds the value of the previous dispatch of the caller, a
+    /// history channel records one value per tick at the end of each tick. As with behind
+    /// channels the caller is not ordered after the channel owner.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Name of the channel for past value access.
+    /// * 'depth' - Number of past values accessible through the token, must be non-zero.
+    ///
+    pub fn bind_read_history_channel<T: 'static>(
         &self,
-        channel_store: &ChannelStore,
-    ) -> Vec<String> {
-        channel_store.query_unowned_dangling_channel_names()
+        channel_store: &mut ChannelStore,
+        name: String,
+        depth: usize,
+    ) -> ChannelHistoryToken<T> {
+        channel_store.bind_read_history_channel(name, self.owner_id, depth)
     }
-}
 
-pub struct ChannelReadBuilder {
-    owner_id: usize,
-}
+    /// Fallible variant of bind_read_history_channel() which returns an error instead of
+    /// panicking when the channel does not exist, has no owner, holds a different type or the
+    /// requested depth is zero.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Name of the channel for past value access.
+    /// * 'depth' - Number of past values accessible through the token, must be non-zero.
+    ///
+    pub fn try_bind_read_history_channel<T: 'static>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+        depth: usize,
+    ) -> Result<ChannelHistoryToken<T>, ChannelError> {
+        channel_store.try_bind_read_history_channel(name, self.owner_id, depth)
+    }
 
-impl ChannelReadBuilder {
-    pub fn new(owner_id: usize) -> ChannelReadBuilder {
-        ChannelReadBuilder { owner_id }
+    /// Bind a caller to a queue channel. The caller obtains its own cursor starting at the next
+    /// published message, messages are retained until every bound reader has drained them.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Name of the queue channel requested for access.
+    ///
+    pub fn bind_queue_channel<T: AnyClone>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+    ) -> ChannelSubscriberToken<T> {
+        channel_store.bind_queue_channel(name, self.owner_id)
     }
 
-    /// Bind a caller to an owned channel for access.
+    /// Fallible variant of bind_queue_channel() which returns an error instead of panicking
+    /// when the channel does not exist, has no owner or is not a queue channel of type T.
     ///
     /// ### Arguments
     /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
-    /// * 'name' - Name of the channel requested for access.
+    /// * 'name' - Name of the queue channel requested for access.
     ///
-    pub fn bind_read_channel<T: 'static>(
+    pub fn try_bind_queue_channel<T: AnyClone>(
         &self,
         channel_store: &mut ChannelStore,
         name: String,
-    ) -> ChannelReaderToken<T> {
-        channel_store.bind_read_channel(name, self.owner_id)
+    ) -> Result<ChannelSubscriberToken<T>, ChannelError> {
+        channel_store.try_bind_queue_channel(name, self.owner_id)
     }
 
     /// Bind a caller to a channel for accessing the previous value of a channel. Access to the
@@ -366,7 +1905,22 @@ impl ChannelReadBuilder {
         channel_store: &mut ChannelStore,
         name: String,
     ) -> ChannelBehindToken<T> {
-        channel_store.bind_read_behind_channel(name)
+        channel_store.bind_read_behind_channel(name, self.owner_id)
+    }
+
+    /// Fallible variant of bind_read_behind_channel() which returns an error instead of panicking
+    /// when the channel does not exist, has no owner or holds a different type.
+    ///
+    /// ### Argument
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Name of the channel for previous value access.
+    ///
+    pub fn try_bind_read_behind_channel<T: 'static>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+    ) -> Result<ChannelBehindToken<T>, ChannelError> {
+        channel_store.try_bind_read_behind_channel(name, self.owner_id)
     }
 }
 
@@ -397,15 +1951,42 @@ impl ChannelDanglingBuilder {
     ) -> ChannelReaderToken<T> {
         channel_store.register_dangling_channel(name, self.owner_id, default_value)
     }
+
+    /// Fallible variant of register_dangling_channel() which returns an error instead of panicking
+    /// when the name is empty or already in use.
+    ///
+    /// ### Arguments
+    /// * 'channel_store' - ChannelStore object in which to perform channel operations on.
+    /// * 'name' - Unique channel name in string form.
+    /// * 'default_value' - Value to be assigned into this channel on init.
+    ///
+    pub fn try_register_dangling_channel<T: AnyClone>(
+        &self,
+        channel_store: &mut ChannelStore,
+        name: String,
+        default_value: T,
+    ) -> Result<ChannelReaderToken<T>, ChannelError> {
+        channel_store.try_register_dangling_channel(name, self.owner_id, default_value)
+    }
 }
 
 #[cfg(test)]
 mod unit_tests {
-    use alloc::{string::ToString, vec};
-
-    use crate::channel::{store::IdType, token::ChannelTokenOps};
-
-    use super::{ChannelStore, RegViewProducer};
+    use alloc::{
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+    use core::time::Duration;
+
+    use crate::channel::{
+        error::ChannelError, queue::DropPolicy, snapshot::SnapshotError, store::IdType,
+        token::ChannelTokenOps,
+    };
+
+    use super::{
+        ChannelInfo, ChannelReader, ChannelStore, OBSERVER_ID, ReaderKind, RegViewProducer,
+    };
 
     #[test]
     fn test_register_write_channel() {
@@ -418,7 +1999,7 @@ mod unit_tests {
             8u8,
         );
 
-        assert_eq!(token_test1.get_accessor_id(), 0);
+        assert_eq!(token_test1.get_location().slot, 0);
 
         let test2_channel_name = "test2.test.channel";
         let token_test2 = channel_store.register_write_channel(
@@ -427,7 +2008,7 @@ mod unit_tests {
             10u8,
         );
 
-        assert_eq!(token_test2.get_accessor_id(), 1);
+        assert_eq!(token_test2.get_location().slot, 1);
 
         let test1_channel = &channel_store.channels[channel_store
             .get_existing_channel_idx(test1_channel_name)
@@ -436,7 +2017,7 @@ mod unit_tests {
     }
 
     #[test]
-    #[should_panic(expected = "assertion failed: self.is_unique_channel_name(name.as_str())")]
+    #[should_panic(expected = "Channel [test1.test.channel] already exists")]
     fn test_duplicate_register_write_channel() {
         let mut channel_store = ChannelStore::default();
         let test1_channel_name = "test1.test.channel";
@@ -447,7 +2028,7 @@ mod unit_tests {
             8u8,
         );
 
-        assert_eq!(token_test1.get_accessor_id(), 0);
+        assert_eq!(token_test1.get_location().slot, 0);
         let _token_test2 = channel_store.register_write_channel(
             test1_channel_name.to_string(),
             test_owner_id,
@@ -456,7 +2037,7 @@ mod unit_tests {
     }
 
     #[test]
-    #[should_panic(expected = "assertion failed: !name.is_empty(")]
+    #[should_panic(expected = "Channel name cannot be empty")]
     fn test_empty_name_register_write_channel() {
         let mut channel_store = ChannelStore::default();
         let test1_channel_name = "";
@@ -483,7 +2064,7 @@ mod unit_tests {
     }
 
     #[test]
-    #[should_panic(expected = "Channel [test1.test.channel] does not exist.")]
+    #[should_panic(expected = "Channel [test1.test.channel] does not exist")]
     fn test_empty_bind_read_channel() {
         let mut channel_store = ChannelStore::default();
         let test1_channel_name = "test1.test.channel";
@@ -494,7 +2075,7 @@ mod unit_tests {
     }
 
     #[test]
-    #[should_panic(expected = "Channel [test2.test.channel] does not exist.")]
+    #[should_panic(expected = "Channel [test2.test.channel] does not exist")]
     fn test_mismatch_bind_read_channel() {
         let mut channel_store = ChannelStore::default();
         let test1_channel_name = "test1.test.channel";
@@ -525,7 +2106,7 @@ mod unit_tests {
             vec!["test.test1".to_string(), "test.test3".to_string()]
         );
 
-        channel_store.try_obtain_channel_ownership::<u8>("test.test1".to_string(), 2);
+        channel_store.obtain_channel_ownership::<u8>("test.test1".to_string(), 2);
         assert!(matches!(
             channel_store.channels.first().unwrap().owner_id,
             IdType::Owner(2)
@@ -537,12 +2118,12 @@ mod unit_tests {
     }
 
     #[test]
-    #[should_panic(expected = "Channel [test.test1] already has an owner.")]
+    #[should_panic(expected = "Channel [test.test1] already has an owner")]
     fn test_dangling_channels_multi_owner() {
         let mut channel_store = ChannelStore::default();
         channel_store.register_dangling_channel("test.test1".to_string(), 1, 90u8);
-        channel_store.try_obtain_channel_ownership::<u8>("test.test1".to_string(), 2);
-        channel_store.try_obtain_channel_ownership::<u8>("test.test1".to_string(), 3);
+        channel_store.obtain_channel_ownership::<u8>("test.test1".to_string(), 2);
+        channel_store.obtain_channel_ownership::<u8>("test.test1".to_string(), 3);
     }
 
     #[test]
@@ -551,26 +2132,30 @@ mod unit_tests {
         channel_store.register_write_channel("test.test1".to_string(), 1, 70u8);
         channel_store.bind_read_channel::<u8>("test.test1".to_string(), 2);
         let behind_tok: crate::channel::token::ChannelBehindToken<u8> =
-            channel_store.bind_read_behind_channel("test.test1".to_string());
+            channel_store.bind_read_behind_channel("test.test1".to_string(), 2);
 
-        assert_eq!(behind_tok.get_accessor_id(), 0usize);
-        assert!(channel_store.channels.first().unwrap().behind_reg.is_some());
-        assert_eq!(
-            channel_store.active_behind_channels_idx.first().unwrap(),
-            &0usize
-        );
-        assert_eq!(channel_store.active_behind_channels_idx.len(), 1);
+        assert_eq!(behind_tok.get_location().slot, 1usize);
+        let behind_reg = channel_store.active_behind_regs.first().unwrap();
+        assert_eq!(behind_reg.channel_idx, 0usize);
+        assert_eq!(behind_reg.slot, 1usize);
+        assert_eq!(behind_reg.reader_id, 2usize);
+        assert_eq!(channel_store.active_behind_regs.len(), 1);
+
+        // Binding the same channel again from the same reader reuses the behind register.
+        let behind_tok_2: crate::channel::token::ChannelBehindToken<u8> =
+            channel_store.bind_read_behind_channel("test.test1".to_string(), 2);
+        assert_eq!(behind_tok_2.get_location().slot, 1usize);
+        assert_eq!(channel_store.active_behind_regs.len(), 1);
 
         channel_store.register_write_channel("test.test2".to_string(), 1, 70u8);
-        assert!(channel_store.channels.get(1).unwrap().behind_reg.is_none());
-        assert_eq!(channel_store.active_behind_channels_idx.len(), 1)
+        assert_eq!(channel_store.active_behind_regs.len(), 1)
     }
 
     #[test]
     fn test_behind_channel_update() {
         let mut channel_store = ChannelStore::default();
         let write_tok = channel_store.register_write_channel("test.test1".to_string(), 1, 70u8);
-        let behind_tok = channel_store.bind_read_behind_channel("test.test1".to_string());
+        let behind_tok = channel_store.bind_read_behind_channel("test.test1".to_string(), 2);
 
         let mut reg_val: u8 = channel_store.grab(&write_tok).get();
         assert_eq!(reg_val, 70u8);
@@ -583,7 +2168,7 @@ mod unit_tests {
         reg_behind_val = channel_store.grab(&behind_tok).get();
         assert_eq!(reg_behind_val, 70u8);
 
-        channel_store.update_active_behind_registers();
+        channel_store.update_active_behind_registers(|_| true);
 
         channel_store.grab(&write_tok).set(100u8);
         reg_val = channel_store.grab(&write_tok).get();
@@ -593,7 +2178,7 @@ mod unit_tests {
     }
 
     #[test]
-    #[should_panic(expected = "Channel [test.test1] does match the requested type.")]
+    #[should_panic(expected = "Channel [test.test1] does not match the requested type")]
     fn test_channel_register_read_mismatched_type() {
         let mut channel_store = ChannelStore::default();
         channel_store.register_write_channel("test.test1".to_string(), 1, 70u8);
@@ -601,18 +2186,587 @@ mod unit_tests {
     }
 
     #[test]
-    #[should_panic(expected = "Channel [test.test1] does match the requested type.")]
+    #[should_panic(expected = "Channel [test.test1] does not match the requested type")]
     fn test_channel_register_behind_mismatched_type() {
         let mut channel_store = ChannelStore::default();
         channel_store.register_write_channel("test.test1".to_string(), 1, 70u8);
-        channel_store.bind_read_behind_channel::<u16>("test.test1".to_string());
+        channel_store.bind_read_behind_channel::<u16>("test.test1".to_string(), 2);
     }
 
     #[test]
-    #[should_panic(expected = "Channel [test.test1] does match the requested type.")]
+    #[should_panic(expected = "Channel [test.test1] does not match the requested type")]
     fn test_channel_register_dangling_mismatched_type() {
         let mut channel_store = ChannelStore::default();
         channel_store.register_dangling_channel("test.test1".to_string(), 1, 90u8);
-        channel_store.try_obtain_channel_ownership::<u16>("test.test1".to_string(), 2);
+        channel_store.obtain_channel_ownership::<u16>("test.test1".to_string(), 2);
+    }
+
+    #[test]
+    fn test_try_register_errors() {
+        let mut channel_store = ChannelStore::default();
+        assert_eq!(
+            channel_store
+                .try_register_write_channel(String::new(), 1, 8u8)
+                .err(),
+            Some(ChannelError::EmptyName { requester: 1 })
+        );
+
+        channel_store
+            .try_register_write_channel("test.test1".to_string(), 1, 8u8)
+            .unwrap();
+        assert_eq!(
+            channel_store
+                .try_register_dangling_channel("test.test1".to_string(), 2, 8u8)
+                .err(),
+            Some(ChannelError::DuplicateName {
+                name: "test.test1".to_string(),
+                requester: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_bind_errors() {
+        let mut channel_store = ChannelStore::default();
+        channel_store.register_write_channel("test.test1".to_string(), 1, 70u8);
+        channel_store.register_dangling_channel("test.test2".to_string(), 1, 70u8);
+
+        assert_eq!(
+            channel_store
+                .try_bind_read_channel::<u8>("test.missing".to_string(), 2)
+                .err(),
+            Some(ChannelError::NotFound {
+                name: "test.missing".to_string(),
+                requester: 2
+            })
+        );
+        assert_eq!(
+            channel_store
+                .try_bind_read_behind_channel::<u8>("test.test2".to_string(), 2)
+                .err(),
+            Some(ChannelError::NoOwner {
+                name: "test.test2".to_string(),
+                requester: 2
+            })
+        );
+        assert_eq!(
+            channel_store
+                .try_bind_read_channel::<u16>("test.test1".to_string(), 2)
+                .err(),
+            Some(ChannelError::TypeMismatch {
+                name: "test.test1".to_string(),
+                requester: 2,
+                expected: "u16",
+                actual: "u8"
+            })
+        );
+        assert!(channel_store.active_behind_regs.is_empty());
+    }
+
+    #[test]
+    fn test_try_acquire_channel_ownership_errors() {
+        let mut channel_store = ChannelStore::default();
+        channel_store.register_dangling_channel("test.test1".to_string(), 1, 90u8);
+
+        assert_eq!(
+            channel_store
+                .try_acquire_channel_ownership::<u16>("test.test1".to_string(), 2)
+                .err(),
+            Some(ChannelError::TypeMismatch {
+                name: "test.test1".to_string(),
+                requester: 2,
+                expected: "u16",
+                actual: "u8"
+            })
+        );
+        assert!(
+            channel_store
+                .try_acquire_channel_ownership::<u8>("test.test1".to_string(), 2)
+                .is_ok()
+        );
+        assert_eq!(
+            channel_store
+                .try_acquire_channel_ownership::<u8>("test.test1".to_string(), 3)
+                .err(),
+            Some(ChannelError::AlreadyOwned {
+                name: "test.test1".to_string(),
+                requester: 3,
+                owner: 2
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid accessor token.")]
+    fn test_grab_foreign_token() {
+        let mut channel_store_1 = ChannelStore::default();
+        let mut channel_store_2 = ChannelStore::default();
+        channel_store_1.register_write_channel("test.test1".to_string(), 1, 70u8);
+        let write_tok = channel_store_2.register_write_channel("test.test1".to_string(), 1, 70u8);
+
+        channel_store_1.grab(&write_tok).get();
+    }
+
+    #[test]
+    fn test_typed_slabs() {
+        let mut channel_store = ChannelStore::default();
+        let tok_u8 = channel_store.register_write_channel("test.test1".to_string(), 1, 70u8);
+        let tok_u16 = channel_store.register_write_channel("test.test2".to_string(), 1, 700u16);
+        let tok_u8_2 = channel_store.register_write_channel("test.test3".to_string(), 1, 71u8);
+
+        assert_eq!(channel_store.slabs.len(), 2);
+        assert_eq!(tok_u8.get_location().slab, tok_u8_2.get_location().slab);
+        assert_ne!(tok_u8.get_location().slab, tok_u16.get_location().slab);
+
+        channel_store.grab(&tok_u16).update(|value| *value += 1);
+        assert_eq!(channel_store.grab(&tok_u8).get(), 70u8);
+        assert_eq!(channel_store.grab(&tok_u16).get(), 701u16);
+        assert_eq!(channel_store.grab(&tok_u8_2).get(), 71u8);
+    }
+
+    #[test]
+    fn test_channels_info() {
+        let mut channel_store = ChannelStore::default();
+        channel_store.register_write_channel("test.test1".to_string(), 1, 70u8);
+        channel_store.register_dangling_channel("test.test2".to_string(), 3, 0u16);
+        channel_store.bind_read_channel::<u8>("test.test1".to_string(), 2);
+        channel_store.bind_read_channel::<u8>("test.test1".to_string(), 3);
+        channel_store.bind_read_channel::<u8>("test.test1".to_string(), 2);
+
+        let channels: Vec<ChannelInfo> = channel_store.channels().collect();
+        assert_eq!(
+            channels,
+            vec![
+                ChannelInfo {
+                    name: "test.test1",
+                    owner: Some(1),
+                    readers: &[
+                        ChannelReader {
+                            id: 2,
+                            kind: ReaderKind::Current
+                        },
+                        ChannelReader {
+                            id: 3,
+                            kind: ReaderKind::Current
+                        }
+                    ],
+                    type_name: "u8",
+                    dangling: false,
+                },
+                ChannelInfo {
+                    name: "test.test2",
+                    owner: None,
+                    readers: &[ChannelReader {
+                        id: 3,
+                        kind: ReaderKind::Current
+                    }],
+                    type_name: "u16",
+                    dangling: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_channel_reader_metadata() {
+        let mut channel_store = ChannelStore::default();
+        channel_store.register_write_channel("test.test1".to_string(), 1, 70u8);
+        channel_store.register_write_channel("test.test2".to_string(), 1, 70u8);
+        channel_store.bind_read_channel::<u8>("test.test1".to_string(), 2);
+        channel_store.bind_read_behind_channel::<u8>("test.test1".to_string(), 2);
+        channel_store.bind_read_behind_channel::<u8>("test.test1".to_string(), 3);
+        channel_store.bind_read_behind_channel::<u8>("test.test1".to_string(), 3);
+
+        let channel = channel_store.channel("test.test1").unwrap();
+        assert_eq!(channel.fan_out(), 2);
+        assert_eq!(
+            channel
+                .readers_of_kind(ReaderKind::Current)
+                .collect::<Vec<usize>>(),
+            vec![2]
+        );
+        assert_eq!(
+            channel
+                .readers_of_kind(ReaderKind::Behind)
+                .collect::<Vec<usize>>(),
+            vec![2, 3]
+        );
+        assert!(channel_store.channel("test.missing").is_none());
+
+        let unread: Vec<&str> = channel_store
+            .unread_channels()
+            .map(|channel| channel.name)
+            .collect();
+        assert_eq!(unread, vec!["test.test2"]);
+    }
+
+    #[test]
+    fn test_bind_observer() {
+        let mut channel_store = ChannelStore::default();
+        let write_tok = channel_store.register_write_channel("test.test1".to_string(), 1, 70u8);
+        let observer_tok = channel_store.try_bind_observer::<u8>("test.test1").unwrap();
+
+        channel_store.grab(&write_tok).set(71);
+        assert_eq!(channel_store.grab(&observer_tok).get(), 71);
+        assert!(channel_store.channel("test.test1").unwrap().is_unread());
+        assert_eq!(
+            channel_store.try_bind_observer::<u16>("test.test1").err(),
+            Some(ChannelError::TypeMismatch {
+                name: "test.test1".to_string(),
+                requester: OBSERVER_ID,
+                expected: "u16",
+                actual: "u8"
+            })
+        );
+    }
+
+    #[test]
+    fn test_queue_channel() {
+        let mut channel_store = ChannelStore::default();
+        let publish_tok = channel_store.register_queue_channel::<u8>(
+            "test.events".to_string(),
+            1,
+            4,
+            DropPolicy::DropNewest,
+        );
+        let fast_tok = channel_store.bind_queue_channel::<u8>("test.events".to_string(), 2);
+        let slow_tok = channel_store.bind_queue_channel::<u8>("test.events".to_string(), 3);
+
+        let publisher = channel_store.grab(&publish_tok);
+        assert!(publisher.push(1));
+        assert!(publisher.push(2));
+        assert_eq!(channel_store.grab(&fast_tok).drain(), vec![1, 2]);
+        assert!(publisher.push(3));
+        assert_eq!(channel_store.grab(&fast_tok).drain(), vec![3]);
+        assert_eq!(channel_store.grab(&slow_tok).pending(), 3);
+        assert_eq!(channel_store.grab(&slow_tok).drain(), vec![1, 2, 3]);
+        assert_eq!(publisher.dropped(), 0);
+
+        let dependencies: Vec<(usize, usize)> = channel_store
+            .node_graph
+            .as_ref()
+            .unwrap()
+            .dependencies()
+            .map(|dependency| (dependency.owner, dependency.consumer))
+            .collect();
+        assert_eq!(dependencies, vec![(1, 2), (1, 3)]);
+        assert_eq!(channel_store.channel("test.events").unwrap().fan_out(), 2);
+    }
+
+    #[test]
+    fn test_queue_channel_clock() {
+        let mut channel_store = ChannelStore::default();
+        let publish_tok = channel_store.register_queue_channel::<u8>(
+            "test.events".to_string(),
+            1,
+            4,
+            DropPolicy::DropNewest,
+        );
+        channel_store.bind_queue_channel::<u8>("test.events".to_string(), 2);
+        channel_store
+            .try_set_staleness_limit("test.events".to_string(), 2, 0)
+            .unwrap();
+
+        // Binding a reader is not a write, the queue is stale until a message is published.
+        assert_eq!(channel_store.check_staleness_limits(|_| true).len(), 1);
+
+        channel_store
+            .clock
+            .advance(2, core::time::Duration::from_millis(20));
+        channel_store.grab(&publish_tok).push(1);
+        assert!(channel_store.check_staleness_limits(|_| true).is_empty());
+        let channel = &channel_store.channels[0];
+        assert_eq!(
+            channel_store.slabs[channel.slab].last_write_tick(channel.slot),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_queue_channel_type_errors() {
+        let mut channel_store = ChannelStore::default();
+        channel_store.register_queue_channel::<u8>(
+            "test.events".to_string(),
+            1,
+            4,
+            DropPolicy::DropOldest,
+        );
+        channel_store.register_write_channel("test.value".to_string(), 1, 0u8);
+
+        assert!(matches!(
+            channel_store.try_bind_read_channel::<u8>("test.events".to_string(), 2),
+            Err(ChannelError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            channel_store.try_bind_queue_channel::<u8>("test.value".to_string(), 2),
+            Err(ChannelError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_history_channel() {
+        let mut channel_store = ChannelStore::default();
+        let write_tok = channel_store.register_write_channel("test.test1".to_string(), 1, 0u32);
+        let short_tok =
+            channel_store.bind_read_history_channel::<u32>("test.test1".to_string(), 2, 2);
+        let long_tok =
+            channel_store.bind_read_history_channel::<u32>("test.test1".to_string(), 3, 3);
+        assert_eq!(channel_store.active_history_regs.len(), 1);
+        assert_eq!(channel_store.active_history_regs[0].depth, 3);
+
+        assert_eq!(channel_store.grab(&long_tok).get(3), 0);
+        for value in 1..=4 {
+            channel_store.grab(&write_tok).set(value);
+            channel_store.update_history_registers();
+        }
+        channel_store.grab(&write_tok).set(5);
+
+        let long_view = channel_store.grab(&long_tok);
+        assert_eq!(long_view.depth(), 3);
+        assert_eq!(
+            (1..=3).map(|age| long_view.get(age)).collect::<Vec<u32>>(),
+            vec![4, 3, 2]
+        );
+        assert_eq!(*channel_store.grab(&short_tok).borrow(2), 3);
+
+        let behind: Vec<(usize, usize)> = channel_store
+            .behind_dependencies()
+            .iter()
+            .map(|dependency| (dependency.owner, dependency.consumer))
+            .collect();
+        assert_eq!(behind, vec![(1, 2), (1, 3)]);
+        assert!(
+            channel_store
+                .node_graph
+                .as_ref()
+                .unwrap()
+                .dependencies()
+                .next()
+                .is_none()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "History index [3] is out of range, the history depth is [2].")]
+    fn test_history_channel_out_of_range() {
+        let mut channel_store = ChannelStore::default();
+        channel_store.register_write_channel("test.test1".to_string(), 1, 0u32);
+        let history_tok =
+            channel_store.bind_read_history_channel::<u32>("test.test1".to_string(), 2, 2);
+        channel_store.grab(&history_tok).get(3);
+    }
+
+    #[test]
+    fn test_staleness_limit_requires_reader() {
+        let mut channel_store = ChannelStore::default();
+        channel_store.register_write_channel("test.test1".to_string(), 1, 0u32);
+        assert_eq!(
+            channel_store.try_set_staleness_limit("test.test1".to_string(), 2, 1),
+            Err(ChannelError::NotRead {
+                name: "test.test1".to_string(),
+                requester: 2
+            })
+        );
+        assert!(channel_store.staleness_limits.is_empty());
+
+        channel_store.bind_read_channel::<u32>("test.test1".to_string(), 2);
+        assert_eq!(
+            channel_store.try_set_staleness_limit("test.test1".to_string(), 2, 1),
+            Ok(())
+        );
+        assert_eq!(channel_store.staleness_limits.len(), 1);
+    }
+
+    #[test]
+    fn test_history_channel_zero_depth() {
+        let mut channel_store = ChannelStore::default();
+        channel_store.register_write_channel("test.test1".to_string(), 1, 0u32);
+        assert_eq!(
+            channel_store
+                .try_bind_read_history_channel::<u32>("test.test1".to_string(), 2, 0)
+                .err(),
+            Some(ChannelError::ZeroHistoryDepth {
+                name: "test.test1".to_string(),
+                requester: 2
+            })
+        );
+        assert!(channel_store.active_history_regs.is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut channel_store = ChannelStore::default();
+        let write_tok = channel_store.register_write_channel("test.test1".to_string(), 1, 10u32);
+        let behind_tok = channel_store.bind_read_behind_channel::<u32>("test.test1".to_string(), 2);
+        let history_tok =
+            channel_store.bind_read_history_channel::<u32>("test.test1".to_string(), 3, 2);
+
+        channel_store.grab(&write_tok).set(11);
+        channel_store.update_active_behind_registers(|_| true);
+        channel_store.update_history_registers();
+        let snapshot = channel_store.snapshot();
+        assert_eq!(
+            snapshot.channel_names().collect::<Vec<&str>>(),
+            vec!["test.test1"]
+        );
+
+        channel_store.grab(&write_tok).set(12);
+        channel_store.update_active_behind_registers(|_| true);
+        channel_store.update_history_registers();
+        channel_store.restore(&snapshot).unwrap();
+
+        assert_eq!(channel_store.grab(&write_tok).get(), 11);
+        assert_eq!(channel_store.grab(&behind_tok).get(), 11);
+        assert_eq!(channel_store.grab(&history_tok).get(1), 11);
+        assert_eq!(channel_store.grab(&history_tok).get(2), 10);
+
+        // The clock and the staleness state are restored with the registers.
+        let read_tok = channel_store.bind_read_channel::<u32>("test.test1".to_string(), 4);
+        channel_store.set_staleness_limit("test.test1".to_string(), 4, 1);
+        channel_store.clock.advance(1, Duration::from_millis(10));
+        channel_store.grab(&write_tok).set(13);
+        let fresh_snapshot = channel_store.snapshot();
+        channel_store.clock.advance(5, Duration::from_millis(50));
+        assert_eq!(channel_store.check_staleness_limits(|_| true), vec![(0, 4)]);
+        assert!(!channel_store.grab(&read_tok).is_fresh());
+        channel_store.restore(&fresh_snapshot).unwrap();
+        assert!(channel_store.grab(&read_tok).is_fresh());
+        assert_eq!(channel_store.clock.timestamp(), Duration::from_millis(10));
+        channel_store.clock.advance(5, Duration::from_millis(50));
+        // The stale period is reported again as the limit was not exceeded when captured.
+        assert_eq!(channel_store.check_staleness_limits(|_| true), vec![(0, 4)]);
+
+        // Identically wired stores accept the snapshot.
+        let mut other_store = ChannelStore::default();
+        let other_tok = other_store.register_write_channel("test.test1".to_string(), 1, 0u32);
+        other_store.bind_read_behind_channel::<u32>("test.test1".to_string(), 2);
+        other_store.bind_read_history_channel::<u32>("test.test1".to_string(), 3, 2);
+        other_store.restore(&snapshot).unwrap();
+        assert_eq!(other_store.grab(&other_tok).get(), 11);
+    }
+
+    #[test]
+    fn test_snapshot_restore_errors() {
+        let mut channel_store = ChannelStore::default();
+        channel_store.register_write_channel("test.test1".to_string(), 1, 10u32);
+        let snapshot = channel_store.snapshot();
+
+        let mut renamed_store = ChannelStore::default();
+        renamed_store.register_write_channel("test.test2".to_string(), 1, 10u32);
+        assert_eq!(
+            renamed_store.restore(&snapshot),
+            Err(SnapshotError::ChannelName {
+                expected: "test.test2".to_string(),
+                actual: "test.test1".to_string(),
+            })
+        );
+
+        let mut retyped_store = ChannelStore::default();
+        retyped_store.register_write_channel("test.test1".to_string(), 1, 10u8);
+        assert_eq!(
+            retyped_store.restore(&snapshot),
+            Err(SnapshotError::ChannelType {
+                name: "test.test1".to_string(),
+                expected: "u8",
+                actual: "u32",
+            })
+        );
+
+        let mut behind_store = ChannelStore::default();
+        behind_store.register_write_channel("test.test1".to_string(), 1, 10u32);
+        behind_store.bind_read_behind_channel::<u32>("test.test1".to_string(), 2);
+        assert_eq!(
+            behind_store.restore(&snapshot),
+            Err(SnapshotError::RegisterLayout)
+        );
+
+        assert_eq!(
+            ChannelStore::default().restore(&snapshot),
+            Err(SnapshotError::ChannelCount {
+                expected: 0,
+                actual: 1,
+            })
+        );
+
+        // Borrowed registers are detected before any register is restored.
+        let mut borrowed_store = ChannelStore::default();
+        let write_tok = borrowed_store.register_write_channel("test.test1".to_string(), 1, 20u32);
+        let write_view = borrowed_store.grab(&write_tok);
+        let guard = write_view.borrow_mut();
+        assert_eq!(
+            borrowed_store.restore(&snapshot),
+            Err(SnapshotError::RegisterBorrowed {
+                name: "test.test1".to_string()
+            })
+        );
+        drop(guard);
+        assert_eq!(borrowed_store.grab(&write_tok).get(), 20);
+        borrowed_store.restore(&snapshot).unwrap();
+        assert_eq!(borrowed_store.grab(&write_tok).get(), 10);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_encode_channel() {
+        use crate::channel::codec::EncodeError;
+
+        let mut channel_store = ChannelStore::default();
+        let write_tok = channel_store.register_write_channel_serde(
+            "test.pose".to_string(),
+            1,
+            (1.5f64, vec![1u8, 2]),
+        );
+        channel_store.register_write_channel("test.opaque".to_string(), 1, 0u8);
+
+        channel_store
+            .grab(&write_tok)
+            .update(|value| value.1.push(3));
+        assert_eq!(
+            channel_store.encode_channel("test.pose"),
+            Ok(serde_json::json!([1.5, [1, 2, 3]]))
+        );
+        assert_eq!(
+            channel_store.serializable_channels().collect::<Vec<&str>>(),
+            vec!["test.pose"]
+        );
+        assert_eq!(
+            channel_store.encode_channel("test.opaque"),
+            Err(EncodeError::NotSerializable {
+                name: "test.opaque".to_string()
+            })
+        );
+        assert_eq!(
+            channel_store.encode_channel("test.missing"),
+            Err(EncodeError::NotFound {
+                name: "test.missing".to_string()
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_channel_schema() {
+        use crate::channel::codec::EncodeError;
+
+        let mut channel_store = ChannelStore::default();
+        let write_tok = channel_store.register_write_channel_serde(
+            "test.samples".to_string(),
+            1,
+            Vec::<u32>::new(),
+        );
+        assert_eq!(
+            channel_store.channel_schema("test.samples"),
+            Ok(serde_json::json!({ "type": "array" }))
+        );
+        assert!(matches!(
+            channel_store.channel_schema("test.missing"),
+            Err(EncodeError::NotFound { name }) if name == "test.missing"
+        ));
+
+        channel_store.grab(&write_tok).set(vec![1]);
+        assert_eq!(
+            channel_store.channel_schema("test.samples"),
+            Ok(serde_json::json!({ "type": "array", "items": { "type": "integer" } }))
+        );
     }
 }
@@ -1,9 +1,24 @@
 use core::marker::PhantomData;
 
+/// Location of a register within the typed storage of a channel store.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub(crate) struct RegLocation {
+    /// Id of the channel store which produced the location, zero for invalid locations.
+    pub store_id: usize,
+    /// Index of the typed slab holding the register.
+    pub slab: usize,
+    /// Index of the register within the slab.
+    pub slot: usize,
+}
+
 pub(super) trait ChannelTokenOps {
-    fn new(accessor_id: usize) -> Self;
+    fn new(location: RegLocation) -> Self;
+
+    /// Produce a token which does not refer to any channel. Used in place of a real token when
+    /// registration errors are being collected instead of raised.
+    fn invalid() -> Self;
 
-    fn get_accessor_id(&self) -> usize;
+    fn get_location(&self) -> RegLocation;
 
     fn is_valid(&self) -> bool;
 }
@@ -11,77 +26,201 @@ pub(super) trait ChannelTokenOps {
 /// Channel owner token used to access a channel with read and write permissions.
 #[derive(Default)]
 pub struct ChannelOwnerToken<T> {
-    accessor_id: usize,
-    is_valid: bool,
+    location: RegLocation,
     phantom_marker: PhantomData<T>,
 }
 
 /// Channel owner token used to access a channel with read only permissions.
 #[derive(Default)]
 pub struct ChannelReaderToken<T> {
-    accessor_id: usize,
-    is_valid: bool,
+    location: RegLocation,
     phantom_marker: PhantomData<T>,
 }
 
 /// Channel behind token used to read the previous channel value.
 #[derive(Default)]
 pub struct ChannelBehindToken<T> {
-    accessor_id: usize,
-    is_valid: bool,
+    location: RegLocation,
+    phantom_marker: PhantomData<T>,
+}
+
+/// Channel history token used to read the values a channel held over the past ticks.
+#[derive(Default)]
+pub struct ChannelHistoryToken<T> {
+    location: RegLocation,
+    /// Index of the history ring within the channel store.
+    history: usize,
+    /// Number of past values accessible through the token.
+    depth: usize,
+    phantom_marker: PhantomData<T>,
+}
+
+impl<T> ChannelHistoryToken<T> {
+    pub(super) fn with_history(location: RegLocation, history: usize, depth: usize) -> Self {
+        Self {
+            location,
+            history,
+            depth,
+            phantom_marker: PhantomData,
+        }
+    }
+
+    pub(super) fn get_history(&self) -> usize {
+        self.history
+    }
+
+    pub(super) fn get_depth(&self) -> usize {
+        self.depth
+    }
+}
+
+/// Queue channel publisher token used to push messages into a queue channel.
+#[derive(Default)]
+pub struct ChannelPublisherToken<T> {
+    location: RegLocation,
     phantom_marker: PhantomData<T>,
 }
 
+/// Queue channel subscriber token used to drain the messages of a queue channel through the
+/// cursor of the reader which bound it.
+#[derive(Default)]
+pub struct ChannelSubscriberToken<T> {
+    location: RegLocation,
+    /// Index of the reader cursor within the queue.
+    cursor: usize,
+    phantom_marker: PhantomData<T>,
+}
+
+impl<T> ChannelSubscriberToken<T> {
+    pub(super) fn with_cursor(location: RegLocation, cursor: usize) -> Self {
+        Self {
+            location,
+            cursor,
+            phantom_marker: PhantomData,
+        }
+    }
+
+    pub(super) fn get_cursor(&self) -> usize {
+        self.cursor
+    }
+}
+
 impl<T> ChannelTokenOps for ChannelOwnerToken<T> {
-    fn new(accessor_id: usize) -> Self {
+    fn new(location: RegLocation) -> Self {
         Self {
-            accessor_id,
-            is_valid: true,
+            location,
             phantom_marker: PhantomData,
         }
     }
 
-    fn get_accessor_id(&self) -> usize {
-        self.accessor_id
+    fn invalid() -> Self {
+        Self::new(RegLocation::default())
+    }
+
+    fn get_location(&self) -> RegLocation {
+        self.location
     }
 
     fn is_valid(&self) -> bool {
-        self.is_valid
+        self.location.store_id != 0
     }
 }
 
 impl<T> ChannelTokenOps for ChannelReaderToken<T> {
-    fn new(accessor_id: usize) -> Self {
+    fn new(location: RegLocation) -> Self {
         Self {
-            accessor_id,
-            is_valid: true,
+            location,
             phantom_marker: PhantomData,
         }
     }
 
-    fn get_accessor_id(&self) -> usize {
-        self.accessor_id
+    fn invalid() -> Self {
+        Self::new(RegLocation::default())
+    }
+
+    fn get_location(&self) -> RegLocation {
+        self.location
     }
 
     fn is_valid(&self) -> bool {
-        self.is_valid
+        self.location.store_id != 0
     }
 }
 
 impl<T> ChannelTokenOps for ChannelBehindToken<T> {
-    fn new(accessor_id: usize) -> Self {
+    fn new(location: RegLocation) -> Self {
+        Self {
+            location,
+            phantom_marker: PhantomData,
+        }
+    }
+
+    fn invalid() -> Self {
+        Self::new(RegLocation::default())
+    }
+
+    fn get_location(&self) -> RegLocation {
+        self.location
+    }
+
+    fn is_valid(&self) -> bool {
+        self.location.store_id != 0
+    }
+}
+
+impl<T> ChannelTokenOps for ChannelPublisherToken<T> {
+    fn new(location: RegLocation) -> Self {
         Self {
-            accessor_id,
-            is_valid: true,
+            location,
             phantom_marker: PhantomData,
         }
     }
 
-    fn get_accessor_id(&self) -> usize {
-        self.accessor_id
+    fn invalid() -> Self {
+        Self::new(RegLocation::default())
+    }
+
+    fn get_location(&self) -> RegLocation {
+        self.location
+    }
+
+    fn is_valid(&self) -> bool {
+        self.location.store_id != 0
+    }
+}
+
+impl<T> ChannelTokenOps for ChannelSubscriberToken<T> {
+    fn new(location: RegLocation) -> Self {
+        Self::with_cursor(location, 0)
+    }
+
+    fn invalid() -> Self {
+        Self::new(RegLocation::default())
+    }
+
+    fn get_location(&self) -> RegLocation {
+        self.location
+    }
+
+    fn is_valid(&self) -> bool {
+        self.location.store_id != 0
+    }
+}
+
+impl<T> ChannelTokenOps for ChannelHistoryToken<T> {
+    fn new(location: RegLocation) -> Self {
+        Self::with_history(location, 0, 0)
+    }
+
+    fn invalid() -> Self {
+        Self::new(RegLocation::default())
+    }
+
+    fn get_location(&self) -> RegLocation {
+        self.location
     }
 
     fn is_valid(&self) -> bool {
-        self.is_valid
+        self.location.store_id != 0
     }
 }
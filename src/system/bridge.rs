@@ -0,0 +1,453 @@
+use core::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr,
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+};
+
+use alloc::{boxed::Box, string::String, sync::Arc};
+
+use crate::channel::{
+    reg::AnyClone,
+    store::{ChannelReadBuilder, ChannelStore, ChannelWriteBuilder, RegViewProducer},
+    token::{ChannelOwnerToken, ChannelReaderToken},
+};
+
+use super::component::{Component, DispatchContext};
+
+/// Transfer semantics of a bridge.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BridgeMode {
+    /// Only the most recent exported value is kept. The importer observes the latest value
+    /// exported before its dispatch, intermediate values may be skipped.
+    Latest,
+    /// Every exported value is queued and the importer takes one value per dispatch. Values
+    /// exported while the queue is full are dropped and counted.
+    Queued { capacity: usize },
+}
+
+/// Single value handoff slot. Publishing replaces any value not yet taken.
+struct LatestSlot<T> {
+    value: AtomicPtr<T>,
+    marker: PhantomData<Box<T>>,
+}
+
+impl<T> LatestSlot<T> {
+    fn new() -> Self {
+        Self {
+            value: AtomicPtr::new(ptr::null_mut()),
+            marker: PhantomData,
+        }
+    }
+
+    fn publish(&self, value: T) {
+        let old = self
+            .value
+            .swap(Box::into_raw(Box::new(value)), Ordering::AcqRel);
+        if !old.is_null() {
+            // SAFETY: Non-null pointers within the slot are produced by Box::into_raw and
+            // ownership is transferred out of the slot by the swap.
+            drop(unsafe { Box::from_raw(old) });
+        }
+    }
+
+    fn take(&self) -> Option<T> {
+        let value = self.value.swap(ptr::null_mut(), Ordering::AcqRel);
+        if value.is_null() {
+            return None;
+        }
+
+        // SAFETY: Non-null pointers within the slot are produced by Box::into_raw and ownership
+        // is transferred out of the slot by the swap.
+        Some(*unsafe { Box::from_raw(value) })
+    }
+}
+
+impl<T> Drop for LatestSlot<T> {
+    fn drop(&mut self) {
+        self.take();
+    }
+}
+
+/// Bounded single producer single consumer queue.
+struct SpscQueue<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    /// Total number of values taken, only advanced by the consumer.
+    head: AtomicUsize,
+    /// Total number of values pushed, only advanced by the producer.
+    tail: AtomicUsize,
+}
+
+// SAFETY: Each slot is accessed by either the producer or the consumer as arbitrated by the head
+// and tail counters, values are moved between threads which requires T: Send.
+unsafe impl<T: Send> Sync for SpscQueue<T> {}
+
+impl<T> SpscQueue<T> {
+    fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "Bridge queue capacity must be non-zero.");
+        Self {
+            slots: (0..capacity)
+                .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+                .collect(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Push a value, returning the value back if the queue is full. Must only be called by the
+    /// single producer.
+    fn push(&self, value: T) -> Result<(), T> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        if tail.wrapping_sub(self.head.load(Ordering::Acquire)) == self.slots.len() {
+            return Err(value);
+        }
+
+        // SAFETY: The slot at the tail is not visible to the consumer until the tail is advanced.
+        unsafe { (*self.slots[tail % self.slots.len()].get()).write(value) };
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    /// Take the oldest value. Must only be called by the single consumer.
+    fn pop(&self) -> Option<T> {
+        let head = self.head.load(Ordering::Relaxed);
+        if head == self.tail.load(Ordering::Acquire) {
+            return None;
+        }
+
+        // SAFETY: The slot at the head was initialized by the producer before the tail was
+        // advanced past it, the producer does not reuse it until the head is advanced.
+        let value = unsafe { (*self.slots[head % self.slots.len()].get()).assume_init_read() };
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+        Some(value)
+    }
+
+    fn len(&self) -> usize {
+        self.tail
+            .load(Ordering::Acquire)
+            .wrapping_sub(self.head.load(Ordering::Acquire))
+    }
+}
+
+impl<T> Drop for SpscQueue<T> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+enum BridgeBuffer<T> {
+    Latest(LatestSlot<T>),
+    Queued(SpscQueue<T>),
+}
+
+struct BridgeShared<T> {
+    buffer: BridgeBuffer<T>,
+    /// Number of values dropped as the queue was full. Pointer sized so that bridges do not
+    /// require 64 bit atomics.
+    dropped: AtomicUsize,
+}
+
+/// Create a bridge moving the values of a channel from one runner into another. The exporter is
+/// added as a component to the source runner and reads the source channel. The importer is added
+/// as a component to the destination runner and owns the target channel.
+///
+/// Values are handed over without locks. The exporter publishes the source channel value each
+/// time it is dispatched, after the owner of the source channel within the same tick. The
+/// importer takes the handed over value when dispatched, before any reader of the target channel
+/// within the same tick. Runners may be dispatched from different threads.
+///
+/// ### Arguments
+/// * 'source_channel' - Name of the channel read within the source runner.
+/// * 'target_channel' - Name of the channel registered within the destination runner.
+/// * 'mode' - Transfer semantics of the bridge.
+/// * 'initial_value' - Value of the target channel until the first value is imported.
+///
+pub fn bridge<T: AnyClone>(
+    source_channel: &str,
+    target_channel: &str,
+    mode: BridgeMode,
+    initial_value: T,
+) -> (BridgeExporter<T>, BridgeImporter<T>) {
+    let shared = Arc::new(BridgeShared {
+        buffer: match mode {
+            BridgeMode::Latest => BridgeBuffer::Latest(LatestSlot::new()),
+            BridgeMode::Queued { capacity } => BridgeBuffer::Queued(SpscQueue::new(capacity)),
+        },
+        dropped: AtomicUsize::new(0),
+    });
+
+    (
+        BridgeExporter {
+            shared: shared.clone(),
+            channel: String::from(source_channel),
+            token: None,
+        },
+        BridgeImporter {
+            shared,
+            channel: String::from(target_channel),
+            initial_value: Some(initial_value),
+            token: None,
+        },
+    )
+}
+
+/// Read only view of the state of a bridge, usable after the bridge components were moved into
+/// their runners.
+pub struct BridgeMonitor<T> {
+    shared: Arc<BridgeShared<T>>,
+}
+
+impl<T> BridgeMonitor<T> {
+    /// Number of values dropped as the queue of a queued bridge was full.
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed) as u64
+    }
+
+    /// Number of values exported but not yet imported.
+    pub fn pending(&self) -> usize {
+        match &self.shared.buffer {
+            BridgeBuffer::Latest(slot) => {
+                usize::from(!slot.value.load(Ordering::Acquire).is_null())
+            }
+            BridgeBuffer::Queued(queue) => queue.len(),
+        }
+    }
+}
+
+/// Component exporting a channel of the source runner into a bridge.
+pub struct BridgeExporter<T> {
+    shared: Arc<BridgeShared<T>>,
+    channel: String,
+    token: Option<ChannelReaderToken<T>>,
+}
+
+impl<T> BridgeExporter<T> {
+    pub fn monitor(&self) -> BridgeMonitor<T> {
+        BridgeMonitor {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T: AnyClone> Component for BridgeExporter<T> {
+    fn register_read_channels(
+        &mut self,
+        channel_builder: ChannelReadBuilder,
+        channel_store: &mut ChannelStore,
+    ) {
+        self.token = Some(channel_builder.bind_read_channel(channel_store, self.channel.clone()));
+    }
+
+    fn dispatch(&mut self, channel_store: &ChannelStore, _context: &DispatchContext) {
+        let value = channel_store.grab(self.token.as_ref().unwrap()).get();
+        match &self.shared.buffer {
+            BridgeBuffer::Latest(slot) => slot.publish(value),
+            BridgeBuffer::Queued(queue) => {
+                if queue.push(value).is_err() {
+                    self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+}
+
+/// Component importing bridged values into an owned channel of the destination runner.
+pub struct BridgeImporter<T> {
+    shared: Arc<BridgeShared<T>>,
+    channel: String,
+    initial_value: Option<T>,
+    token: Option<ChannelOwnerToken<T>>,
+}
+
+impl<T> BridgeImporter<T> {
+    pub fn monitor(&self) -> BridgeMonitor<T> {
+        BridgeMonitor {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T: AnyClone> Component for BridgeImporter<T> {
+    fn register_write_channels(
+        &mut self,
+        channel_builder: ChannelWriteBuilder,
+        channel_store: &mut ChannelStore,
+    ) {
+        self.token = Some(channel_builder.register_write_channel(
+            channel_store,
+            self.channel.clone(),
+            self.initial_value.take().unwrap(),
+        ));
+    }
+
+    fn dispatch(&mut self, channel_store: &ChannelStore, _context: &DispatchContext) {
+        let value = match &self.shared.buffer {
+            BridgeBuffer::Latest(slot) => slot.take(),
+            BridgeBuffer::Queued(queue) => queue.pop(),
+        };
+        if let Some(value) = value {
+            channel_store.grab(self.token.as_ref().unwrap()).set(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use alloc::{boxed::Box, rc::Rc, string::ToString, vec::Vec};
+    use core::cell::RefCell;
+
+    use crate::{
+        channel::{
+            store::{ChannelReadBuilder, ChannelStore, ChannelWriteBuilder, RegViewProducer},
+            token::{ChannelOwnerToken, ChannelReaderToken},
+        },
+        system::{
+            component::{Component, DispatchContext},
+            runner::Runner,
+        },
+    };
+
+    use super::{BridgeMode, SpscQueue, bridge};
+
+    struct TestCounter(ChannelOwnerToken<u32>);
+    impl Component for TestCounter {
+        fn register_write_channels(
+            &mut self,
+            channel_builder: ChannelWriteBuilder,
+            channel_store: &mut ChannelStore,
+        ) {
+            self.0 = channel_builder.register_write_channel(
+                channel_store,
+                "test.counter".to_string(),
+                0,
+            );
+        }
+
+        fn dispatch(&mut self, channel_store: &ChannelStore, _context: &DispatchContext) {
+            channel_store.grab(&self.0).update(|value| *value += 1);
+        }
+    }
+
+    struct TestObserver(ChannelReaderToken<u32>, Rc<RefCell<Vec<u32>>>);
+    impl Component for TestObserver {
+        fn register_read_channels(
+            &mut self,
+            channel_builder: ChannelReadBuilder,
+            channel_store: &mut ChannelStore,
+        ) {
+            self.0 = channel_builder.bind_read_channel(channel_store, "test.imported".to_string());
+        }
+
+        fn dispatch(&mut self, channel_store: &ChannelStore, _context: &DispatchContext) {
+            self.1.borrow_mut().push(channel_store.grab(&self.0).get());
+        }
+    }
+
+    /// Dispatch the source runner 'source_ticks' times for every tick of the destination runner.
+    fn run_bridge(mode: BridgeMode, source_ticks: usize) -> (Vec<u32>, u64) {
+        let observed = Rc::new(RefCell::new(Vec::new()));
+        let (exporter, importer) = bridge("test.counter", "test.imported", mode, 0u32);
+        let monitor = exporter.monitor();
+
+        let mut source_runner = Runner::default();
+        source_runner.add_component(Box::new(exporter));
+        source_runner.add_component(Box::new(TestCounter(ChannelOwnerToken::default())));
+        source_runner.initialize();
+
+        let mut destination_runner = Runner::default();
+        destination_runner.add_component(Box::new(TestObserver(
+            ChannelReaderToken::default(),
+            observed.clone(),
+        )));
+        destination_runner.add_component(Box::new(importer));
+        destination_runner.initialize();
+
+        for _ in 0..4 {
+            for _ in 0..source_ticks {
+                source_runner.dispatch_components();
+            }
+            destination_runner.dispatch_components();
+        }
+        destination_runner.dispatch_components();
+
+        let observed = observed.borrow().clone();
+        (observed, monitor.dropped())
+    }
+
+    #[test]
+    fn test_bridge_latest() {
+        assert_eq!(
+            run_bridge(BridgeMode::Latest, 3),
+            (Vec::from([3, 6, 9, 12, 12]), 0)
+        );
+    }
+
+    #[test]
+    fn test_bridge_queued() {
+        assert_eq!(
+            run_bridge(BridgeMode::Queued { capacity: 16 }, 2),
+            (Vec::from([1, 2, 3, 4, 5]), 0)
+        );
+    }
+
+    #[test]
+    fn test_bridge_queued_overflow() {
+        assert_eq!(
+            run_bridge(BridgeMode::Queued { capacity: 2 }, 3),
+            (Vec::from([1, 2, 4, 7, 10]), 7)
+        );
+    }
+
+    #[test]
+    fn test_spsc_queue_drop() {
+        let value = Rc::new(0u8);
+        let queue = SpscQueue::new(4);
+        queue.push(value.clone()).unwrap();
+        queue.push(value.clone()).unwrap();
+        assert_eq!(queue.len(), 2);
+        drop(queue);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_bridge_threads() {
+        let (exporter, importer) = bridge(
+            "test.counter",
+            "test.imported",
+            BridgeMode::Queued { capacity: 1024 },
+            0u32,
+        );
+        let monitor = importer.monitor();
+
+        let source = std::thread::spawn(move || {
+            let mut source_runner = Runner::default();
+            source_runner.add_component(Box::new(exporter));
+            source_runner.add_component(Box::new(TestCounter(ChannelOwnerToken::default())));
+            source_runner.initialize();
+            for _ in 0..1000 {
+                source_runner.dispatch_components();
+            }
+        });
+
+        let mut destination_runner = Runner::default();
+        destination_runner.add_component(Box::new(importer));
+        destination_runner.initialize();
+        let imported = destination_runner
+            .channel_store()
+            .try_bind_observer::<u32>("test.imported")
+            .unwrap();
+
+        let mut last = 0;
+        while last < 1000 {
+            destination_runner.dispatch_components();
+            let value = destination_runner.channel_store().grab(&imported).get();
+            // Queued values are imported in order without gaps.
+            assert!(value == last || value == last + 1);
+            last = value;
+        }
+        source.join().unwrap();
+        assert_eq!(monitor.dropped(), 0);
+    }
+}
@@ -0,0 +1,124 @@
+use core::{cell::Cell, time::Duration};
+
+use alloc::rc::Rc;
+
+/// Time source used by runners to timestamp each dispatch. Timestamps are expressed as the
+/// monotonic duration elapsed since an epoch chosen by the clock implementation.
+pub trait Clock {
+    /// Current timestamp of the clock.
+    fn now(&self) -> Duration;
+}
+
+/// Monotonic clock backed by the standard library, the epoch is the creation of the clock.
+#[cfg(feature = "std")]
+pub struct StdClock {
+    epoch: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl Default for StdClock {
+    fn default() -> Self {
+        Self {
+            epoch: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for StdClock {
+    fn now(&self) -> Duration {
+        self.epoch.elapsed()
+    }
+}
+
+/// Manually stepped clock used for simulation and testing. Clones of a simulation clock share
+/// the same time, a clone may be kept to step the clock after handing it to a runner.
+#[derive(Clone, Default)]
+pub struct SimClock {
+    now: Rc<Cell<Duration>>,
+}
+
+impl SimClock {
+    /// Advance the clock by the given duration.
+    pub fn step(&self, dt: Duration) {
+        self.now.set(self.now.get() + dt);
+    }
+
+    /// Set the clock to the given timestamp. The timestamp must not be earlier than the current one.
+    pub fn set(&self, now: Duration) {
+        assert!(
+            now >= self.now.get(),
+            "Simulation clock cannot move backwards."
+        );
+        self.now.set(now);
+    }
+}
+
+impl Clock for SimClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+/// Clock reading its timestamp from a user supplied function, such as a hardware timer read.
+pub struct FnClock<F: Fn() -> Duration> {
+    read_fn: F,
+}
+
+impl<F: Fn() -> Duration> FnClock<F> {
+    pub fn new(read_fn: F) -> Self {
+        Self { read_fn }
+    }
+}
+
+impl<F: Fn() -> Duration> Clock for FnClock<F> {
+    fn now(&self) -> Duration {
+        (self.read_fn)()
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use core::{cell::Cell, time::Duration};
+
+    use super::{Clock, FnClock, SimClock};
+
+    #[test]
+    fn test_sim_clock() {
+        let clock = SimClock::default();
+        let clock_handle = clock.clone();
+        assert_eq!(clock.now(), Duration::ZERO);
+
+        clock_handle.step(Duration::from_millis(10));
+        assert_eq!(clock.now(), Duration::from_millis(10));
+        clock_handle.set(Duration::from_secs(1));
+        assert_eq!(clock.now(), Duration::from_secs(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Simulation clock cannot move backwards.")]
+    fn test_sim_clock_backwards() {
+        let clock = SimClock::default();
+        clock.step(Duration::from_millis(10));
+        clock.set(Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_fn_clock() {
+        let counter = Cell::new(0u64);
+        let clock = FnClock::new(|| {
+            counter.set(counter.get() + 1);
+            Duration::from_micros(counter.get())
+        });
+        assert_eq!(clock.now(), Duration::from_micros(1));
+        assert_eq!(clock.now(), Duration::from_micros(2));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_std_clock() {
+        let clock = super::StdClock::default();
+        let first = clock.now();
+        assert!(clock.now() >= first);
+    }
+}
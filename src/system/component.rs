@@ -1,10 +1,31 @@
-use alloc::boxed::Box;
+use core::{
+    any::{self, Any},
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+
+use alloc::{boxed::Box, string::String};
 
-use crate::channel::store::{
-    ChannelDanglingBuilder, ChannelReadBuilder, ChannelStore, ChannelWriteBuilder,
+#[cfg(feature = "std")]
+use crate::channel::store::SyncChannelStore;
+use crate::{
+    channel::store::{
+        ChannelDanglingBuilder, ChannelReadBuilder, ChannelStore, ChannelWriteBuilder,
+    },
+    system::{schedule::RateSchedule, timing::TimingStats},
 };
 
+/// Internal state of a component captured by Component::save_state().
+pub type ComponentState = Box<dyn Any + Send>;
+
 pub trait Component {
+    /// Name of the component used by runner introspection, graph export and diagnostics. The
+    /// name is obtained once when the component is added to a runner. Defaults to the type name
+    /// of the component.
+    fn name(&self) -> &str {
+        any::type_name::<Self>()
+    }
+
     /// Dangling channel registration for a given component is done within this method.
     /// This method is called first by the Runner API as dangling channel ownership may be
     /// "picked up" by other components.
@@ -36,11 +57,178 @@ pub trait Component {
     }
 
     /// Runtime code is called within this method for execution per runner dispatch.
-    fn dispatch(&mut self, channel_store: &ChannelStore);
+    fn dispatch(&mut self, channel_store: &ChannelStore, context: &DispatchContext);
+
+    /// Capture the internal state of the component which is not held within channels, called
+    /// by Runner::snapshot(). Components without such state return None.
+    fn save_state(&self) -> Option<ComponentState> {
+        None
+    }
+
+    /// Restore internal state captured by save_state(), called by Runner::restore(). The state
+    /// was produced by the component with the same id within an identically built runner.
+    fn restore_state(&mut self, _state: &(dyn Any + Send)) {}
+}
+
+/// Timing information provided to a component on each dispatch.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct DispatchContext {
+    tick: u64,
+    timestamp: Duration,
+    dt: Duration,
+}
+
+impl DispatchContext {
+    pub fn new(tick: u64, timestamp: Duration, dt: Duration) -> Self {
+        Self {
+            tick,
+            timestamp,
+            dt,
+        }
+    }
+
+    /// Index of the runner tick (dispatch_components() call) during which the component is dispatched.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Runner clock timestamp sampled at the start of the current tick.
+    pub fn timestamp(&self) -> Duration {
+        self.timestamp
+    }
+
+    /// Time elapsed since the previous dispatch of this component, zero on the first dispatch.
+    pub fn dt(&self) -> Duration {
+        self.dt
+    }
+}
+
+/// Read only description of a component held by a runner.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ComponentInfo<'a> {
+    /// Id of the component within the runner, assigned in insertion order.
+    pub id: usize,
+    pub name: &'a str,
+    pub schedule: RateSchedule,
+}
+
+/// Component which may be dispatched on a worker thread by Runner::dispatch_components_parallel().
+/// Channels are accessed through a SyncChannelStore, as such a parallel component can only grab
+/// channels holding Send and Sync values.
+///
+/// The runner dispatches parallel components through dispatch_parallel() only, both with
+/// dispatch_components() and dispatch_components_parallel(). Component::dispatch() is never called
+/// by the runner and may forward to dispatch_parallel() through SyncChannelStore::new().
+#[cfg(feature = "std")]
+pub trait ParallelComponent: Component + Send {
+    /// Runtime code is called within this method for execution per runner dispatch.
+    fn dispatch_parallel(&mut self, channel_store: &SyncChannelStore, context: &DispatchContext);
+}
+
+/// Component owned by a runner.
+pub(super) enum ComponentBox {
+    Local(Box<dyn Component>),
+    #[cfg(feature = "std")]
+    Parallel(Box<dyn ParallelComponent>),
+}
+
+impl ComponentBox {
+    /// Dispatch the component, parallel components are dispatched through a SyncChannelStore.
+    pub fn dispatch(&mut self, channel_store: &ChannelStore, context: &DispatchContext) {
+        match self {
+            ComponentBox::Local(component) => component.dispatch(channel_store, context),
+            #[cfg(feature = "std")]
+            ComponentBox::Parallel(component) => {
+                component.dispatch_parallel(&SyncChannelStore::new(channel_store), context)
+            }
+        }
+    }
+
+    /// Returns true if the component may be dispatched on worker threads.
+    #[cfg(feature = "std")]
+    pub fn is_parallel(&self) -> bool {
+        matches!(self, ComponentBox::Parallel(_))
+    }
+
+    /// Obtain the component if it may be dispatched on worker threads.
+    #[cfg(feature = "std")]
+    pub fn as_parallel_mut(&mut self) -> Option<&mut dyn ParallelComponent> {
+        match self {
+            ComponentBox::Local(_) => None,
+            ComponentBox::Parallel(component) => Some(component.as_mut()),
+        }
+    }
+}
+
+impl Deref for ComponentBox {
+    type Target = dyn Component;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            ComponentBox::Local(component) => component.as_ref(),
+            #[cfg(feature = "std")]
+            ComponentBox::Parallel(component) => component.as_ref(),
+        }
+    }
+}
+
+impl DerefMut for ComponentBox {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            ComponentBox::Local(component) => component.as_mut(),
+            #[cfg(feature = "std")]
+            ComponentBox::Parallel(component) => component.as_mut(),
+        }
+    }
 }
 
 pub(super) struct ComponentHolder {
-    pub component: Box<dyn Component>,
+    pub component: ComponentBox,
     /// The "id" field is used to track owners and consumers of channels for layout generation of execution topology.
     pub id: usize,
+    /// Name of the component obtained from Component::name() when the component was added.
+    pub name: String,
+    /// Rate at which the component is dispatched by the runner.
+    pub schedule: RateSchedule,
+    /// Timestamp of the previous dispatch of the component.
+    pub last_dispatch: Option<Duration>,
+    /// Measured dispatch execution time, only recorded when runner timing is enabled.
+    pub timing: TimingStats,
+    /// Optional dispatch execution time budget of the component.
+    pub budget: Option<Duration>,
+    /// Dependency level of the component, components of a level only depend on lower levels.
+    pub level: usize,
+}
+
+impl ComponentHolder {
+    /// Hold a component dispatched on every tick, without budget and not yet ordered.
+    ///
+    /// ### Arguments
+    /// * 'id' - Id of the component within the runner.
+    /// * 'component' - Component to be held.
+    ///
+    pub fn new(id: usize, component: ComponentBox) -> Self {
+        let name = String::from(component.name());
+        Self {
+            component,
+            id,
+            name,
+            schedule: RateSchedule::default(),
+            last_dispatch: None,
+            timing: TimingStats::default(),
+            budget: None,
+            level: 0,
+        }
+    }
+
+    /// Build the dispatch context of the component for the given tick, recording the timestamp
+    /// as the last dispatch of the component.
+    pub fn next_dispatch_context(&mut self, tick: u64, timestamp: Duration) -> DispatchContext {
+        let dt = self.last_dispatch.map_or(Duration::ZERO, |last_dispatch| {
+            timestamp.saturating_sub(last_dispatch)
+        });
+        self.last_dispatch = Some(timestamp);
+
+        DispatchContext::new(tick, timestamp, dt)
+    }
 }
@@ -0,0 +1,391 @@
+use core::time::Duration;
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::channel::{
+    error::ChannelError,
+    reg::AnyClone,
+    store::{ChannelStore, RegViewProducer},
+};
+
+use super::telemetry::{TelemetryError, TelemetrySink};
+
+/// Numeric channel value type which may be exported as CSV columns. Booleans are written as 0
+/// and 1, fixed-size arrays are flattened into one column per element.
+pub trait CsvValue: AnyClone {
+    /// Append the column headers of a value, named after the channel.
+    ///
+    /// ### Arguments
+    /// * 'name' - Name of the channel or array element.
+    /// * 'headers' - Headers of the row.
+    ///
+    fn headers(name: &str, headers: &mut Vec<String>) {
+        headers.push(String::from(name));
+    }
+
+    /// Append the fields of the value to the row, one field per header.
+    fn write_fields(&self, row: &mut Vec<String>);
+}
+
+macro_rules! impl_csv_value {
+    ($($num:ty),*) => {
+        $(
+            impl CsvValue for $num {
+                fn write_fields(&self, row: &mut Vec<String>) {
+                    row.push(self.to_string());
+                }
+            }
+        )*
+    };
+}
+
+impl_csv_value!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+impl CsvValue for bool {
+    fn write_fields(&self, row: &mut Vec<String>) {
+        row.push(String::from(if *self { "1" } else { "0" }));
+    }
+}
+
+impl<T: CsvValue, const N: usize> CsvValue for [T; N] {
+    fn headers(name: &str, headers: &mut Vec<String>) {
+        for idx in 0..N {
+            T::headers(&format!("{}[{}]", name, idx), headers);
+        }
+    }
+
+    fn write_fields(&self, row: &mut Vec<String>) {
+        for value in self.iter() {
+            value.write_fields(row);
+        }
+    }
+}
+
+/// Reads the fields of a bound channel from the channel store.
+type ColumnReader = Box<dyn Fn(&ChannelStore, &mut Vec<String>)>;
+
+/// Channel selected for export.
+struct CsvChannel {
+    name: String,
+    bind: fn(&ChannelStore, &str) -> Result<ColumnReader, ChannelError>,
+    headers: fn(&str, &mut Vec<String>),
+    reader: Option<ColumnReader>,
+}
+
+/// Bind an observer of a channel holding values of type T.
+fn bind_column<T: CsvValue>(
+    channel_store: &ChannelStore,
+    name: &str,
+) -> Result<ColumnReader, ChannelError> {
+    let token = channel_store.try_bind_observer::<T>(name)?;
+    Ok(Box::new(move |channel_store, row| {
+        channel_store.grab(&token).borrow().write_fields(row)
+    }))
+}
+
+/// Quote a CSV field if it holds a separator, quote or line break.
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        String::from(field)
+    }
+}
+
+/// Telemetry sink writing selected numeric channels into a CSV file, one row per sampled tick and
+/// one column per channel value. The first two columns hold the tick and the runner clock
+/// timestamp of the tick in seconds. Headers are named after the channels, array elements are
+/// suffixed with their index such as "nav.position[2]".
+///
+/// Rows are decimated, only every 'every_ticks' tick is written starting with the first tick.
+pub struct CsvTelemetrySink<W: Write = BufWriter<File>> {
+    writer: W,
+    channels: Vec<CsvChannel>,
+    every_ticks: u64,
+    /// Fields of the row being written, reused between rows.
+    row: Vec<String>,
+    /// First write error, no further rows are written once set.
+    error: Option<TelemetryError>,
+}
+
+impl CsvTelemetrySink {
+    /// Create or truncate the CSV file at the given path.
+    ///
+    /// ### Argument
+    /// * 'path' - Path of the CSV file.
+    ///
+    pub fn create(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> CsvTelemetrySink<W> {
+    /// Create a sink writing the CSV file into the given writer.
+    ///
+    /// ### Argument
+    /// * 'writer' - Destination of the CSV file.
+    ///
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            channels: Vec::new(),
+            every_ticks: 1,
+            row: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Select a channel holding values of type T, columns are ordered by selection.
+    ///
+    /// ### Argument
+    /// * 'name' - Name of the channel.
+    ///
+    pub fn add_channel<T: CsvValue>(mut self, name: &str) -> Self {
+        self.channels.push(CsvChannel {
+            name: String::from(name),
+            bind: bind_column::<T>,
+            headers: T::headers,
+            reader: None,
+        });
+        self
+    }
+
+    /// Only write a row every 'every_ticks' ticks.
+    ///
+    /// ### Argument
+    /// * 'every_ticks' - Decimation factor, must be non-zero.
+    ///
+    pub fn with_decimation(mut self, every_ticks: u64) -> Self {
+        assert!(every_ticks > 0, "Decimation factor must be non-zero.");
+        self.every_ticks = every_ticks;
+        self
+    }
+
+    /// Write the fields of the pending row as a line.
+    fn write_row(&mut self) -> Result<(), TelemetryError> {
+        let mut line = String::new();
+        for (idx, field) in self.row.iter().enumerate() {
+            if idx > 0 {
+                line.push(',');
+            }
+            line.push_str(field);
+        }
+        line.push('\n');
+        self.row.clear();
+
+        self.writer
+            .write_all(line.as_bytes())
+            .map_err(|err| TelemetryError::Write {
+                message: err.to_string(),
+            })
+    }
+}
+
+impl<W: Write> TelemetrySink for CsvTelemetrySink<W> {
+    fn attach(&mut self, channel_store: &ChannelStore) -> Result<(), TelemetryError> {
+        let mut headers = Vec::new();
+        for channel in self.channels.iter_mut() {
+            channel.reader = Some((channel.bind)(channel_store, &channel.name).map_err(|err| {
+                TelemetryError::Channel {
+                    name: channel.name.clone(),
+                    reason: err.to_string(),
+                }
+            })?);
+            (channel.headers)(&channel.name, &mut headers);
+        }
+
+        self.row.push(String::from("tick"));
+        self.row.push(String::from("timestamp"));
+        self.row
+            .extend(headers.iter().map(|header| escape_field(header)));
+        self.write_row()
+    }
+
+    fn record_tick(&mut self, tick: u64, timestamp: Duration, channel_store: &ChannelStore) {
+        if self.error.is_some() || !tick.is_multiple_of(self.every_ticks) {
+            return;
+        }
+
+        self.row.push(tick.to_string());
+        self.row.push(timestamp.as_secs_f64().to_string());
+        for channel in self.channels.iter() {
+            let reader = channel
+                .reader
+                .as_ref()
+                .expect("CSV sink must be attached before recording.");
+            reader(channel_store, &mut self.row);
+        }
+
+        if let Err(err) = self.write_row() {
+            self.error = Some(err);
+        }
+    }
+
+    fn finish(&mut self) -> Result<(), TelemetryError> {
+        let flushed = self.writer.flush().map_err(|err| TelemetryError::Write {
+            message: err.to_string(),
+        });
+        match self.error.as_ref() {
+            Some(err) => Err(err.clone()),
+            None => flushed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use core::time::Duration;
+
+    use alloc::{boxed::Box, string::ToString, vec::Vec};
+
+    use crate::{
+        channel::{
+            store::{ChannelStore, ChannelWriteBuilder, RegViewProducer},
+            token::ChannelOwnerToken,
+        },
+        system::{
+            clock::SimClock,
+            component::{Component, DispatchContext},
+            runner::Runner,
+            telemetry::{TelemetryError, TelemetrySink},
+        },
+    };
+
+    use super::{CsvTelemetrySink, CsvValue};
+
+    #[test]
+    fn test_csv_value_columns() {
+        let mut headers = Vec::new();
+        <[[f32; 2]; 2]>::headers("test.matrix", &mut headers);
+        assert_eq!(
+            headers,
+            [
+                "test.matrix[0][0]",
+                "test.matrix[0][1]",
+                "test.matrix[1][0]",
+                "test.matrix[1][1]"
+            ]
+        );
+
+        let mut row = Vec::new();
+        [[1.5f32, -2.0], [0.25, 3.0]].write_fields(&mut row);
+        true.write_fields(&mut row);
+        (-7i16).write_fields(&mut row);
+        assert_eq!(row, ["1.5", "-2", "0.25", "3", "1", "-7"]);
+    }
+
+    #[test]
+    fn test_csv_sink() {
+        let mut channel_store = ChannelStore::default();
+        let builder = ChannelWriteBuilder::new(0);
+        let speed =
+            builder.register_write_channel(&mut channel_store, "nav.speed".to_string(), 0.0f64);
+        builder.register_write_channel(&mut channel_store, "nav.valid,raw".to_string(), true);
+        builder.register_write_channel(
+            &mut channel_store,
+            "nav.position".to_string(),
+            [1i32, 2, 3],
+        );
+
+        let mut output = Vec::new();
+        let mut sink = CsvTelemetrySink::new(&mut output)
+            .add_channel::<f64>("nav.speed")
+            .add_channel::<bool>("nav.valid,raw")
+            .add_channel::<[i32; 3]>("nav.position")
+            .with_decimation(2);
+        sink.attach(&channel_store).unwrap();
+        for tick in 0..5 {
+            channel_store.grab(&speed).set(tick as f64 * 0.5);
+            sink.record_tick(tick, Duration::from_millis(tick * 10), &channel_store);
+        }
+        assert_eq!(sink.finish(), Ok(()));
+        drop(sink);
+
+        assert_eq!(
+            core::str::from_utf8(&output).unwrap(),
+            "tick,timestamp,nav.speed,\"nav.valid,raw\",nav.position[0],nav.position[1],nav.position[2]\n\
+             0,0,0,1,1,2,3\n\
+             2,0.02,1,1,1,2,3\n\
+             4,0.04,2,1,1,2,3\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_sink_type_mismatch() {
+        let mut channel_store = ChannelStore::default();
+        ChannelWriteBuilder::new(0).register_write_channel(
+            &mut channel_store,
+            "nav.speed".to_string(),
+            0.0f64,
+        );
+
+        let mut sink = CsvTelemetrySink::new(Vec::new()).add_channel::<f32>("nav.speed");
+        assert!(matches!(
+            sink.attach(&channel_store),
+            Err(TelemetryError::Channel { name, .. }) if name == "nav.speed"
+        ));
+    }
+
+    struct TestWriter(ChannelOwnerToken<u32>);
+    impl Component for TestWriter {
+        fn register_write_channels(
+            &mut self,
+            channel_builder: ChannelWriteBuilder,
+            channel_store: &mut ChannelStore,
+        ) {
+            self.0 = channel_builder.register_write_channel(
+                channel_store,
+                "test.counter".to_string(),
+                0,
+            );
+        }
+
+        fn dispatch(&mut self, channel_store: &ChannelStore, _context: &DispatchContext) {
+            channel_store.grab(&self.0).update(|value| *value += 1);
+        }
+    }
+
+    #[test]
+    fn test_csv_file() {
+        let path = std::env::temp_dir().join("comet_test_csv_file.csv");
+        let clock = SimClock::default();
+        let mut runner = Runner::default();
+        runner.set_clock(Box::new(clock.clone()));
+        runner.add_component(Box::new(TestWriter(ChannelOwnerToken::default())));
+        runner.initialize();
+        runner
+            .add_telemetry_sink(Box::new(
+                CsvTelemetrySink::create(&path)
+                    .unwrap()
+                    .add_channel::<u32>("test.counter")
+                    .with_decimation(3),
+            ))
+            .unwrap();
+
+        for _ in 0..7 {
+            clock.step(Duration::from_millis(500));
+            runner.dispatch_components();
+        }
+        assert_eq!(runner.finish_telemetry(), Ok(()));
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            contents,
+            "tick,timestamp,test.counter\n0,0.5,1\n3,2,4\n6,3.5,7\n"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use alloc::{collections::VecDeque, vec::Vec};
+
+/// Number of events an event log holds unless configured otherwise.
+pub const DEFAULT_EVENT_LOG_CAPACITY: usize = 1024;
+
+/// Bounded log of events recorded by a runner, such as overruns or write policy violations.
+///
+/// The log holds at most 'capacity' events. Once full, the oldest event is discarded for every
+/// new event and counted as dropped, so that a runner which is never drained keeps a constant
+/// memory footprint.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct EventLog<T> {
+    events: VecDeque<T>,
+    capacity: usize,
+    dropped: u64,
+}
+
+impl<T> Default for EventLog<T> {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_EVENT_LOG_CAPACITY)
+    }
+}
+
+impl<T> EventLog<T> {
+    /// Create an empty log holding at most 'capacity' events.
+    ///
+    /// ### Argument
+    /// * 'capacity' - Maximum number of events retained by the log.
+    ///
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            events: VecDeque::new(),
+            capacity,
+            dropped: 0,
+        }
+    }
+
+    /// Maximum number of events retained by the log.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the maximum number of events retained by the log. The oldest events are discarded
+    /// and counted as dropped if the log holds more events than the new capacity.
+    ///
+    /// ### Argument
+    /// * 'capacity' - Maximum number of events retained by the log.
+    ///
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.events.len() > capacity {
+            self.events.pop_front();
+            self.dropped += 1;
+        }
+    }
+
+    /// Number of events held by the log.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns true if the log holds no events.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Number of events discarded because the log was full since it was last drained.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Iterate over the held events from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.events.iter()
+    }
+
+    /// Append an event, discarding the oldest event if the log is full.
+    pub(crate) fn push(&mut self, event: T) {
+        if self.capacity == 0 {
+            self.dropped += 1;
+            return;
+        }
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+            self.dropped += 1;
+        }
+        self.events.push_back(event);
+    }
+
+    /// Remove and return every held event from oldest to newest, resetting the dropped count.
+    pub fn take(&mut self) -> Vec<T> {
+        self.dropped = 0;
+        self.events.drain(..).collect()
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use alloc::vec::Vec;
+
+    use super::EventLog;
+
+    #[test]
+    fn test_event_log_bounded() {
+        let mut log = EventLog::with_capacity(3);
+        for event in 0..5 {
+            log.push(event);
+        }
+        assert_eq!(log.len(), 3);
+        assert_eq!(log.dropped(), 2);
+        assert_eq!(log.iter().copied().collect::<Vec<u32>>(), [2, 3, 4]);
+
+        log.set_capacity(1);
+        assert_eq!(log.dropped(), 4);
+        assert_eq!(log.take(), [4]);
+        assert!(log.is_empty());
+        assert_eq!(log.dropped(), 0);
+
+        log.set_capacity(0);
+        log.push(5);
+        assert!(log.is_empty());
+        assert_eq!(log.dropped(), 1);
+    }
+}
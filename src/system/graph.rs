@@ -0,0 +1,326 @@
+use core::fmt::Write;
+
+use alloc::{string::String, vec::Vec};
+
+/// Output format of an exported execution graph.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GraphFormat {
+    /// Graphviz DOT digraph.
+    Dot,
+    /// Mermaid flowchart.
+    Mermaid,
+    /// JSON document holding the components, channel edges and execution order.
+    Json,
+}
+
+/// Kind of channel connection between two components.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GraphEdgeKind {
+    /// Channel registered by its owner and bound by a reader.
+    Current,
+    /// Channel registered as a dangling channel by a reader. Unowned dangling channels have no producer.
+    Dangling,
+    /// Channel bound with bind_read_behind_channel() or bind_read_history_channel(), the reader
+    /// observes past values.
+    Behind,
+}
+
+impl GraphEdgeKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            GraphEdgeKind::Current => "current",
+            GraphEdgeKind::Dangling => "dangling",
+            GraphEdgeKind::Behind => "behind",
+        }
+    }
+}
+
+/// Channel connection from a producing component to a consuming component.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GraphEdge {
+    /// Id of the component owning the channel, None for unowned dangling channels.
+    pub producer: Option<usize>,
+    /// Id of the component reading the channel.
+    pub consumer: usize,
+    /// Name of the channel.
+    pub channel: String,
+    pub kind: GraphEdgeKind,
+}
+
+/// Component within an execution graph.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GraphNode {
+    /// Id of the component within the runner.
+    pub id: usize,
+    pub name: String,
+}
+
+/// Snapshot of the component wiring of an initialized runner.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ExecutionGraph {
+    /// Components in execution order.
+    pub components: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl ExecutionGraph {
+    /// Render the graph in the given format.
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Mermaid => self.to_mermaid(),
+            GraphFormat::Json => self.to_json(),
+        }
+    }
+
+    /// Number of unowned dangling channel source nodes, one per edge without a producer.
+    fn unowned_source_count(&self) -> usize {
+        self.edges
+            .iter()
+            .filter(|edge| edge.producer.is_none())
+            .count()
+    }
+
+    fn to_dot(&self) -> String {
+        let mut out = String::new();
+        out.push_str("digraph comet {\n    rankdir=LR;\n");
+        for (order, node) in self.components.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    c{} [label=\"{}\\norder: {}\"];",
+                node.id,
+                escape_quoted(&node.name),
+                order
+            );
+        }
+        for unowned_idx in 0..self.unowned_source_count() {
+            let _ = writeln!(
+                out,
+                "    u{} [label=\"unowned\", shape=point];",
+                unowned_idx
+            );
+        }
+
+        let mut unowned_idx = 0;
+        for edge in self.edges.iter() {
+            let label = escape_quoted(&edge.channel);
+            let _ = match (edge.producer, edge.kind) {
+                (None, _) => {
+                    unowned_idx += 1;
+                    writeln!(
+                        out,
+                        "    u{} -> c{} [label=\"{}\", style=dashed];",
+                        unowned_idx - 1,
+                        edge.consumer,
+                        label
+                    )
+                }
+                (Some(producer), GraphEdgeKind::Current) => writeln!(
+                    out,
+                    "    c{} -> c{} [label=\"{}\"];",
+                    producer, edge.consumer, label
+                ),
+                (Some(producer), GraphEdgeKind::Dangling) => writeln!(
+                    out,
+                    "    c{} -> c{} [label=\"{}\", style=dashed];",
+                    producer, edge.consumer, label
+                ),
+                (Some(producer), GraphEdgeKind::Behind) => writeln!(
+                    out,
+                    "    c{} -> c{} [label=\"{}\", style=dashed, constraint=false];",
+                    producer, edge.consumer, label
+                ),
+            };
+        }
+        out.push_str("}\n");
+
+        out
+    }
+
+    fn to_mermaid(&self) -> String {
+        let mut out = String::new();
+        out.push_str("flowchart LR\n");
+        for (order, node) in self.components.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    c{}[\"{}<br/>order: {}\"]",
+                node.id,
+                escape_mermaid(&node.name),
+                order
+            );
+        }
+        for unowned_idx in 0..self.unowned_source_count() {
+            let _ = writeln!(out, "    u{}((unowned))", unowned_idx);
+        }
+
+        let mut unowned_idx = 0;
+        for edge in self.edges.iter() {
+            let source = match edge.producer {
+                Some(producer) => alloc::format!("c{}", producer),
+                None => {
+                    unowned_idx += 1;
+                    alloc::format!("u{}", unowned_idx - 1)
+                }
+            };
+            let arrow = match edge.kind {
+                GraphEdgeKind::Current => "-->",
+                GraphEdgeKind::Dangling | GraphEdgeKind::Behind => "-.->",
+            };
+            let _ = writeln!(
+                out,
+                "    {} {}|\"{}\"| c{}",
+                source,
+                arrow,
+                escape_mermaid(&edge.channel),
+                edge.consumer
+            );
+        }
+
+        out
+    }
+
+    fn to_json(&self) -> String {
+        let mut out = String::new();
+        out.push_str("{\"components\":[");
+        for (order, node) in self.components.iter().enumerate() {
+            if order > 0 {
+                out.push(',');
+            }
+            let _ = write!(
+                out,
+                "{{\"id\":{},\"name\":\"{}\",\"order\":{}}}",
+                node.id,
+                escape_quoted(&node.name),
+                order
+            );
+        }
+        out.push_str("],\"channels\":[");
+        for (edge_idx, edge) in self.edges.iter().enumerate() {
+            if edge_idx > 0 {
+                out.push(',');
+            }
+            let _ = write!(
+                out,
+                "{{\"name\":\"{}\",\"producer\":",
+                escape_quoted(&edge.channel)
+            );
+            let _ = match edge.producer {
+                Some(producer) => write!(out, "{}", producer),
+                None => write!(out, "null"),
+            };
+            let _ = write!(
+                out,
+                ",\"consumer\":{},\"kind\":\"{}\"}}",
+                edge.consumer,
+                edge.kind.as_str()
+            );
+        }
+        out.push_str("]}");
+
+        out
+    }
+}
+
+/// Escape a string for use within a double quoted DOT or JSON string.
+fn escape_quoted(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for chr in value.chars() {
+        match chr {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            chr if (chr as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", chr as u32);
+            }
+            chr => escaped.push(chr),
+        }
+    }
+
+    escaped
+}
+
+/// Escape a string for use within a quoted Mermaid label.
+fn escape_mermaid(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use alloc::{string::String, vec::Vec};
+
+    use super::{ExecutionGraph, GraphEdge, GraphEdgeKind, GraphFormat, GraphNode};
+
+    fn test_graph() -> ExecutionGraph {
+        ExecutionGraph {
+            components: Vec::from([
+                GraphNode {
+                    id: 1,
+                    name: String::from("producer"),
+                },
+                GraphNode {
+                    id: 0,
+                    name: String::from("consumer"),
+                },
+            ]),
+            edges: Vec::from([
+                GraphEdge {
+                    producer: Some(1),
+                    consumer: 0,
+                    channel: String::from("test.a"),
+                    kind: GraphEdgeKind::Current,
+                },
+                GraphEdge {
+                    producer: Some(0),
+                    consumer: 1,
+                    channel: String::from("test.b"),
+                    kind: GraphEdgeKind::Behind,
+                },
+                GraphEdge {
+                    producer: None,
+                    consumer: 0,
+                    channel: String::from("test.\"c\""),
+                    kind: GraphEdgeKind::Dangling,
+                },
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_graph_dot() {
+        assert_eq!(
+            test_graph().render(GraphFormat::Dot),
+            "digraph comet {\n    rankdir=LR;\n    \
+             c1 [label=\"producer\\norder: 0\"];\n    \
+             c0 [label=\"consumer\\norder: 1\"];\n    \
+             u0 [label=\"unowned\", shape=point];\n    \
+             c1 -> c0 [label=\"test.a\"];\n    \
+             c0 -> c1 [label=\"test.b\", style=dashed, constraint=false];\n    \
+             u0 -> c0 [label=\"test.\\\"c\\\"\", style=dashed];\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_graph_mermaid() {
+        assert_eq!(
+            test_graph().render(GraphFormat::Mermaid),
+            "flowchart LR\n    \
+             c1[\"producer<br/>order: 0\"]\n    \
+             c0[\"consumer<br/>order: 1\"]\n    \
+             u0((unowned))\n    \
+             c1 -->|\"test.a\"| c0\n    \
+             c0 -.->|\"test.b\"| c1\n    \
+             u0 -.->|\"test.#quot;c#quot;\"| c0\n"
+        );
+    }
+
+    #[test]
+    fn test_graph_json() {
+        assert_eq!(
+            test_graph().render(GraphFormat::Json),
+            "{\"components\":[{\"id\":1,\"name\":\"producer\",\"order\":0},{\"id\":0,\"name\":\"consumer\",\"order\":1}],\"channels\":[\
+             {\"name\":\"test.a\",\"producer\":1,\"consumer\":0,\"kind\":\"current\"},\
+             {\"name\":\"test.b\",\"producer\":0,\"consumer\":1,\"kind\":\"behind\"},\
+             {\"name\":\"test.\\\"c\\\"\",\"producer\":null,\"consumer\":0,\"kind\":\"dangling\"}]}"
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use core::fmt;
+
+use alloc::{string::String, vec::Vec};
+
+use crate::channel::store::ChannelStore;
+
+/// Wiring check run by the runner during initialization, or channel check run by the runner
+/// after each tick.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LintRule {
+    /// A channel is registered but no component reads it.
+    UnreadChannel,
+    /// A dangling channel was not picked up by any owner and will only ever hold its default value.
+    UnownedDanglingChannel,
+    /// A reader was dispatched while a channel value was older than its staleness limit. Checked
+    /// after each tick, a deny level aborts the runner once the tick is complete.
+    StaleChannel,
+    /// A behind channel reader was dispatched while the channel owner was dispatched but never
+    /// wrote the channel, the reader only ever observed the initial value. Checked after each
+    /// tick and reported once per behind channel, a deny level aborts the runner once the tick
+    /// is complete.
+    UnwrittenBehindChannel,
+}
+
+impl LintRule {
+    /// Every lint rule known to the runner.
+    pub const ALL: [LintRule; 4] = [
+        LintRule::UnreadChannel,
+        LintRule::UnownedDanglingChannel,
+        LintRule::StaleChannel,
+        LintRule::UnwrittenBehindChannel,
+    ];
+
+    /// Identifier of the rule used within diagnostic messages.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LintRule::UnreadChannel => "unread_channel",
+            LintRule::UnownedDanglingChannel => "unowned_dangling_channel",
+            LintRule::StaleChannel => "stale_channel",
+            LintRule::UnwrittenBehindChannel => "unwritten_behind_channel",
+        }
+    }
+
+    /// Severity of the rule unless configured otherwise, every rule defaults to warn. Terminal
+    /// outputs consumed outside of the runner may be exempted by allowing
+    /// LintRule::UnreadChannel.
+    pub fn default_level(&self) -> LintLevel {
+        LintLevel::Warn
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            LintRule::UnreadChannel => 0,
+            LintRule::UnownedDanglingChannel => 1,
+            LintRule::StaleChannel => 2,
+            LintRule::UnwrittenBehindChannel => 3,
+        }
+    }
+}
+
+/// Severity assigned to a lint rule.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum LintLevel {
+    /// The rule is not checked.
+    Allow,
+    /// Findings are reported as warnings and do not prevent initialization.
+    Warn,
+    /// Findings are reported as errors and prevent initialization.
+    Deny,
+}
+
+/// Severity of every lint rule.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LintConfig {
+    levels: [LintLevel; LintRule::ALL.len()],
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            levels: LintRule::ALL.map(|rule| rule.default_level()),
+        }
+    }
+}
+
+impl LintConfig {
+    pub fn level(&self, rule: LintRule) -> LintLevel {
+        self.levels[rule.index()]
+    }
+
+    pub fn set_level(&mut self, rule: LintRule, level: LintLevel) {
+        self.levels[rule.index()] = level;
+    }
+
+    /// Check the channel wiring of a channel store against every rule which is not allowed.
+    pub(crate) fn check(&self, channel_store: &ChannelStore) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for channel in channel_store.channels() {
+            let (rule, component) = match (channel.owner, channel.readers.first()) {
+                (Some(owner), None) => (LintRule::UnreadChannel, owner),
+                (None, Some(reader)) => (LintRule::UnownedDanglingChannel, reader.id),
+                _ => continue,
+            };
+
+            let level = self.level(rule);
+            if level != LintLevel::Allow {
+                diagnostics.push(Diagnostic {
+                    rule,
+                    level,
+                    channel: String::from(channel.name),
+                    component,
+                });
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// Finding of a lint rule.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Diagnostic {
+    pub rule: LintRule,
+    pub level: LintLevel,
+    /// Name of the channel the finding refers to.
+    pub channel: String,
+    /// Id of the component which registered the channel, or the reader for stale channels.
+    pub component: usize,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.rule {
+            LintRule::UnreadChannel => write!(
+                f,
+                "Channel [{}] written by component [{}] is never read",
+                self.channel, self.component
+            )?,
+            LintRule::UnownedDanglingChannel => write!(
+                f,
+                "Dangling channel [{}] registered by component [{}] has no owner",
+                self.channel, self.component
+            )?,
+            LintRule::StaleChannel => write!(
+                f,
+                "Channel [{}] read by component [{}] exceeded its staleness limit",
+                self.channel, self.component
+            )?,
+            LintRule::UnwrittenBehindChannel => write!(
+                f,
+                "Behind channel [{}] read by component [{}] was never written by its owner",
+                self.channel, self.component
+            )?,
+        }
+        write!(f, " ({}).", self.rule.as_str())
+    }
+}
+
+/// Receiver of the diagnostics produced by the initialization lint pass.
+pub trait DiagnosticSink {
+    fn report(&mut self, diagnostic: &Diagnostic);
+}
+
+impl<F: FnMut(&Diagnostic)> DiagnosticSink for F {
+    fn report(&mut self, diagnostic: &Diagnostic) {
+        self(diagnostic)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use alloc::{string::ToString, vec::Vec};
+
+    use crate::channel::store::{
+        ChannelDanglingBuilder, ChannelReadBuilder, ChannelStore, ChannelWriteBuilder,
+    };
+
+    use super::{Diagnostic, LintConfig, LintLevel, LintRule};
+
+    #[test]
+    fn test_lint_check() {
+        let mut channel_store = ChannelStore::default();
+        ChannelDanglingBuilder::new(2).register_dangling_channel(
+            &mut channel_store,
+            "test.dangling".to_string(),
+            0u8,
+        );
+        ChannelWriteBuilder::new(1).register_write_channel(
+            &mut channel_store,
+            "test.read".to_string(),
+            0u8,
+        );
+        ChannelWriteBuilder::new(1).register_write_channel(
+            &mut channel_store,
+            "test.unread".to_string(),
+            0u8,
+        );
+        ChannelReadBuilder::new(2)
+            .bind_read_channel::<u8>(&mut channel_store, "test.read".to_string());
+
+        let mut config = LintConfig::default();
+        assert_eq!(config.level(LintRule::UnreadChannel), LintLevel::Warn);
+        assert_eq!(config.check(&channel_store).len(), 2);
+
+        config.set_level(LintRule::UnownedDanglingChannel, LintLevel::Deny);
+        assert_eq!(
+            config.check(&channel_store),
+            Vec::from([
+                Diagnostic {
+                    rule: LintRule::UnownedDanglingChannel,
+                    level: LintLevel::Deny,
+                    channel: "test.dangling".to_string(),
+                    component: 2,
+                },
+                Diagnostic {
+                    rule: LintRule::UnreadChannel,
+                    level: LintLevel::Warn,
+                    channel: "test.unread".to_string(),
+                    component: 1,
+                },
+            ])
+        );
+
+        config.set_level(LintRule::UnreadChannel, LintLevel::Allow);
+        config.set_level(LintRule::UnownedDanglingChannel, LintLevel::Allow);
+        assert!(config.check(&channel_store).is_empty());
+    }
+
+    #[test]
+    fn test_diagnostic_display() {
+        let diagnostic = Diagnostic {
+            rule: LintRule::UnreadChannel,
+            level: LintLevel::Warn,
+            channel: "test.unread".to_string(),
+            component: 1,
+        };
+        assert_eq!(
+            diagnostic.to_string(),
+            "Channel [test.unread] written by component [1] is never read (unread_channel)."
+        );
+    }
+}
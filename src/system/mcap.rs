@@ -0,0 +1,532 @@
+use core::time::Duration;
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::channel::store::ChannelStore;
+
+use super::telemetry::{ChannelFilter, TelemetryError, TelemetrySink};
+
+/// Leading and trailing bytes of every MCAP file.
+const MCAP_MAGIC: &[u8] = b"\x89MCAP0\r\n";
+
+const OP_HEADER: u8 = 0x01;
+const OP_FOOTER: u8 = 0x02;
+const OP_SCHEMA: u8 = 0x03;
+const OP_CHANNEL: u8 = 0x04;
+const OP_MESSAGE: u8 = 0x05;
+const OP_DATA_END: u8 = 0x0F;
+
+/// Writer of the records of an unchunked MCAP file without a summary section.
+struct McapWriter<W: Write> {
+    writer: W,
+    /// Content of the record being written, reused between records.
+    record: Vec<u8>,
+}
+
+impl<W: Write> McapWriter<W> {
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            record: Vec::new(),
+        }
+    }
+
+    fn put_u16(&mut self, value: u16) {
+        self.record.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u32(&mut self, value: u32) {
+        self.record.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u64(&mut self, value: u64) {
+        self.record.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Length prefixed byte array, also used for strings.
+    fn put_bytes(&mut self, bytes: &[u8]) {
+        self.put_u32(bytes.len() as u32);
+        self.record.extend_from_slice(bytes);
+    }
+
+    /// Write the pending record content with its opcode and length prefix.
+    fn write_record(&mut self, opcode: u8) -> std::io::Result<()> {
+        self.writer.write_all(&[opcode])?;
+        self.writer
+            .write_all(&(self.record.len() as u64).to_le_bytes())?;
+        self.writer.write_all(&self.record)?;
+        self.record.clear();
+        Ok(())
+    }
+
+    fn write_header(&mut self) -> std::io::Result<()> {
+        self.writer.write_all(MCAP_MAGIC)?;
+        self.put_bytes(b"");
+        self.put_bytes(b"comet");
+        self.write_record(OP_HEADER)
+    }
+
+    fn write_schema(&mut self, id: u16, name: &str, data: &[u8]) -> std::io::Result<()> {
+        self.put_u16(id);
+        self.put_bytes(name.as_bytes());
+        self.put_bytes(b"jsonschema");
+        self.put_bytes(data);
+        self.write_record(OP_SCHEMA)
+    }
+
+    fn write_channel(&mut self, id: u16, schema_id: u16, topic: &str) -> std::io::Result<()> {
+        self.put_u16(id);
+        self.put_u16(schema_id);
+        self.put_bytes(topic.as_bytes());
+        self.put_bytes(b"json");
+        // Empty metadata map.
+        self.put_u32(0);
+        self.write_record(OP_CHANNEL)
+    }
+
+    fn write_message(
+        &mut self,
+        channel_id: u16,
+        sequence: u32,
+        time_nanos: u64,
+        data: &[u8],
+    ) -> std::io::Result<()> {
+        self.put_u16(channel_id);
+        self.put_u32(sequence);
+        // Log and publish times are both the tick timestamp.
+        self.put_u64(time_nanos);
+        self.put_u64(time_nanos);
+        self.record.extend_from_slice(data);
+        self.write_record(OP_MESSAGE)
+    }
+
+    /// Write the data end and footer records, CRC and summary fields are left zero.
+    fn write_footer(&mut self) -> std::io::Result<()> {
+        self.put_u32(0);
+        self.write_record(OP_DATA_END)?;
+        self.put_u64(0);
+        self.put_u64(0);
+        self.put_u32(0);
+        self.write_record(OP_FOOTER)?;
+        self.writer.write_all(MCAP_MAGIC)?;
+        self.writer.flush()
+    }
+}
+
+/// Channel logged into the MCAP file.
+struct McapChannel {
+    name: String,
+    /// MCAP channel and schema id of the channel.
+    id: u16,
+    accessor_id: usize,
+    /// Write sequence of the channel when it was last logged, None before the first tick.
+    sequence: Option<u64>,
+}
+
+/// Telemetry sink writing channel updates into an MCAP file, readable by tools such as Foxglove.
+/// Every selected channel maps to an MCAP channel whose topic is the channel name. Messages are
+/// JSON encoded with a JSON schema derived from the channel type when the sink is attached, see
+/// ChannelStore::channel_schema(). Channels whose schema cannot be derived are logged with a
+/// permissive schema accepting any value. Attaching fails if more than 65535 channels are
+/// selected. Channels are logged on the first tick and on every tick they were written, using the
+/// runner clock timestamp of the tick.
+///
+/// Only channels registered with a serializable type, see register_write_channel_serde(), can be
+/// logged. Selected channels without a serializable type are skipped.
+pub struct McapSink<W: Write = BufWriter<File>> {
+    writer: McapWriter<W>,
+    filter: ChannelFilter,
+    /// Logged channels, in MCAP channel id order.
+    channels: Vec<McapChannel>,
+    /// First write error, no further records are written once set.
+    error: Option<TelemetryError>,
+    finished: bool,
+}
+
+impl McapSink {
+    /// Create or truncate the MCAP file at the given path.
+    ///
+    /// ### Arguments
+    /// * 'path' - Path of the MCAP file.
+    /// * 'filter' - Selection of the logged channels.
+    ///
+    pub fn create(path: impl AsRef<Path>, filter: ChannelFilter) -> std::io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?), filter))
+    }
+}
+
+impl<W: Write> McapSink<W> {
+    /// Create a sink writing the MCAP file into the given writer.
+    ///
+    /// ### Arguments
+    /// * 'writer' - Destination of the MCAP file.
+    /// * 'filter' - Selection of the logged channels.
+    ///
+    pub fn new(writer: W, filter: ChannelFilter) -> Self {
+        Self {
+            writer: McapWriter::new(writer),
+            filter,
+            channels: Vec::new(),
+            error: None,
+            finished: false,
+        }
+    }
+
+    /// Names of the logged channels, available once the sink is attached.
+    pub fn channel_names(&self) -> impl Iterator<Item = &str> {
+        self.channels.iter().map(|channel| channel.name.as_str())
+    }
+
+    fn write_error(err: std::io::Error) -> TelemetryError {
+        TelemetryError::Write {
+            message: err.to_string(),
+        }
+    }
+}
+
+impl<W: Write> TelemetrySink for McapSink<W> {
+    fn attach(&mut self, channel_store: &ChannelStore) -> Result<(), TelemetryError> {
+        let names: Vec<String> = channel_store
+            .serializable_channels()
+            .filter(|name| self.filter.matches(name))
+            .map(String::from)
+            .collect();
+
+        self.writer.write_header().map_err(Self::write_error)?;
+        for name in names {
+            let schema = channel_store
+                .channel_schema(&name)
+                .unwrap_or_else(|_| serde_json::json!({}));
+            let schema = serde_json::to_vec(&schema).map_err(|err| TelemetryError::Channel {
+                name: name.clone(),
+                reason: err.to_string(),
+            })?;
+            let channel_info = channel_store
+                .channel(&name)
+                .expect("Serializable channel must exist.");
+
+            // MCAP channel and schema ids start at one, zero being reserved.
+            let id =
+                u16::try_from(self.channels.len() + 1).map_err(|_| TelemetryError::Channel {
+                    name: name.clone(),
+                    reason: String::from("MCAP files hold at most 65535 channels."),
+                })?;
+            self.writer
+                .write_schema(id, channel_info.type_name, &schema)
+                .map_err(Self::write_error)?;
+            self.writer
+                .write_channel(id, id, &name)
+                .map_err(Self::write_error)?;
+            self.channels.push(McapChannel {
+                accessor_id: channel_store
+                    .channel_accessor_id(&name)
+                    .expect("Serializable channel must exist."),
+                name,
+                id,
+                sequence: None,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn record_tick(&mut self, _tick: u64, timestamp: Duration, channel_store: &ChannelStore) {
+        if self.error.is_some() || self.finished {
+            return;
+        }
+
+        for channel in self.channels.iter_mut() {
+            let sequence = channel_store.channel_write_sequence(channel.accessor_id);
+            if channel.sequence == Some(sequence) {
+                continue;
+            }
+            channel.sequence = Some(sequence);
+
+            let result = channel_store
+                .encode_channel(&channel.name)
+                .map_err(|err| err.to_string())
+                .and_then(|value| serde_json::to_vec(&value).map_err(|err| err.to_string()))
+                .and_then(|data| {
+                    self.writer
+                        .write_message(
+                            channel.id,
+                            sequence as u32,
+                            timestamp.as_nanos() as u64,
+                            &data,
+                        )
+                        .map_err(|err| err.to_string())
+                });
+            if let Err(message) = result {
+                self.error = Some(TelemetryError::Write { message });
+                return;
+            }
+        }
+    }
+
+    fn finish(&mut self) -> Result<(), TelemetryError> {
+        if !self.finished {
+            self.finished = true;
+            let result = self.writer.write_footer().map_err(Self::write_error);
+            if self.error.is_none() {
+                result?;
+            }
+        }
+
+        match self.error.as_ref() {
+            Some(err) => Err(err.clone()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<W: Write> Drop for McapSink<W> {
+    fn drop(&mut self) {
+        // Errors cannot be reported while dropped, finish() reports them when called explicitly.
+        let _ = self.finish();
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use core::{cell::RefCell, time::Duration};
+    use std::io::Write;
+
+    use alloc::{
+        boxed::Box,
+        format,
+        rc::Rc,
+        string::{String, ToString},
+        vec::Vec,
+    };
+
+    use crate::{
+        channel::{
+            store::{ChannelStore, ChannelWriteBuilder, RegViewProducer},
+            token::ChannelOwnerToken,
+        },
+        system::{
+            clock::SimClock,
+            component::{Component, DispatchContext},
+            runner::Runner,
+            telemetry::{ChannelFilter, TelemetryError, TelemetrySink},
+        },
+    };
+
+    use super::{MCAP_MAGIC, McapSink};
+
+    /// In memory MCAP destination whose bytes remain readable after the sink is dropped.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Split an MCAP file into the opcode and content of each record.
+    fn parse_records(bytes: &[u8]) -> Vec<(u8, Vec<u8>)> {
+        assert!(bytes.starts_with(MCAP_MAGIC) && bytes.ends_with(MCAP_MAGIC));
+        let mut input = &bytes[MCAP_MAGIC.len()..bytes.len() - MCAP_MAGIC.len()];
+        let mut records = Vec::new();
+        while !input.is_empty() {
+            let len = u64::from_le_bytes(input[1..9].try_into().unwrap()) as usize;
+            records.push((input[0], input[9..9 + len].to_vec()));
+            input = &input[9 + len..];
+        }
+        records
+    }
+
+    /// Read the length prefixed string at the given offset of a record.
+    fn read_string(content: &[u8], offset: usize) -> String {
+        let len = u32::from_le_bytes(content[offset..offset + 4].try_into().unwrap()) as usize;
+        String::from_utf8(content[offset + 4..offset + 4 + len].to_vec()).unwrap()
+    }
+
+    fn test_store() -> (ChannelStore, ChannelOwnerToken<(f64, u32)>) {
+        let mut channel_store = ChannelStore::default();
+        let builder = ChannelWriteBuilder::new(0);
+        let pose = builder.register_write_channel_serde(
+            &mut channel_store,
+            "nav.pose".to_string(),
+            (0.5f64, 1u32),
+        );
+        builder.register_write_channel_serde(&mut channel_store, "nav.debug".to_string(), true);
+        builder.register_write_channel(&mut channel_store, "nav.opaque".to_string(), 0u8);
+        (channel_store, pose)
+    }
+
+    #[test]
+    fn test_mcap_sink() {
+        let (channel_store, pose) = test_store();
+        let buffer = SharedBuffer::default();
+        let mut sink = McapSink::new(
+            buffer.clone(),
+            ChannelFilter::default().include("nav.*").exclude("*.debug"),
+        );
+        sink.attach(&channel_store).unwrap();
+        assert_eq!(sink.channel_names().collect::<Vec<&str>>(), ["nav.pose"]);
+
+        sink.record_tick(0, Duration::from_millis(1), &channel_store);
+        sink.record_tick(1, Duration::from_millis(2), &channel_store);
+        channel_store.grab(&pose).set((1.5, 2));
+        sink.record_tick(2, Duration::from_millis(3), &channel_store);
+        assert_eq!(sink.finish(), Ok(()));
+        drop(sink);
+
+        let records = parse_records(&buffer.0.borrow());
+        assert_eq!(
+            records
+                .iter()
+                .map(|(opcode, _)| *opcode)
+                .collect::<Vec<u8>>(),
+            [0x01, 0x03, 0x04, 0x05, 0x05, 0x0F, 0x02]
+        );
+
+        let (_, schema) = &records[1];
+        assert_eq!(read_string(schema, 2), "(f64, u32)");
+        assert_eq!(read_string(schema, 16), "jsonschema");
+        assert_eq!(
+            read_string(schema, 30),
+            r#"{"maxItems":2,"minItems":2,"prefixItems":[{"type":"number"},{"type":"integer"}],"type":"array"}"#
+        );
+
+        let (_, channel) = &records[2];
+        assert_eq!(read_string(channel, 4), "nav.pose");
+
+        // The pose is logged on the first tick and after it was written.
+        let (_, message) = &records[4];
+        assert_eq!(u16::from_le_bytes([message[0], message[1]]), 1);
+        assert_eq!(
+            u64::from_le_bytes(message[6..14].try_into().unwrap()),
+            3_000_000
+        );
+        assert_eq!(&message[22..], b"[1.5,2]");
+    }
+
+    /// Value whose serialization always fails.
+    #[derive(Clone)]
+    struct TestFailing;
+    impl serde::Serialize for TestFailing {
+        fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("test failure"))
+        }
+    }
+
+    #[test]
+    fn test_mcap_sink_schema_fallback() {
+        let mut channel_store = ChannelStore::default();
+        let builder = ChannelWriteBuilder::new(0);
+        builder.register_write_channel_serde(
+            &mut channel_store,
+            "nav.failing".to_string(),
+            TestFailing,
+        );
+        builder.register_write_channel_serde(
+            &mut channel_store,
+            "nav.fix".to_string(),
+            None::<(f64, f64)>,
+        );
+
+        let buffer = SharedBuffer::default();
+        let mut sink = McapSink::new(buffer.clone(), ChannelFilter::default());
+        sink.attach(&channel_store).unwrap();
+        assert_eq!(sink.channel_names().count(), 2);
+        drop(sink);
+
+        // Both channels are described by a schema accepting any value.
+        let records = parse_records(&buffer.0.borrow());
+        let schemas: Vec<String> = records
+            .iter()
+            .filter(|(opcode, _)| *opcode == 0x03)
+            .map(|(_, schema)| {
+                let name_len = read_string(schema, 2).len();
+                read_string(schema, 6 + name_len + 4 + "jsonschema".len())
+            })
+            .collect();
+        assert_eq!(schemas, ["{}", "{}"]);
+    }
+
+    #[test]
+    fn test_mcap_sink_channel_limit() {
+        let mut channel_store = ChannelStore::default();
+        let builder = ChannelWriteBuilder::new(0);
+        for idx in 0..=u16::MAX as usize {
+            builder.register_write_channel_serde(&mut channel_store, format!("test.{}", idx), 0u8);
+        }
+
+        let mut sink = McapSink::new(Vec::new(), ChannelFilter::default());
+        assert!(matches!(
+            sink.attach(&channel_store),
+            Err(TelemetryError::Channel { name, .. }) if name == "test.65535"
+        ));
+        assert_eq!(sink.channel_names().count(), u16::MAX as usize);
+    }
+
+    struct TestWriter(ChannelOwnerToken<u32>);
+    impl Component for TestWriter {
+        fn register_write_channels(
+            &mut self,
+            channel_builder: ChannelWriteBuilder,
+            channel_store: &mut ChannelStore,
+        ) {
+            self.0 = channel_builder.register_write_channel_serde(
+                channel_store,
+                "test.counter".to_string(),
+                0,
+            );
+        }
+
+        fn dispatch(&mut self, channel_store: &ChannelStore, _context: &DispatchContext) {
+            channel_store.grab(&self.0).update(|value| *value += 1);
+        }
+    }
+
+    #[test]
+    fn test_mcap_file() {
+        let path = std::env::temp_dir().join("comet_test_mcap_file.mcap");
+        let clock = SimClock::default();
+        let mut runner = Runner::default();
+        runner.set_clock(Box::new(clock.clone()));
+        runner.add_component(Box::new(TestWriter(ChannelOwnerToken::default())));
+        runner.initialize();
+        runner
+            .add_telemetry_sink(Box::new(
+                McapSink::create(&path, ChannelFilter::default()).unwrap(),
+            ))
+            .unwrap();
+
+        for _ in 0..3 {
+            clock.step(Duration::from_millis(10));
+            runner.dispatch_components();
+        }
+        assert_eq!(runner.finish_telemetry(), Ok(()));
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let messages: Vec<Vec<u8>> = parse_records(&bytes)
+            .into_iter()
+            .filter(|(opcode, _)| *opcode == 0x05)
+            .map(|(_, content)| content)
+            .collect();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(&messages[2][22..], b"3");
+        assert_eq!(
+            u64::from_le_bytes(messages[2][6..14].try_into().unwrap()),
+            30_000_000
+        );
+    }
+}
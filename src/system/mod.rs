@@ -1,3 +1,20 @@
+pub mod bridge;
+pub mod clock;
 pub mod component;
+#[cfg(feature = "std")]
+pub mod csv;
+pub mod event_log;
+pub mod graph;
+pub mod lint;
+#[cfg(all(feature = "std", feature = "serde"))]
+pub mod mcap;
 pub mod order;
+#[cfg(feature = "std")]
+mod pool;
+pub mod record;
+pub mod report;
 pub mod runner;
+pub mod schedule;
+pub mod telemetry;
+pub mod timing;
+pub mod write_policy;
@@ -2,23 +2,47 @@ use alloc::vec::Vec;
 
 use super::component::ComponentHolder;
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy, Debug)]
 pub(crate) struct NodeDependency {
     pub owner: usize,
     pub consumer: usize,
+    /// Accessor id of the channel which created this dependency.
+    pub channel: usize,
 }
 
+/// Dependency graph between nodes stored as adjacency lists indexed by owner node id.
 #[derive(Default)]
 pub(crate) struct NodeGraph {
-    mappings: Vec<NodeDependency>,
+    adjacency: Vec<Vec<NodeDependency>>,
+    /// Total number of unique dependencies within the graph.
+    edge_count: usize,
 }
 
 impl NodeGraph {
     pub(crate) fn insert_node_dependency(&mut self, node_dep: NodeDependency) {
         assert_ne!(node_dep.owner, node_dep.consumer);
-        if !self.mappings.contains(&node_dep) {
-            self.mappings.push(node_dep);
+        if self.adjacency.len() <= node_dep.owner {
+            self.adjacency.resize_with(node_dep.owner + 1, Vec::new);
         }
+
+        let consumers = &mut self.adjacency[node_dep.owner];
+        if !consumers.contains(&node_dep) {
+            consumers.push(node_dep);
+            self.edge_count += 1;
+        }
+    }
+
+    /// Every dependency within the graph, ordered by owner node id.
+    pub(crate) fn dependencies(&self) -> impl Iterator<Item = &NodeDependency> {
+        self.adjacency.iter().flatten()
+    }
+
+    /// Dependencies in which the given node is the owner, in insertion order.
+    pub(crate) fn consumers_of(&self, owner: usize) -> &[NodeDependency] {
+        self.adjacency
+            .get(owner)
+            .map(|consumers| consumers.as_slice())
+            .unwrap_or(&[])
     }
 }
 
@@ -28,9 +52,21 @@ struct NodeMarker {
     pub perm_marker: bool,
 }
 
+/// Depth first search frame used in place of recursion while visiting nodes.
+struct VisitFrame {
+    marker_idx: usize,
+    next_consumer: usize,
+}
+
 pub(crate) struct NodeOrderCalc {
     node_graph: NodeGraph,
     node_markers: Vec<NodeMarker>,
+    /// Lookup from node id to node marker index.
+    marker_lookup: Vec<usize>,
+    /// Dependencies followed to reach the node currently being visited, in visit order.
+    visit_path: Vec<NodeDependency>,
+    /// Dependency paths of every cycle encountered while calculating the order.
+    cycles: Vec<Vec<NodeDependency>>,
 }
 
 impl NodeOrderCalc {
@@ -47,112 +83,163 @@ impl NodeOrderCalc {
         node_markers
     }
 
+    fn build_marker_lookup(node_markers: &[NodeMarker]) -> Vec<usize> {
+        let lookup_len = node_markers
+            .iter()
+            .map(|marker| marker.node_id + 1)
+            .max()
+            .unwrap_or(0);
+        let mut marker_lookup = Vec::new();
+        marker_lookup.resize(lookup_len, usize::MAX);
+        for (marker_idx, marker) in node_markers.iter().enumerate() {
+            marker_lookup[marker.node_id] = marker_idx;
+        }
+
+        marker_lookup
+    }
+
     pub(super) fn new(node_graph: NodeGraph, components: &[ComponentHolder]) -> Self {
         let node_markers = Self::build_node_markers(components);
+        let marker_lookup = Self::build_marker_lookup(&node_markers);
         Self {
             node_graph,
             node_markers,
+            marker_lookup,
+            visit_path: Vec::new(),
+            cycles: Vec::new(),
         }
     }
 
-    fn visit_node(&mut self, curr_marker_idx: usize, ordering: &mut Vec<usize>) {
-        let curr_marker_id = {
-            let curr_marker = self.node_markers.get_mut(curr_marker_idx).unwrap();
+    /// Release the dependency graph used to calculate the ordering.
+    pub(super) fn into_node_graph(self) -> NodeGraph {
+        self.node_graph
+    }
 
-            if curr_marker.perm_marker {
-                return;
-            }
-            if curr_marker.temp_marker {
-                panic!("Cycle detected in execution order.")
+    /// Visit a node and every node reachable from it, appending nodes to the ordering in
+    /// post-order (reverse topological order).
+    fn visit_node(&mut self, start_marker_idx: usize, ordering: &mut Vec<usize>) {
+        if self.node_markers[start_marker_idx].perm_marker {
+            return;
+        }
+        self.node_markers[start_marker_idx].temp_marker = true;
+
+        let mut visit_stack = Vec::from([VisitFrame {
+            marker_idx: start_marker_idx,
+            next_consumer: 0,
+        }]);
+        while let Some(frame) = visit_stack.last_mut() {
+            let node_id = self.node_markers[frame.marker_idx].node_id;
+            let consumers = self.node_graph.consumers_of(node_id);
+
+            if frame.next_consumer == consumers.len() {
+                // All consumers have been visited, the node is complete.
+                let curr_marker = &mut self.node_markers[frame.marker_idx];
+                curr_marker.temp_marker = false;
+                curr_marker.perm_marker = true;
+                ordering.push(node_id);
+                visit_stack.pop();
+                self.visit_path.pop();
+                continue;
             }
-            curr_marker.temp_marker = true;
 
-            curr_marker.node_id
-        };
+            let consumer_dep = consumers[frame.next_consumer];
+            frame.next_consumer += 1;
+            let consumer_marker_idx = self.marker_lookup[consumer_dep.consumer];
+            let consumer_marker = &mut self.node_markers[consumer_marker_idx];
 
-        let consumers: Vec<usize> = self
-            .node_graph
-            .mappings
-            .iter()
-            .filter(|x| x.owner == curr_marker_id)
-            .map(|x| x.consumer)
-            .collect();
-        for consumer_id in consumers {
-            let rec_marker_idx = self
-                .node_markers
-                .iter()
-                .enumerate()
-                .find(|(_, marker)| consumer_id == marker.node_id)
-                .unwrap()
-                .0;
-            self.visit_node(rec_marker_idx, ordering)
-        }
+            if consumer_marker.perm_marker {
+                continue;
+            }
+            if consumer_marker.temp_marker {
+                // The consumer is already on the visit path, the dependency closes a loop back to it.
+                // Record the loop and continue so that every cycle within the graph is reported.
+                let cycle_start = self
+                    .visit_path
+                    .iter()
+                    .position(|dep| dep.owner == consumer_dep.consumer)
+                    .unwrap_or(self.visit_path.len());
+                let mut cycle = self.visit_path[cycle_start..].to_vec();
+                cycle.push(consumer_dep);
+                self.cycles.push(cycle);
+                continue;
+            }
 
-        {
-            let curr_marker = self.node_markers.get_mut(curr_marker_idx).unwrap();
+            consumer_marker.temp_marker = true;
+            self.visit_path.push(consumer_dep);
+            visit_stack.push(VisitFrame {
+                marker_idx: consumer_marker_idx,
+                next_consumer: 0,
+            });
+        }
+    }
 
-            curr_marker.temp_marker = false;
-            curr_marker.perm_marker = true;
-        };
+    /// Calculate the execution order of all nodes. On failure the dependency path of every
+    /// detected cycle is returned, the last dependency of each path being the one which closes the loop.
+    pub(crate) fn calculate_topological_order(
+        &mut self,
+    ) -> Result<Vec<usize>, Vec<Vec<NodeDependency>>> {
+        let mut ordering: Vec<usize> = Vec::with_capacity(self.node_markers.len());
 
-        ordering.insert(0, curr_marker_id);
-    }
+        for marker_idx in 0..self.node_markers.len() {
+            self.visit_node(marker_idx, &mut ordering);
+        }
 
-    pub(crate) fn calculate_topological_order(&mut self) -> Vec<usize> {
-        let mut ordering: Vec<usize> = Vec::new();
-
-        while self.node_markers.iter().any(|x| !x.perm_marker) {
-            let select_marker_idx = self
-                .node_markers
-                .iter()
-                .enumerate()
-                .find(|(_, marker)| !marker.perm_marker)
-                .unwrap()
-                .0;
-            self.visit_node(select_marker_idx, &mut ordering);
+        if !self.cycles.is_empty() {
+            return Err(core::mem::take(&mut self.cycles));
         }
 
-        ordering
+        ordering.reverse();
+        Ok(ordering)
     }
 }
 
 #[cfg(test)]
 mod unit_tests {
-    use alloc::{boxed::Box, vec::Vec};
+    use alloc::{boxed::Box, string::String, vec::Vec};
 
     use crate::system::{
-        component::{Component, ComponentHolder},
+        component::{Component, ComponentBox, ComponentHolder},
         order::{NodeDependency, NodeGraph},
+        schedule::RateSchedule,
+        timing::TimingStats,
     };
 
     use super::NodeOrderCalc;
 
     struct TestComponent();
     impl Component for TestComponent {
-        fn dispatch(&mut self, _channel_store: &crate::channel::store::ChannelStore) {}
+        fn dispatch(
+            &mut self,
+            _channel_store: &crate::channel::store::ChannelStore,
+            _context: &crate::system::component::DispatchContext,
+        ) {
+        }
     }
 
     #[test]
     #[should_panic(expected = "assertion `left != right` failed")]
     fn test_node_graph() {
         let mut node_graph = NodeGraph::default();
-        assert_eq!(node_graph.mappings.len(), 0);
+        assert_eq!(node_graph.edge_count, 0);
 
         node_graph.insert_node_dependency(NodeDependency {
             owner: 1,
             consumer: 2,
+            channel: 0,
         });
-        assert_eq!(node_graph.mappings.len(), 1);
+        assert_eq!(node_graph.edge_count, 1);
 
         node_graph.insert_node_dependency(NodeDependency {
             owner: 1,
             consumer: 2,
+            channel: 0,
         });
-        assert_eq!(node_graph.mappings.len(), 1);
+        assert_eq!(node_graph.edge_count, 1);
 
         node_graph.insert_node_dependency(NodeDependency {
             owner: 1,
             consumer: 1,
+            channel: 0,
         });
     }
 
@@ -160,16 +247,34 @@ mod unit_tests {
     fn test_build_node_markers() {
         let holders = [
             ComponentHolder {
-                component: Box::new(TestComponent {}),
+                component: ComponentBox::Local(Box::new(TestComponent {})),
                 id: 0,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
             },
             ComponentHolder {
-                component: Box::new(TestComponent {}),
+                component: ComponentBox::Local(Box::new(TestComponent {})),
                 id: 1,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
             },
             ComponentHolder {
-                component: Box::new(TestComponent {}),
+                component: ComponentBox::Local(Box::new(TestComponent {})),
                 id: 2,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
             },
         ];
 
@@ -189,77 +294,234 @@ mod unit_tests {
     fn test_order() {
         let holders = [
             ComponentHolder {
-                component: Box::new(TestComponent {}),
+                component: ComponentBox::Local(Box::new(TestComponent {})),
                 id: 0,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
             },
             ComponentHolder {
-                component: Box::new(TestComponent {}),
+                component: ComponentBox::Local(Box::new(TestComponent {})),
                 id: 1,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
             },
             ComponentHolder {
-                component: Box::new(TestComponent {}),
+                component: ComponentBox::Local(Box::new(TestComponent {})),
                 id: 2,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
             },
         ];
 
         let mut node_graph = NodeGraph::default();
-        assert_eq!(node_graph.mappings.len(), 0);
+        assert_eq!(node_graph.edge_count, 0);
 
         node_graph.insert_node_dependency(NodeDependency {
             owner: 1,
             consumer: 2,
+            channel: 0,
         });
         node_graph.insert_node_dependency(NodeDependency {
             owner: 1,
             consumer: 0,
+            channel: 0,
         });
         node_graph.insert_node_dependency(NodeDependency {
             owner: 1,
             consumer: 2,
+            channel: 0,
         });
 
         let mut order_calc = NodeOrderCalc::new(node_graph, &holders);
-        let ordering = order_calc.calculate_topological_order();
+        let ordering = order_calc.calculate_topological_order().unwrap();
 
         assert_eq!(ordering, Vec::from([1, 2, 0]));
     }
 
     #[test]
-    #[should_panic(expected = "Cycle detected in execution order.")]
     fn test_order_cycle() {
         let holders = [
             ComponentHolder {
-                component: Box::new(TestComponent {}),
+                component: ComponentBox::Local(Box::new(TestComponent {})),
                 id: 0,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
             },
             ComponentHolder {
-                component: Box::new(TestComponent {}),
+                component: ComponentBox::Local(Box::new(TestComponent {})),
                 id: 1,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
             },
             ComponentHolder {
-                component: Box::new(TestComponent {}),
+                component: ComponentBox::Local(Box::new(TestComponent {})),
                 id: 2,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
             },
         ];
 
         let mut node_graph = NodeGraph::default();
-        assert_eq!(node_graph.mappings.len(), 0);
+        assert_eq!(node_graph.edge_count, 0);
 
         node_graph.insert_node_dependency(NodeDependency {
             owner: 0,
             consumer: 1,
+            channel: 0,
         });
         node_graph.insert_node_dependency(NodeDependency {
             owner: 1,
             consumer: 2,
+            channel: 1,
         });
         node_graph.insert_node_dependency(NodeDependency {
             owner: 2,
             consumer: 0,
+            channel: 2,
         });
 
         let mut order_calc = NodeOrderCalc::new(node_graph, &holders);
         // This should fail on dep cycle check.
-        let _ordering = order_calc.calculate_topological_order();
+        let cycles = order_calc.calculate_topological_order().unwrap_err();
+        assert_eq!(cycles.len(), 1);
+        let cycle_path: Vec<(usize, usize, usize)> = cycles[0]
+            .iter()
+            .map(|dep| (dep.owner, dep.consumer, dep.channel))
+            .collect();
+        assert_eq!(cycle_path, Vec::from([(0, 1, 0), (1, 2, 1), (2, 0, 2)]));
+    }
+
+    #[test]
+    fn test_order_multiple_cycles() {
+        let holders = [
+            ComponentHolder {
+                component: ComponentBox::Local(Box::new(TestComponent {})),
+                id: 0,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
+            },
+            ComponentHolder {
+                component: ComponentBox::Local(Box::new(TestComponent {})),
+                id: 1,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
+            },
+            ComponentHolder {
+                component: ComponentBox::Local(Box::new(TestComponent {})),
+                id: 2,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
+            },
+            ComponentHolder {
+                component: ComponentBox::Local(Box::new(TestComponent {})),
+                id: 3,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
+            },
+        ];
+
+        let mut node_graph = NodeGraph::default();
+        node_graph.insert_node_dependency(NodeDependency {
+            owner: 0,
+            consumer: 1,
+            channel: 0,
+        });
+        node_graph.insert_node_dependency(NodeDependency {
+            owner: 1,
+            consumer: 0,
+            channel: 1,
+        });
+        node_graph.insert_node_dependency(NodeDependency {
+            owner: 2,
+            consumer: 3,
+            channel: 2,
+        });
+        node_graph.insert_node_dependency(NodeDependency {
+            owner: 3,
+            consumer: 2,
+            channel: 3,
+        });
+
+        let mut order_calc = NodeOrderCalc::new(node_graph, &holders);
+        let cycles = order_calc.calculate_topological_order().unwrap_err();
+        let cycle_paths: Vec<Vec<(usize, usize)>> = cycles
+            .iter()
+            .map(|cycle| cycle.iter().map(|dep| (dep.owner, dep.consumer)).collect())
+            .collect();
+        assert_eq!(
+            cycle_paths,
+            Vec::from([Vec::from([(0, 1), (1, 0)]), Vec::from([(2, 3), (3, 2)])])
+        );
+    }
+
+    #[test]
+    fn test_order_long_chain() {
+        let node_count = 10_000usize;
+        let holders: Vec<ComponentHolder> = (0..node_count)
+            .map(|id| ComponentHolder {
+                component: ComponentBox::Local(Box::new(TestComponent {})),
+                id,
+                name: String::new(),
+                schedule: RateSchedule::default(),
+                last_dispatch: None,
+                timing: TimingStats::default(),
+                budget: None,
+                level: 0,
+            })
+            .collect();
+
+        // Chain the nodes in reverse insertion order, each node consuming from the next.
+        let mut node_graph = NodeGraph::default();
+        for id in 1..node_count {
+            node_graph.insert_node_dependency(NodeDependency {
+                owner: id,
+                consumer: id - 1,
+                channel: id,
+            });
+        }
+
+        let mut order_calc = NodeOrderCalc::new(node_graph, &holders);
+        let ordering = order_calc.calculate_topological_order().unwrap();
+        assert_eq!(ordering, (0..node_count).rev().collect::<Vec<usize>>());
     }
 }
@@ -0,0 +1,181 @@
+use core::{any::Any, mem};
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc::{self, Receiver, Sender},
+    thread::{self, JoinHandle},
+};
+
+use alloc::{boxed::Box, format, vec::Vec};
+
+/// Borrowed job of a worker with its lifetime erased, see WorkerPool::run().
+struct Job(*mut (dyn FnMut() + Send + 'static));
+
+// SAFETY: The job closure is Send, the pointer is only dereferenced by the receiving worker.
+unsafe impl Send for Job {}
+
+/// Outcome of a job, holding the panic payload of the job if it panicked.
+type JobResult = Result<(), Box<dyn Any + Send>>;
+
+struct Worker {
+    jobs: Option<Sender<Job>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Persistent worker threads running borrowed jobs. Workers are spawned once when the pool is
+/// created and joined when the pool is dropped.
+pub(crate) struct WorkerPool {
+    workers: Vec<Worker>,
+    results: Receiver<JobResult>,
+}
+
+impl WorkerPool {
+    /// Spawn the worker threads of a pool.
+    ///
+    /// ### Argument
+    /// * 'threads' - Number of worker threads.
+    ///
+    pub fn new(threads: usize) -> Self {
+        let (result_sender, results) = mpsc::channel::<JobResult>();
+        let workers = (0..threads)
+            .map(|idx| {
+                let (jobs, job_receiver) = mpsc::channel::<Job>();
+                let result_sender = result_sender.clone();
+                let handle = thread::Builder::new()
+                    .name(format!("comet-worker-{}", idx))
+                    .spawn(move || {
+                        while let Ok(job) = job_receiver.recv() {
+                            // SAFETY: WorkerPool::run() keeps the job borrowed until its result
+                            // was received.
+                            let result =
+                                panic::catch_unwind(AssertUnwindSafe(|| unsafe { (*job.0)() }));
+                            if result_sender.send(result).is_err() {
+                                break;
+                            }
+                        }
+                    })
+                    .expect("Failed to spawn a worker thread.");
+                Worker {
+                    jobs: Some(jobs),
+                    handle: Some(handle),
+                }
+            })
+            .collect();
+
+        Self { workers, results }
+    }
+
+    /// Number of worker threads of the pool.
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Run each job on its own worker, blocking until every job completed. A panic of a job is
+    /// resumed on the calling thread once every job completed.
+    ///
+    /// ### Argument
+    /// * 'jobs' - Jobs to be run, at most one per worker thread.
+    ///
+    pub fn run(&self, jobs: &mut [&mut (dyn FnMut() + Send)]) {
+        assert!(jobs.len() <= self.workers.len());
+
+        let mut sent = 0;
+        for (worker, job) in self.workers.iter().zip(jobs.iter_mut()) {
+            let job: *mut (dyn FnMut() + Send + '_) = &mut **job;
+            // SAFETY: The lifetime is only erased for the duration of this call, every job sent
+            // is awaited below before returning, including when a job panics.
+            let job = unsafe {
+                mem::transmute::<*mut (dyn FnMut() + Send + '_), *mut (dyn FnMut() + Send + 'static)>(
+                    job,
+                )
+            };
+            match worker.jobs.as_ref().map(|jobs| jobs.send(Job(job))) {
+                Some(Ok(())) => sent += 1,
+                _ => break,
+            }
+        }
+
+        let mut payload = None;
+        for _ in 0..sent {
+            // Workers only exit once their job sender is dropped, a result is received for every
+            // job sent.
+            if let Err(job_payload) = self.results.recv().expect("Worker thread exited.") {
+                payload.get_or_insert(job_payload);
+            }
+        }
+        if let Some(payload) = payload {
+            panic::resume_unwind(payload);
+        }
+        assert_eq!(sent, jobs.len(), "Worker thread exited.");
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        for worker in self.workers.iter_mut() {
+            worker.jobs = None;
+        }
+        for worker in self.workers.iter_mut() {
+            if let Some(handle) = worker.handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use std::thread::ThreadId;
+
+    use alloc::vec::Vec;
+
+    use super::WorkerPool;
+
+    #[test]
+    fn test_worker_pool_run() {
+        let pool = WorkerPool::new(3);
+        let counter = AtomicUsize::new(0);
+        let mut first_threads: Vec<ThreadId> = Vec::new();
+        for round in 0..100 {
+            let mut threads = [None; 3];
+            let mut jobs: Vec<_> = threads
+                .iter_mut()
+                .map(|thread| {
+                    let counter = &counter;
+                    move || {
+                        counter.fetch_add(1, Ordering::Relaxed);
+                        *thread = Some(std::thread::current().id());
+                    }
+                })
+                .collect();
+            pool.run(
+                &mut jobs
+                    .iter_mut()
+                    .map(|job| job as &mut (dyn FnMut() + Send))
+                    .collect::<Vec<_>>(),
+            );
+            drop(jobs);
+
+            // Jobs run on the same workers on every round.
+            let threads: Vec<ThreadId> = threads.iter().map(|thread| thread.unwrap()).collect();
+            if round == 0 {
+                first_threads = threads;
+            } else {
+                assert_eq!(threads, first_threads);
+            }
+        }
+        assert_eq!(counter.load(Ordering::Relaxed), 300);
+        assert!(!first_threads.contains(&std::thread::current().id()));
+    }
+
+    #[test]
+    #[should_panic(expected = "test job panic")]
+    fn test_worker_pool_panic() {
+        let pool = WorkerPool::new(2);
+        let mut first = || {};
+        let mut second = || panic!("test job panic");
+        pool.run(&mut [&mut first, &mut second]);
+    }
+}
@@ -0,0 +1,869 @@
+use core::{cell::RefCell, fmt, time::Duration};
+
+use alloc::{boxed::Box, rc::Rc, string::String, vec, vec::Vec};
+
+use crate::channel::{
+    error::ChannelError,
+    record::{RecordCodec, Recordable},
+    store::ChannelStore,
+};
+
+/// Leading bytes of every record log.
+const RECORD_MAGIC: [u8; 6] = *b"CMTREC";
+/// Version of the record log layout.
+const RECORD_VERSION: u8 = 2;
+
+/// Channel selected for recording or replay.
+#[derive(Clone)]
+struct RecordChannel {
+    name: String,
+    codec: RecordCodec,
+}
+
+/// Set of channels selected for recording or replay, each with the type of its values.
+#[derive(Clone, Default)]
+pub struct RecordChannels {
+    channels: Vec<RecordChannel>,
+}
+
+impl RecordChannels {
+    /// Select a channel holding values of type T.
+    ///
+    /// ### Argument
+    /// * 'name' - Name of the channel.
+    ///
+    pub fn add<T: Recordable>(&mut self, name: &str) -> &mut Self {
+        self.channels.push(RecordChannel {
+            name: String::from(name),
+            codec: RecordCodec::of::<T>(),
+        });
+        self
+    }
+
+    /// Names of the selected channels, in selection order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.channels.iter().map(|channel| channel.name.as_str())
+    }
+}
+
+/// Destination of the bytes of a record log.
+pub trait RecordWriter {
+    /// Append bytes to the log.
+    fn write(&mut self, bytes: &[u8]) -> Result<(), RecordError>;
+
+    /// Write out any buffered bytes of the log, called when the recorder is finished.
+    fn flush(&mut self) -> Result<(), RecordError> {
+        Ok(())
+    }
+}
+
+impl RecordWriter for Vec<u8> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), RecordError> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// In memory record log. Clones of a record buffer share the same bytes, a clone may be kept to
+/// read the log after handing the buffer to a recorder.
+#[derive(Clone, Default)]
+pub struct RecordBuffer {
+    bytes: Rc<RefCell<Vec<u8>>>,
+}
+
+impl RecordBuffer {
+    /// Copy of the bytes written so far.
+    pub fn bytes(&self) -> Vec<u8> {
+        self.bytes.borrow().clone()
+    }
+
+    /// Parse the bytes written so far.
+    pub fn parse(&self) -> Result<RecordLog, RecordError> {
+        RecordLog::parse(&self.bytes.borrow())
+    }
+}
+
+impl RecordWriter for RecordBuffer {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), RecordError> {
+        self.bytes.borrow_mut().extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// Record log written to a file through a buffered writer.
+#[cfg(feature = "std")]
+pub struct FileRecordWriter {
+    writer: std::io::BufWriter<std::fs::File>,
+}
+
+#[cfg(feature = "std")]
+impl FileRecordWriter {
+    /// Create or truncate the file at the given path.
+    pub fn create(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        Ok(Self {
+            writer: std::io::BufWriter::new(std::fs::File::create(path)?),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl RecordWriter for FileRecordWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), RecordError> {
+        use std::{io::Write, string::ToString};
+
+        self.writer
+            .write_all(bytes)
+            .map_err(|err| RecordError::Write {
+                message: err.to_string(),
+            })
+    }
+
+    fn flush(&mut self) -> Result<(), RecordError> {
+        use std::{io::Write, string::ToString};
+
+        self.writer.flush().map_err(|err| RecordError::Write {
+            message: err.to_string(),
+        })
+    }
+}
+
+/// Error produced while recording, parsing or replaying a record log.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RecordError {
+    /// A selected channel does not exist or holds a different type.
+    Channel(ChannelError),
+    /// The log is truncated or was not produced by a recorder.
+    InvalidLog,
+    /// A channel selected for replay is not part of the log.
+    NotRecorded { name: String },
+    /// A channel selected for replay was recorded with a different type.
+    TypeMismatch {
+        name: String,
+        expected: String,
+        actual: String,
+    },
+    /// A channel selected for replay is not owned by the replayed component.
+    NotOwner { name: String, component_id: usize },
+    /// A recorded value of a channel selected for replay cannot be decoded.
+    MalformedValue { name: String, tick: u64 },
+    /// The record writer failed to write the log.
+    Write { message: String },
+}
+
+impl From<ChannelError> for RecordError {
+    fn from(err: ChannelError) -> Self {
+        RecordError::Channel(err)
+    }
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::Channel(err) => err.fmt(f),
+            RecordError::InvalidLog => write!(f, "Record log is truncated or malformed."),
+            RecordError::NotRecorded { name } => {
+                write!(f, "Channel [{}] is not part of the record log.", name)
+            }
+            RecordError::TypeMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Channel [{}] was recorded with type [{}] but is replayed as type [{}].",
+                name, actual, expected
+            ),
+            RecordError::NotOwner { name, component_id } => write!(
+                f,
+                "Channel [{}] is not owned by replayed component [{}].",
+                name, component_id
+            ),
+            RecordError::MalformedValue { name, tick } => write!(
+                f,
+                "Recorded value of channel [{}] at tick [{}] is malformed.",
+                name, tick
+            ),
+            RecordError::Write { message } => {
+                write!(f, "Record log write failed: {}", message)
+            }
+        }
+    }
+}
+
+impl core::error::Error for RecordError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            RecordError::Channel(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Selected channel resolved within the channel store of a runner.
+struct RecordTarget {
+    accessor_id: usize,
+    codec: RecordCodec,
+    /// Write sequence of the channel when it was last recorded, None before the first tick.
+    sequence: Option<u64>,
+}
+
+/// Writes the values of selected channels into a record log at the end of every runner tick.
+/// The first tick records every selected channel, later ticks only record channels which were
+/// written since they were last recorded.
+///
+/// A log starts with a header listing the name and type tag of each selected channel, followed by
+/// one frame per tick holding the tick, timestamp and encoded value of each recorded channel.
+pub struct Recorder {
+    channels: RecordChannels,
+    writer: Box<dyn RecordWriter>,
+    targets: Vec<RecordTarget>,
+    /// Encoding buffer reused between ticks.
+    frame: Vec<u8>,
+    /// First write error, no further ticks are recorded once set.
+    error: Option<RecordError>,
+}
+
+impl Recorder {
+    /// Create a recorder which is attached to a runner with Runner::set_recorder().
+    ///
+    /// ### Arguments
+    /// * 'channels' - Channels to be recorded.
+    /// * 'writer' - Destination of the record log.
+    ///
+    pub fn new(channels: RecordChannels, writer: Box<dyn RecordWriter>) -> Self {
+        Self {
+            channels,
+            writer,
+            targets: Vec::new(),
+            frame: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// First error raised by the record writer, recording stops once a write failed.
+    pub fn error(&self) -> Option<&RecordError> {
+        self.error.as_ref()
+    }
+
+    /// Flush the record writer, returning the first error raised by the writer while recording.
+    pub fn finish(&mut self) -> Result<(), RecordError> {
+        if let Some(err) = self.error.as_ref() {
+            return Err(err.clone());
+        }
+        self.writer
+            .flush()
+            .inspect_err(|err| self.error = Some(err.clone()))
+    }
+
+    /// Resolve the selected channels within the store and write the log header.
+    pub(crate) fn attach(&mut self, channel_store: &ChannelStore) -> Result<(), RecordError> {
+        self.targets = self
+            .channels
+            .channels
+            .iter()
+            .map(|channel| {
+                Ok(RecordTarget {
+                    accessor_id: channel_store
+                        .find_record_channel(&channel.name, &channel.codec)?,
+                    codec: channel.codec,
+                    sequence: None,
+                })
+            })
+            .collect::<Result<Vec<RecordTarget>, RecordError>>()?;
+
+        let mut header = Vec::new();
+        RECORD_MAGIC.encode(&mut header);
+        RECORD_VERSION.encode(&mut header);
+        (self.channels.channels.len() as u32).encode(&mut header);
+        for channel in self.channels.channels.iter() {
+            channel.name.encode(&mut header);
+            channel.codec.type_tag().encode(&mut header);
+        }
+        self.writer.write(&header)
+    }
+
+    /// Record the channels written during the tick.
+    pub(crate) fn record_tick(
+        &mut self,
+        tick: u64,
+        timestamp: Duration,
+        channel_store: &ChannelStore,
+    ) {
+        if self.error.is_some() {
+            return;
+        }
+
+        self.frame.clear();
+        tick.encode(&mut self.frame);
+        (timestamp.as_nanos() as u64).encode(&mut self.frame);
+        let count_offset = self.frame.len();
+        0u32.encode(&mut self.frame);
+
+        let mut count = 0u32;
+        for (channel_idx, target) in self.targets.iter_mut().enumerate() {
+            let sequence = channel_store.channel_write_sequence(target.accessor_id);
+            if target.sequence == Some(sequence) {
+                continue;
+            }
+            target.sequence = Some(sequence);
+            count += 1;
+
+            (channel_idx as u32).encode(&mut self.frame);
+            let len_offset = self.frame.len();
+            0u32.encode(&mut self.frame);
+            channel_store.encode_record(target.accessor_id, &target.codec, &mut self.frame);
+            let len = (self.frame.len() - len_offset - 4) as u32;
+            self.frame[len_offset..len_offset + 4].copy_from_slice(&len.to_le_bytes());
+        }
+        self.frame[count_offset..count_offset + 4].copy_from_slice(&count.to_le_bytes());
+
+        if let Err(err) = self.writer.write(&self.frame) {
+            self.error = Some(err);
+        }
+    }
+}
+
+/// Name and type of a channel within a record log.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RecordLogChannel {
+    pub name: String,
+    /// Type tag of the channel values, see Recordable::type_tag().
+    pub type_tag: String,
+}
+
+/// Encoded value of a channel recorded during a tick.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RecordEntry {
+    /// Index of the channel within the log channels.
+    pub channel: usize,
+    pub value: Vec<u8>,
+}
+
+/// Channels recorded during a tick.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RecordFrame {
+    pub tick: u64,
+    /// Runner clock timestamp of the tick.
+    pub timestamp: Duration,
+    pub entries: Vec<RecordEntry>,
+}
+
+/// Parsed record log.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RecordLog {
+    channels: Vec<RecordLogChannel>,
+    frames: Vec<RecordFrame>,
+}
+
+impl RecordLog {
+    /// Parse a record log written by a recorder.
+    ///
+    /// ### Argument
+    /// * 'bytes' - Bytes of the log.
+    ///
+    pub fn parse(mut bytes: &[u8]) -> Result<Self, RecordError> {
+        let input = &mut bytes;
+        if <[u8; 6]>::decode(input) != Some(RECORD_MAGIC)
+            || u8::decode(input) != Some(RECORD_VERSION)
+        {
+            return Err(RecordError::InvalidLog);
+        }
+
+        let channels = Vec::<(String, String)>::decode(input)
+            .ok_or(RecordError::InvalidLog)?
+            .into_iter()
+            .map(|(name, type_tag)| RecordLogChannel { name, type_tag })
+            .collect::<Vec<RecordLogChannel>>();
+
+        let mut frames = Vec::new();
+        while !input.is_empty() {
+            let (tick, timestamp_nanos, entries) =
+                <(u64, u64, Vec<(u32, Vec<u8>)>)>::decode(input).ok_or(RecordError::InvalidLog)?;
+            let entries = entries
+                .into_iter()
+                .map(|(channel, value)| RecordEntry {
+                    channel: channel as usize,
+                    value,
+                })
+                .collect::<Vec<RecordEntry>>();
+            if entries.iter().any(|entry| entry.channel >= channels.len()) {
+                return Err(RecordError::InvalidLog);
+            }
+            frames.push(RecordFrame {
+                tick,
+                timestamp: Duration::from_nanos(timestamp_nanos),
+                entries,
+            });
+        }
+
+        Ok(Self { channels, frames })
+    }
+
+    /// Recorded channels, in selection order.
+    pub fn channels(&self) -> &[RecordLogChannel] {
+        &self.channels
+    }
+
+    /// Recorded ticks, in dispatch order.
+    pub fn frames(&self) -> &[RecordFrame] {
+        &self.frames
+    }
+
+    /// Index of the channel with the given name within the log channels.
+    fn channel_idx(&self, name: &str) -> Option<usize> {
+        self.channels
+            .iter()
+            .position(|channel| channel.name == name)
+    }
+
+    /// Find the first tick where a channel recorded within both logs holds a different value.
+    /// Channels keep their last recorded value on ticks where they were not recorded. Only ticks
+    /// covered by both logs are compared.
+    ///
+    /// ### Argument
+    /// * 'live' - Log recorded from the live run.
+    ///
+    pub fn first_divergence(&self, live: &RecordLog) -> Option<Divergence> {
+        let pairs: Vec<(usize, usize)> = self
+            .channels
+            .iter()
+            .enumerate()
+            .filter_map(|(recorded_idx, channel)| {
+                live.channel_idx(&channel.name)
+                    .map(|live_idx| (recorded_idx, live_idx))
+            })
+            .collect();
+
+        let mut recorded_values: Vec<Option<&[u8]>> = vec![None; self.channels.len()];
+        let mut live_values: Vec<Option<&[u8]>> = vec![None; live.channels.len()];
+
+        let (mut recorded_frames, mut live_frames) =
+            (self.frames.iter().peekable(), live.frames.iter().peekable());
+        let (mut recorded_started, mut live_started) = (false, false);
+        while let (Some(recorded_frame), Some(live_frame)) =
+            (recorded_frames.peek(), live_frames.peek())
+        {
+            let tick = recorded_frame.tick.min(live_frame.tick);
+            if recorded_frame.tick == tick {
+                apply_frame(&mut recorded_values, recorded_frame);
+                recorded_frames.next();
+                recorded_started = true;
+            }
+            if live_frame.tick == tick {
+                apply_frame(&mut live_values, live_frame);
+                live_frames.next();
+                live_started = true;
+            }
+            if !(recorded_started && live_started) {
+                continue;
+            }
+
+            if let Some((recorded_idx, _)) = pairs.iter().find(|(recorded_idx, live_idx)| {
+                recorded_values[*recorded_idx] != live_values[*live_idx]
+            }) {
+                return Some(Divergence {
+                    tick,
+                    channel: self.channels[*recorded_idx].name.clone(),
+                });
+            }
+        }
+
+        None
+    }
+}
+
+/// Update the last recorded value of each channel with the entries of a frame.
+fn apply_frame<'a>(values: &mut [Option<&'a [u8]>], frame: &'a RecordFrame) {
+    for entry in frame.entries.iter() {
+        values[entry.channel] = Some(&entry.value);
+    }
+}
+
+/// First difference between a recorded and a live log found by RecordLog::first_divergence().
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Divergence {
+    pub tick: u64,
+    /// Name of the first diverging channel, in selection order of the recorded log.
+    pub channel: String,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Channel [{}] diverged from the recording at tick [{}].",
+            self.channel, self.tick
+        )
+    }
+}
+
+/// Recorded outputs substituted for a live component.
+pub(crate) struct Replay {
+    pub component_id: usize,
+    /// Accessor id and codec of each replayed channel, indexed by log channel.
+    targets: Vec<Option<(usize, RecordCodec)>>,
+    frames: Vec<RecordFrame>,
+    /// Index of the first frame which was not replayed yet.
+    next_frame: usize,
+}
+
+impl Replay {
+    /// Resolve the channels selected for replay within the log and the store, and validate
+    /// every recorded value of these channels so that applying frames cannot fail.
+    ///
+    /// ### Arguments
+    /// * 'component_id' - Id of the replayed component, owning every selected channel.
+    /// * 'channels' - Channels to be replayed.
+    /// * 'log' - Log recorded from the component.
+    /// * 'channel_store' - Channel store of the runner.
+    ///
+    pub(crate) fn new(
+        component_id: usize,
+        channels: &RecordChannels,
+        log: RecordLog,
+        channel_store: &ChannelStore,
+    ) -> Result<Self, RecordError> {
+        let mut targets = vec![None; log.channels.len()];
+        for channel in channels.channels.iter() {
+            let log_idx =
+                log.channel_idx(&channel.name)
+                    .ok_or_else(|| RecordError::NotRecorded {
+                        name: channel.name.clone(),
+                    })?;
+            let type_tag = channel.codec.type_tag();
+            if log.channels[log_idx].type_tag != type_tag {
+                return Err(RecordError::TypeMismatch {
+                    name: channel.name.clone(),
+                    expected: type_tag,
+                    actual: log.channels[log_idx].type_tag.clone(),
+                });
+            }
+
+            let accessor_id = channel_store.find_record_channel(&channel.name, &channel.codec)?;
+            if channel_store.channel_owner(accessor_id) != Some(component_id) {
+                return Err(RecordError::NotOwner {
+                    name: channel.name.clone(),
+                    component_id,
+                });
+            }
+            targets[log_idx] = Some((accessor_id, channel.codec));
+        }
+
+        for frame in log.frames.iter() {
+            for entry in frame.entries.iter() {
+                if let Some((_, codec)) = targets[entry.channel]
+                    && !codec.validate(&entry.value)
+                {
+                    return Err(RecordError::MalformedValue {
+                        name: log.channels[entry.channel].name.clone(),
+                        tick: frame.tick,
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            component_id,
+            targets,
+            frames: log.frames,
+            next_frame: 0,
+        })
+    }
+
+    /// Write the values recorded during the given tick into the replayed channels. Channels keep
+    /// their value on ticks without a recorded value.
+    pub(crate) fn apply(&mut self, tick: u64, channel_store: &ChannelStore) {
+        while let Some(frame) = self.frames.get(self.next_frame) {
+            if frame.tick > tick {
+                break;
+            }
+            self.next_frame += 1;
+            if frame.tick < tick {
+                continue;
+            }
+
+            for entry in frame.entries.iter() {
+                let Some((accessor_id, codec)) = self.targets[entry.channel] else {
+                    continue;
+                };
+                // Values were validated when the replay was created, decoding cannot fail.
+                channel_store.decode_record(accessor_id, &codec, &entry.value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use alloc::{boxed::Box, rc::Rc, string::ToString, vec::Vec};
+    use core::cell::RefCell;
+
+    use crate::{
+        channel::{
+            error::ChannelError,
+            store::{
+                ChannelReadBuilder, ChannelStore, ChannelWriteBuilder, OBSERVER_ID, RegViewProducer,
+            },
+            token::{ChannelOwnerToken, ChannelReaderToken},
+        },
+        system::{
+            component::{Component, DispatchContext},
+            runner::Runner,
+        },
+    };
+
+    use super::{
+        Divergence, RecordBuffer, RecordChannels, RecordError, RecordLog, RecordWriter, Recorder,
+    };
+
+    /// Source writing a value derived from the tick, shifted by the offset. The value is only
+    /// written on even ticks.
+    struct TestSource(ChannelOwnerToken<u32>, u32);
+    impl Component for TestSource {
+        fn register_write_channels(
+            &mut self,
+            channel_builder: ChannelWriteBuilder,
+            channel_store: &mut ChannelStore,
+        ) {
+            self.0 =
+                channel_builder.register_write_channel(channel_store, "test.source".to_string(), 0);
+        }
+
+        fn dispatch(&mut self, channel_store: &ChannelStore, context: &DispatchContext) {
+            if context.tick().is_multiple_of(2) {
+                channel_store
+                    .grab(&self.0)
+                    .set(context.tick() as u32 * 10 + self.1);
+            }
+        }
+    }
+
+    type FilterOutputs = Rc<RefCell<Vec<(u32, bool)>>>;
+
+    struct TestFilter(
+        ChannelReaderToken<u32>,
+        ChannelOwnerToken<(u32, bool)>,
+        FilterOutputs,
+    );
+    impl Component for TestFilter {
+        fn register_write_channels(
+            &mut self,
+            channel_builder: ChannelWriteBuilder,
+            channel_store: &mut ChannelStore,
+        ) {
+            self.1 = channel_builder.register_write_channel(
+                channel_store,
+                "test.filtered".to_string(),
+                (0, false),
+            );
+        }
+
+        fn register_read_channels(
+            &mut self,
+            channel_builder: ChannelReadBuilder,
+            channel_store: &mut ChannelStore,
+        ) {
+            self.0 = channel_builder.bind_read_channel(channel_store, "test.source".to_string());
+        }
+
+        fn dispatch(&mut self, channel_store: &ChannelStore, _context: &DispatchContext) {
+            let source = channel_store.grab(&self.0).get();
+            let filtered = (source * 2, source > 20);
+            channel_store.grab(&self.1).set(filtered);
+            self.2.borrow_mut().push(filtered);
+        }
+    }
+
+    fn test_channels() -> RecordChannels {
+        let mut channels = RecordChannels::default();
+        channels
+            .add::<u32>("test.source")
+            .add::<(u32, bool)>("test.filtered");
+        channels
+    }
+
+    /// Build a runner whose source is shifted by the offset, returning the runner and the
+    /// observed outputs of the filter.
+    fn test_runner(offset: u32) -> (Runner, FilterOutputs) {
+        let observed = Rc::new(RefCell::new(Vec::new()));
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestSource(ChannelOwnerToken::default(), offset)));
+        runner.add_component(Box::new(TestFilter(
+            ChannelReaderToken::default(),
+            ChannelOwnerToken::default(),
+            observed.clone(),
+        )));
+        runner.initialize();
+        (runner, observed)
+    }
+
+    /// Dispatch the runner for five ticks while recording the test channels.
+    fn record_run(runner: &mut Runner) -> RecordLog {
+        let buffer = RecordBuffer::default();
+        runner
+            .set_recorder(Recorder::new(test_channels(), Box::new(buffer.clone())))
+            .unwrap();
+        for _ in 0..5 {
+            runner.dispatch_components();
+        }
+        buffer.parse().unwrap()
+    }
+
+    #[test]
+    fn test_record_log() {
+        let (mut runner, _) = test_runner(0);
+        let log = record_run(&mut runner);
+
+        assert_eq!(
+            log.channels()
+                .iter()
+                .map(|channel| channel.name.as_str())
+                .collect::<Vec<&str>>(),
+            ["test.source", "test.filtered"]
+        );
+        assert_eq!(log.frames().len(), 5);
+        // The source is only written on even ticks.
+        assert_eq!(
+            log.frames()
+                .iter()
+                .map(|frame| frame.entries.len())
+                .collect::<Vec<usize>>(),
+            [2, 1, 2, 1, 2]
+        );
+        assert_eq!(log.channels()[1].type_tag, "(u32, bool)");
+        assert_eq!(log.frames()[4].entries[0].value, 40u32.to_le_bytes());
+        assert_eq!(log.first_divergence(&log), None);
+    }
+
+    /// Writer whose flush always fails.
+    struct TestFailingFlush;
+    impl RecordWriter for TestFailingFlush {
+        fn write(&mut self, _bytes: &[u8]) -> Result<(), RecordError> {
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), RecordError> {
+            Err(RecordError::Write {
+                message: "test failure".to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_recorder_finish() {
+        let (mut runner, _) = test_runner(0);
+        runner
+            .set_recorder(Recorder::new(test_channels(), Box::new(TestFailingFlush)))
+            .unwrap();
+        runner.dispatch_components();
+        let failure = Err(RecordError::Write {
+            message: "test failure".to_string(),
+        });
+        assert_eq!(runner.finish_recorder(), failure);
+        assert!(runner.recorder().is_none());
+        assert_eq!(runner.finish_recorder(), Ok(()));
+
+        let mut recorder = Recorder::new(test_channels(), Box::new(RecordBuffer::default()));
+        assert_eq!(recorder.finish(), Ok(()));
+    }
+
+    #[test]
+    fn test_replay() {
+        let (mut recorded_runner, recorded_outputs) = test_runner(0);
+        let recorded = record_run(&mut recorded_runner);
+
+        // The live source is faulty, replaying the recorded source restores the filter outputs.
+        let (mut live_runner, live_outputs) = test_runner(5);
+        let mut source_channels = RecordChannels::default();
+        source_channels.add::<u32>("test.source");
+        live_runner
+            .replay_component(0, &source_channels, recorded.clone())
+            .unwrap();
+        let replayed = record_run(&mut live_runner);
+        assert_eq!(*live_outputs.borrow(), *recorded_outputs.borrow());
+        assert_eq!(recorded.first_divergence(&replayed), None);
+
+        let (mut faulty_runner, _) = test_runner(5);
+        faulty_runner.dispatch_components();
+        faulty_runner.dispatch_components();
+        let faulty = record_run(&mut faulty_runner);
+        // The faulty log starts at tick 2, the first source value differs.
+        assert_eq!(
+            recorded.first_divergence(&faulty),
+            Some(Divergence {
+                tick: 2,
+                channel: "test.source".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_replay_errors() {
+        let (mut runner, _) = test_runner(0);
+        let recorded = record_run(&mut runner);
+
+        let mut channels = RecordChannels::default();
+        channels.add::<u32>("test.source");
+        assert_eq!(
+            runner.replay_component(1, &channels, recorded.clone()),
+            Err(RecordError::NotOwner {
+                name: "test.source".to_string(),
+                component_id: 1
+            })
+        );
+
+        let mut channels = RecordChannels::default();
+        channels.add::<u64>("test.source");
+        assert_eq!(
+            runner.replay_component(0, &channels, recorded.clone()),
+            Err(RecordError::TypeMismatch {
+                name: "test.source".to_string(),
+                expected: "u64".to_string(),
+                actual: "u32".to_string()
+            })
+        );
+
+        // Corrupt values are rejected before the replay is attached.
+        let mut channels = RecordChannels::default();
+        channels.add::<u32>("test.source");
+        let mut corrupted = recorded.clone();
+        corrupted.frames[2]
+            .entries
+            .iter_mut()
+            .find(|entry| entry.channel == 0)
+            .unwrap()
+            .value
+            .pop();
+        assert_eq!(
+            runner.replay_component(0, &channels, corrupted),
+            Err(RecordError::MalformedValue {
+                name: "test.source".to_string(),
+                tick: 2
+            })
+        );
+        runner.dispatch_components();
+
+        let mut channels = RecordChannels::default();
+        channels.add::<u32>("test.missing");
+        assert_eq!(
+            runner.replay_component(0, &channels, recorded),
+            Err(RecordError::NotRecorded {
+                name: "test.missing".to_string()
+            })
+        );
+
+        let err = runner
+            .set_recorder(Recorder::new(channels, Box::new(Vec::new())))
+            .unwrap_err();
+        let channel_err = ChannelError::NotFound {
+            name: "test.missing".to_string(),
+            requester: OBSERVER_ID,
+        };
+        assert_eq!(err, RecordError::Channel(channel_err.clone()));
+        assert_eq!(
+            core::error::Error::source(&err).map(|source| source.to_string()),
+            Some(channel_err.to_string())
+        );
+        assert_eq!(RecordLog::parse(b"CMTREC"), Err(RecordError::InvalidLog));
+    }
+}
@@ -0,0 +1,212 @@
+use core::fmt;
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{channel::error::ChannelError, system::lint::Diagnostic};
+
+/// Channel dependency between two components which forms part of an execution cycle.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CycleEdge {
+    /// Id of the component which owns the channel.
+    pub producer: usize,
+    /// Name of the component which owns the channel.
+    pub producer_name: String,
+    /// Id of the component which reads the channel.
+    pub consumer: usize,
+    /// Name of the component which reads the channel.
+    pub consumer_name: String,
+    /// Name of the channel creating the dependency.
+    pub channel: String,
+    /// How the consumer depends on the channel.
+    pub kind: CycleEdgeKind,
+}
+
+/// Kind of channel dependency forming a cycle edge.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CycleEdgeKind {
+    /// The consumer reads the value written during the current tick.
+    Current,
+    /// The consumer registered the channel as dangling and the producer acquired its ownership.
+    DanglingOwnership,
+}
+
+/// Dependency cycle between components found while calculating the execution order.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ExecutionCycle {
+    /// Channel dependencies forming the cycle in data flow order. The consumer of the last
+    /// edge is the producer of the first edge.
+    pub edges: Vec<CycleEdge>,
+}
+
+impl ExecutionCycle {
+    /// Ids of the components forming the cycle in data flow order. The first and last
+    /// entries refer to the same component.
+    pub fn components(&self) -> Vec<usize> {
+        let mut components: Vec<usize> = self.edges.iter().map(|edge| edge.producer).collect();
+        if let Some(first) = components.first().copied() {
+            components.push(first);
+        }
+
+        components
+    }
+
+    /// Edge suggested to be bound as a behind channel (bind_read_behind_channel()) in order to
+    /// break the cycle. This is the last current read edge of the cycle, preferring the edge which
+    /// closes the loop back to the first component visited while calculating the execution order.
+    /// Returns None if no edge of the cycle is a current read, such as a cycle formed by dangling
+    /// channel ownership only.
+    pub fn suggested_behind_edge(&self) -> Option<&CycleEdge> {
+        self.edges
+            .iter()
+            .rev()
+            .find(|edge| edge.kind == CycleEdgeKind::Current)
+    }
+}
+
+impl fmt::Display for ExecutionCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cycle detected in execution order: ")?;
+        for edge in self.edges.iter() {
+            write!(f, "{} --[{}]--> ", edge.producer_name, edge.channel)?;
+        }
+        if let Some(first_edge) = self.edges.first() {
+            write!(f, "{}.", first_edge.producer_name)?;
+        }
+        match self.suggested_behind_edge() {
+            Some(suggested_edge) => write!(
+                f,
+                " Consider binding channel [{}] in component {} with bind_read_behind_channel() to break the cycle.",
+                suggested_edge.channel, suggested_edge.consumer_name
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Error which prevents the runner from completing initialization.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum InitError {
+    /// A channel registration made by a component failed.
+    Channel(ChannelError),
+    /// Components depend on each other's channels in a loop.
+    Cycle(ExecutionCycle),
+    /// A lint rule with deny severity found a wiring problem.
+    Lint(Diagnostic),
+}
+
+impl fmt::Display for InitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InitError::Channel(err) => err.fmt(f),
+            InitError::Cycle(cycle) => cycle.fmt(f),
+            InitError::Lint(diagnostic) => diagnostic.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for InitError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            InitError::Channel(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Condition found during initialization which does not prevent the runner from executing.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum InitWarning {
+    /// A lint rule with warn severity found a wiring problem.
+    Lint(Diagnostic),
+}
+
+impl fmt::Display for InitWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InitWarning::Lint(diagnostic) => diagnostic.fmt(f),
+        }
+    }
+}
+
+/// Collection of every error and warning found while initializing a runner.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct InitReport {
+    pub errors: Vec<InitError>,
+    pub warnings: Vec<InitWarning>,
+}
+
+impl InitReport {
+    /// Returns true if no errors were found during initialization.
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl fmt::Display for InitReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Initialization finished with {} error(s) and {} warning(s).",
+            self.errors.len(),
+            self.warnings.len()
+        )?;
+        for err in self.errors.iter() {
+            writeln!(f, "error: {}", err)?;
+        }
+        for warning in self.warnings.iter() {
+            writeln!(f, "warning: {}", warning)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use alloc::{string::ToString, vec::Vec};
+
+    use super::{CycleEdge, CycleEdgeKind, ExecutionCycle};
+
+    fn test_edge(producer: usize, consumer: usize, kind: CycleEdgeKind) -> CycleEdge {
+        CycleEdge {
+            producer,
+            producer_name: ["Estimator", "Controller"][producer].to_string(),
+            consumer,
+            consumer_name: ["Estimator", "Controller"][consumer].to_string(),
+            channel: ["test.estimate", "test.command"][producer].to_string(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_execution_cycle_suggestion() {
+        let cycle = ExecutionCycle {
+            edges: Vec::from([
+                test_edge(0, 1, CycleEdgeKind::Current),
+                test_edge(1, 0, CycleEdgeKind::DanglingOwnership),
+            ]),
+        };
+        // Dangling ownership edges cannot be bound as behind channels.
+        assert_eq!(cycle.suggested_behind_edge(), Some(&cycle.edges[0]));
+        assert_eq!(
+            cycle.to_string(),
+            "Cycle detected in execution order: Estimator --[test.estimate]--> Controller --[test.command]--> Estimator. Consider binding channel [test.estimate] in component Controller with bind_read_behind_channel() to break the cycle."
+        );
+
+        let cycle = ExecutionCycle {
+            edges: Vec::from([
+                test_edge(0, 1, CycleEdgeKind::DanglingOwnership),
+                test_edge(1, 0, CycleEdgeKind::DanglingOwnership),
+            ]),
+        };
+        assert_eq!(cycle.suggested_behind_edge(), None);
+        assert_eq!(
+            cycle.to_string(),
+            "Cycle detected in execution order: Estimator --[test.estimate]--> Controller --[test.command]--> Estimator."
+        );
+
+        let cycle = ExecutionCycle { edges: Vec::new() };
+        assert_eq!(cycle.suggested_behind_edge(), None);
+        assert_eq!(cycle.to_string(), "Cycle detected in execution order: ");
+    }
+}
@@ -1,13 +1,118 @@
-use alloc::{boxed::Box, vec::Vec};
+use core::time::Duration;
 
+#[cfg(feature = "concurrent")]
+use alloc::sync::Arc;
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+use crate::{
+    channel::{
+        snapshot::{SnapshotError, StoreSnapshot},
+        store::{
+            ChannelDanglingBuilder, ChannelReadBuilder, ChannelStore, ChannelWriteBuilder,
+            ReaderKind,
+        },
+    },
+    system::{
+        event_log::EventLog,
+        graph::{ExecutionGraph, GraphEdge, GraphEdgeKind, GraphFormat, GraphNode},
+        lint::{Diagnostic, DiagnosticSink, LintConfig, LintLevel, LintRule},
+        order::{NodeDependency, NodeGraph, NodeOrderCalc},
+        record::{RecordChannels, RecordError, RecordLog, Recorder, Replay},
+        report::{CycleEdge, CycleEdgeKind, ExecutionCycle, InitError, InitReport, InitWarning},
+        schedule::RateSchedule,
+        telemetry::{TelemetryError, TelemetrySink},
+        timing::{Overrun, OverrunCallback, TimingMonitor, TimingStats},
+        write_policy::{WriteMonitor, WriteViolation, WriteViolationCallback},
+    },
+};
+
+#[cfg(feature = "concurrent")]
+use crate::channel::store::SharedChannelStore;
+#[cfg(feature = "std")]
 use crate::{
-    channel::store::{
-        ChannelDanglingBuilder, ChannelReadBuilder, ChannelStore, ChannelWriteBuilder,
+    channel::store::SyncChannelStore,
+    system::{component::ParallelComponent, pool::WorkerPool},
+};
+
+use super::{
+    clock::Clock,
+    component::{
+        Component, ComponentBox, ComponentHolder, ComponentInfo, ComponentState, DispatchContext,
     },
-    system::order::NodeOrderCalc,
 };
 
-use super::component::{Component, ComponentHolder};
+#[cfg(feature = "concurrent")]
+const CHANNEL_STORE_SHARED_MESSAGE: &str = "Channel store cannot be modified while shared.";
+
+/// Channel store held by a runner. With the concurrent feature the store is shared through an Arc
+/// so that it may be observed from other threads once initialized, it is only modified during
+/// initialization while it is not shared.
+#[cfg(feature = "concurrent")]
+type RunnerChannelStore = Arc<ChannelStore>;
+
+/// Channel store held by a runner.
+#[cfg(not(feature = "concurrent"))]
+type RunnerChannelStore = ChannelStore;
+
+/// Mutable access to the channel store of a runner, panics while the store is shared.
+#[cfg(feature = "concurrent")]
+fn store_mut(channel_store: &mut RunnerChannelStore) -> &mut ChannelStore {
+    Arc::get_mut(channel_store).expect(CHANNEL_STORE_SHARED_MESSAGE)
+}
+
+/// Mutable access to the channel store of a runner.
+#[cfg(not(feature = "concurrent"))]
+fn store_mut(channel_store: &mut RunnerChannelStore) -> &mut ChannelStore {
+    channel_store
+}
+
+/// Current time of the runner clock, zero when no clock is set.
+fn sample_clock(clock: Option<&dyn Clock>) -> Duration {
+    clock.map_or(Duration::ZERO, |clock| clock.now())
+}
+
+/// Dispatch a component, or write its recorded outputs instead when it is replayed.
+fn dispatch_or_replay(
+    component_holder: &mut ComponentHolder,
+    replays: &mut [Replay],
+    channel_store: &ChannelStore,
+    context: &DispatchContext,
+) {
+    match replays
+        .iter_mut()
+        .find(|replay| replay.component_id == component_holder.id)
+    {
+        Some(replay) => replay.apply(context.tick(), channel_store),
+        None => component_holder.component.dispatch(channel_store, context),
+    }
+}
+
+/// Captured state of a component within a runner snapshot.
+struct ComponentSnapshot {
+    id: usize,
+    last_dispatch: Option<Duration>,
+    state: Option<ComponentState>,
+}
+
+/// Opaque capture of the channel store, tick counter and component states of an initialized
+/// runner, produced by Runner::snapshot().
+pub struct RunnerSnapshot {
+    store: StoreSnapshot,
+    tick: u64,
+    components: Vec<ComponentSnapshot>,
+}
+
+impl RunnerSnapshot {
+    /// Captured channel store state.
+    pub fn store(&self) -> &StoreSnapshot {
+        &self.store
+    }
+
+    /// Number of completed ticks at the time of the snapshot.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+}
 
 /// Runner used to hold all components and channels which intend to be executed together.
 ///
@@ -18,7 +123,11 @@ use super::component::{Component, ComponentHolder};
 /// struct TestComponent;
 ///
 /// impl Component for TestComponent {
-///     fn dispatch(&mut self, channel_store: &comet::channel::store::ChannelStore) {
+///     fn dispatch(
+///         &mut self,
+///         channel_store: &comet::channel::store::ChannelStore,
+///         context: &comet::system::component::DispatchContext,
+///     ) {
 ///     }
 /// }
 ///
@@ -35,9 +144,37 @@ use super::component::{Component, ComponentHolder};
 #[derive(Default)]
 pub struct Runner {
     components: Vec<ComponentHolder>,
-    channel_store: ChannelStore,
+    channel_store: RunnerChannelStore,
     component_counter: usize,
+    init_started: bool,
     init_complete: bool,
+    /// Number of completed dispatch_components() calls.
+    tick: u64,
+    /// Flags indexed by component id marking components dispatched during the current tick.
+    dispatched_flags: Vec<bool>,
+    /// Time source used to timestamp each tick, timestamps are zero when no clock is set.
+    clock: Option<Box<dyn Clock>>,
+    /// Execution time measurement configuration and results.
+    timing: TimingMonitor,
+    /// Component dependency graph, retained after initialization for graph export.
+    node_graph: NodeGraph,
+    /// Severity of each lint rule checked during initialization or after each tick.
+    lint_config: LintConfig,
+    /// Receiver of lint diagnostics, diagnostics are only part of the init report when not set.
+    diagnostic_sink: Option<Box<dyn DiagnosticSink>>,
+    /// Diagnostics of the lint rules checked after each tick while no diagnostic sink is set.
+    tick_diagnostic_log: EventLog<Diagnostic>,
+    /// Write policy enforcement configuration and results.
+    write_monitor: WriteMonitor,
+    /// Recorder writing selected channels into a record log after each tick.
+    recorder: Option<Recorder>,
+    /// Components whose outputs are substituted with recorded values.
+    replays: Vec<Replay>,
+    /// Sinks receiving channel values after each tick.
+    telemetry_sinks: Vec<Box<dyn TelemetrySink>>,
+    /// Worker threads of dispatch_components_parallel(), spawned on first use.
+    #[cfg(feature = "std")]
+    worker_pool: Option<WorkerPool>,
 }
 
 impl Runner {
@@ -46,202 +183,2534 @@ impl Runner {
     /// ### Argument
     /// * 'component' - Component to be added into the runner object.
     ///
-    pub fn add_component(&mut self, component: Box<dyn Component>) {
+    /// ### Returns
+    /// Id of the component within the runner.
+    ///
+    pub fn add_component(&mut self, component: Box<dyn Component>) -> usize {
+        self.add_component_with_rate(component, 1, 0)
+    }
+
+    /// Include a component into the runner which is dispatched once every 'period_ticks' calls to
+    /// dispatch_components(). Components which are not due on a given tick are skipped while the
+    /// remaining components keep their topological order.
+    ///
+    /// Channels always hold the latest value written, as such a component running slower than the
+    /// owner of a channel observes the most recent value at the time of its dispatch. Behind
+    /// channels are updated at the rate of their reader, the value returned is the channel value
+    /// at the end of the reader's previous dispatch tick.
+    ///
+    /// ### Arguments
+    /// * 'component' - Component to be added into the runner object.
+    /// * 'period_ticks' - Number of ticks between dispatches of the component, must be non-zero.
+    /// * 'phase_offset' - Tick within the period on which the component is dispatched, must be less than 'period_ticks'.
+    ///
+    /// ### Returns
+    /// Id of the component within the runner.
+    ///
+    pub fn add_component_with_rate(
+        &mut self,
+        component: Box<dyn Component>,
+        period_ticks: u64,
+        phase_offset: u64,
+    ) -> usize {
+        self.push_component(
+            ComponentBox::Local(component),
+            RateSchedule::new(period_ticks, phase_offset),
+        )
+    }
+
+    /// Include a component which may be dispatched on a worker thread by
+    /// dispatch_components_parallel(). Components added this way are dispatched through
+    /// ParallelComponent::dispatch_parallel() by both dispatch_components() and
+    /// dispatch_components_parallel().
+    ///
+    /// ### Argument
+    /// * 'component' - Component to be added into the runner object.
+    ///
+    /// ### Returns
+    /// Id of the component within the runner.
+    ///
+    #[cfg(feature = "std")]
+    pub fn add_parallel_component(&mut self, component: Box<dyn ParallelComponent>) -> usize {
+        self.add_parallel_component_with_rate(component, 1, 0)
+    }
+
+    /// Include a component which may be dispatched on a worker thread, dispatched once every
+    /// 'period_ticks' ticks. See add_component_with_rate() for the rate semantics.
+    ///
+    /// ### Arguments
+    /// * 'component' - Component to be added into the runner object.
+    /// * 'period_ticks' - Number of ticks between dispatches of the component, must be non-zero.
+    /// * 'phase_offset' - Tick within the period on which the component is dispatched, must be less than 'period_ticks'.
+    ///
+    /// ### Returns
+    /// Id of the component within the runner.
+    ///
+    #[cfg(feature = "std")]
+    pub fn add_parallel_component_with_rate(
+        &mut self,
+        component: Box<dyn ParallelComponent>,
+        period_ticks: u64,
+        phase_offset: u64,
+    ) -> usize {
+        self.push_component(
+            ComponentBox::Parallel(component),
+            RateSchedule::new(period_ticks, phase_offset),
+        )
+    }
+
+    fn push_component(&mut self, component: ComponentBox, schedule: RateSchedule) -> usize {
         assert!(!self.init_complete);
+        let id = self.component_counter;
         self.components.push(ComponentHolder {
-            component,
-            id: self.component_counter,
+            schedule,
+            ..ComponentHolder::new(id, component)
         });
         self.component_counter += 1;
+
+        id
     }
 
-    /// Initialize the component runner to prepare for runtime. This method must be called
-    /// exactly once after all components have been added and before the first dispatch_components() call.
-    pub fn initialize(&mut self) {
-        assert!(!self.init_complete);
+    /// Set the time source used to timestamp each call to dispatch_components(). The clock is
+    /// sampled once at the start of each tick and the timestamp is provided to every component
+    /// through its DispatchContext.
+    ///
+    /// ### Argument
+    /// * 'clock' - Clock to be used by the runner.
+    ///
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = Some(clock);
+    }
 
-        // Register dangling channels before write channels as dangling channels may be owned during write channel register.
-        for component_holder in self.components.iter_mut() {
-            let write_builder = ChannelDanglingBuilder::new(component_holder.id);
-            component_holder
-                .component
-                .register_dangling_channels(write_builder, &mut self.channel_store);
-        }
+    /// Enable or disable measurement of component dispatch execution time. When enabled the clock
+    /// is sampled around every component dispatch, durations are zero when no clock is set.
+    /// Budgets and the tick deadline are only checked while timing is enabled.
+    ///
+    /// ### Argument
+    /// * 'enabled' - True to measure dispatch execution time.
+    ///
+    pub fn set_timing_enabled(&mut self, enabled: bool) {
+        self.timing.enabled = enabled;
+    }
 
-        // Register component write channels with the channel store. Write channels must be registered before
-        // read channels.
-        for component_holder in self.components.iter_mut() {
-            let write_builder = ChannelWriteBuilder::new(component_holder.id);
-            component_holder
-                .component
-                .register_write_channels(write_builder, &mut self.channel_store);
-        }
+    /// Set the dispatch execution time budget of a component. A dispatch exceeding the budget is
+    /// reported as an overrun.
+    ///
+    /// ### Arguments
+    /// * 'component_id' - Id of the component returned by add_component().
+    /// * 'budget' - Maximum dispatch execution time of the component, None to remove the budget.
+    ///
+    pub fn set_component_budget(&mut self, component_id: usize, budget: Option<Duration>) {
+        self.component_holder_mut(component_id).budget = budget;
+    }
 
-        // Register component read channels with the channel store. Read channels are registered to existing channels,
-        // as such, read channels are registered after write channels.
-        for component_holder in self.components.iter_mut() {
-            let read_builder = ChannelReadBuilder::new(component_holder.id);
-            component_holder
-                .component
-                .register_read_channels(read_builder, &mut self.channel_store);
-        }
+    /// Set the deadline of a complete dispatch_components() call. A tick exceeding the deadline is
+    /// reported as an overrun.
+    ///
+    /// ### Argument
+    /// * 'deadline' - Maximum execution time of a tick, None to remove the deadline.
+    ///
+    pub fn set_tick_deadline(&mut self, deadline: Option<Duration>) {
+        self.timing.tick_deadline = deadline;
+    }
 
-        // Calculate and modify execution order of the inserted components to create an
-        // execution topological sequence.
-        let mut node_order_data = NodeOrderCalc::new(
-            self.channel_store.node_graph.take().unwrap(),
-            &self.components,
-        );
-        let ordering = node_order_data.calculate_topological_order();
-        self.modify_component_ordering(ordering);
+    /// Set the callback invoked for every overrun. While a callback is set overruns are no longer
+    /// stored in the overrun log.
+    ///
+    /// ### Argument
+    /// * 'callback' - Callback to be invoked with each overrun.
+    ///
+    pub fn set_overrun_callback(&mut self, callback: OverrunCallback) {
+        self.timing.overrun_callback = Some(callback);
+    }
 
-        self.init_complete = true;
+    /// Dispatch execution time statistics of a component.
+    ///
+    /// ### Argument
+    /// * 'component_id' - Id of the component returned by add_component().
+    ///
+    pub fn component_timing(&self, component_id: usize) -> &TimingStats {
+        &self
+            .components
+            .iter()
+            .find(|holder| holder.id == component_id)
+            .expect("Invalid component id.")
+            .timing
     }
 
-    /// Dispatch all included components into the runner object.
-    pub fn dispatch_components(&mut self) {
-        assert!(self.init_complete);
+    /// Execution time statistics of complete dispatch_components() calls.
+    pub fn tick_timing(&self) -> &TimingStats {
+        &self.timing.tick_stats
+    }
 
-        for component_holder in self.components.iter_mut() {
-            component_holder.component.dispatch(&self.channel_store);
-        }
+    /// Overruns recorded since the log was last drained, used when no overrun callback is set.
+    /// The log is bounded, the oldest overruns are dropped once it is full.
+    pub fn overruns(&self) -> &EventLog<Overrun> {
+        &self.timing.overrun_log
+    }
 
-        self.channel_store.update_active_behind_registers();
+    /// Remove and return every overrun recorded within the overrun log.
+    pub fn take_overruns(&mut self) -> Vec<Overrun> {
+        self.timing.overrun_log.take()
     }
 
-    /// Re-layout component ordering following provided topology.
-    fn modify_component_ordering(&mut self, ordering: Vec<usize>) {
-        for (insert_idx, component_id) in ordering.iter().enumerate() {
-            let component_idx = self
-                .components
-                .iter()
-                .enumerate()
-                .find(|(_, comp_holder)| comp_holder.id == *component_id)
-                .unwrap()
-                .0;
-            let component = self.components.remove(component_idx);
-            self.components.insert(insert_idx, component);
-        }
+    /// Set the maximum number of overruns held by the overrun log, defaults to
+    /// DEFAULT_EVENT_LOG_CAPACITY.
+    ///
+    /// ### Argument
+    /// * 'capacity' - Maximum number of overruns retained until the log is drained.
+    ///
+    pub fn set_overrun_log_capacity(&mut self, capacity: usize) {
+        self.timing.overrun_log.set_capacity(capacity);
     }
-}
 
-#[cfg(test)]
-mod unit_tests {
-    use alloc::{
-        boxed::Box,
-        string::{String, ToString},
-        vec::Vec,
-    };
+    /// Set the callback invoked for every write policy violation. While a callback is set
+    /// violations are no longer stored in the violation log.
+    ///
+    /// ### Argument
+    /// * 'callback' - Callback to be invoked with each write policy violation.
+    ///
+    pub fn set_write_violation_callback(&mut self, callback: WriteViolationCallback) {
+        self.write_monitor.violation_callback = Some(callback);
+    }
 
-    use crate::system::component::Component;
+    /// Write policy violations recorded since the log was last drained, used when no violation
+    /// callback is set. The log is bounded, the oldest violations are dropped once it is full.
+    pub fn write_violations(&self) -> &EventLog<WriteViolation> {
+        &self.write_monitor.violation_log
+    }
 
-    use super::Runner;
+    /// Remove and return every write policy violation recorded within the violation log.
+    pub fn take_write_violations(&mut self) -> Vec<WriteViolation> {
+        self.write_monitor.violation_log.take()
+    }
 
-    struct TestComponent();
-    impl Component for TestComponent {
-        fn dispatch(&mut self, _channel_store: &crate::channel::store::ChannelStore) {}
+    /// Set the maximum number of write policy violations held by the violation log, defaults to
+    /// DEFAULT_EVENT_LOG_CAPACITY.
+    ///
+    /// ### Argument
+    /// * 'capacity' - Maximum number of violations retained until the log is drained.
+    ///
+    pub fn set_write_violation_log_capacity(&mut self, capacity: usize) {
+        self.write_monitor.violation_log.set_capacity(capacity);
     }
 
-    struct TestProducerComponent(String, Option<String>);
-    impl Component for TestProducerComponent {
-        fn dispatch(&mut self, _channel_store: &crate::channel::store::ChannelStore) {}
+    /// Attach a recorder writing the selected channels into a record log at the end of every
+    /// tick, replacing any previously attached recorder. The log header is written immediately.
+    ///
+    /// ### Argument
+    /// * 'recorder' - Recorder to be attached.
+    ///
+    pub fn set_recorder(&mut self, mut recorder: Recorder) -> Result<(), RecordError> {
+        assert!(self.init_complete);
+        recorder.attach(&self.channel_store)?;
+        self.recorder = Some(recorder);
+        Ok(())
+    }
 
-        fn register_write_channels(
-            &mut self,
-            channel_builder: crate::channel::store::ChannelWriteBuilder,
-            channel_store: &mut crate::channel::store::ChannelStore,
-        ) {
-            channel_builder.register_write_channel(channel_store, self.0.clone(), 0u8);
+    /// Attached recorder, None if no recorder is attached.
+    pub fn recorder(&self) -> Option<&Recorder> {
+        self.recorder.as_ref()
+    }
+
+    /// Detach and return the attached recorder.
+    pub fn take_recorder(&mut self) -> Option<Recorder> {
+        self.recorder.take()
+    }
+
+    /// Flush and detach the attached recorder, returning the first error raised by its writer.
+    /// Succeeds if no recorder is attached.
+    pub fn finish_recorder(&mut self) -> Result<(), RecordError> {
+        match self.recorder.take() {
+            Some(mut recorder) => recorder.finish(),
+            None => Ok(()),
         }
+    }
 
-        fn register_read_channels(
-            &mut self,
-            channel_builder: crate::channel::store::ChannelReadBuilder,
-            channel_store: &mut crate::channel::store::ChannelStore,
-        ) {
-            if let Some(name) = self.1.as_ref() {
-                channel_builder.bind_read_channel::<u8>(channel_store, name.clone());
+    /// Attach a telemetry sink receiving channel values at the end of every tick.
+    ///
+    /// ### Argument
+    /// * 'sink' - Sink to be attached.
+    ///
+    pub fn add_telemetry_sink(
+        &mut self,
+        mut sink: Box<dyn TelemetrySink>,
+    ) -> Result<(), TelemetryError> {
+        assert!(self.init_complete);
+        sink.attach(&self.channel_store)?;
+        self.telemetry_sinks.push(sink);
+        Ok(())
+    }
+
+    /// Complete and detach every telemetry sink, returning the first error raised by a sink.
+    /// Every sink is completed even if an earlier sink fails.
+    pub fn finish_telemetry(&mut self) -> Result<(), TelemetryError> {
+        let mut result = Ok(());
+        for mut sink in core::mem::take(&mut self.telemetry_sinks) {
+            let finished = sink.finish();
+            if result.is_ok() {
+                result = finished;
             }
         }
+        result
     }
 
-    #[test]
-    fn test_runner_insertion() {
-        let mut runner = Runner::default();
-        runner.add_component(Box::new(TestComponent()));
-        runner.add_component(Box::new(TestComponent()));
-        runner.add_component(Box::new(TestComponent()));
-        runner.add_component(Box::new(TestComponent()));
-
-        assert_eq!(runner.components.len(), 4);
-        assert_eq!(runner.components.first().unwrap().id, 0);
-        assert_eq!(runner.components.get(1).unwrap().id, 1);
-        assert_eq!(runner.components.get(2).unwrap().id, 2);
-        assert_eq!(runner.components.last().unwrap().id, 3);
+    /// Substitute the recorded outputs of a component for the live component. On each tick the
+    /// component is due, the values recorded during the same tick are written into the selected
+    /// channels instead of dispatching the component, as such downstream components are
+    /// re-executed against the recording.
+    ///
+    /// ### Arguments
+    /// * 'component_id' - Id of the component to be replayed.
+    /// * 'channels' - Channels owned by the component to be replayed.
+    /// * 'log' - Log recorded with every selected channel.
+    ///
+    pub fn replay_component(
+        &mut self,
+        component_id: usize,
+        channels: &RecordChannels,
+        log: RecordLog,
+    ) -> Result<(), RecordError> {
+        assert!(self.init_complete);
+        let replay = Replay::new(component_id, channels, log, &self.channel_store)?;
+        self.replays
+            .retain(|replay| replay.component_id != component_id);
+        self.replays.push(replay);
+        Ok(())
     }
 
-    #[test]
-    #[should_panic(expected = "assertion failed: self.init_complete")]
-    fn test_init_not_complete() {
-        let mut runner = Runner::default();
-        runner.dispatch_components();
+    /// Resume dispatching a replayed component.
+    ///
+    /// ### Argument
+    /// * 'component_id' - Id of the replayed component.
+    ///
+    pub fn stop_replay(&mut self, component_id: usize) {
+        self.replays
+            .retain(|replay| replay.component_id != component_id);
     }
 
-    #[test]
-    fn test_empty_runner() {
-        let mut runner = Runner::default();
-        runner.initialize();
-        runner.dispatch_components();
+    fn component_name(&self, component_id: usize) -> &str {
+        self.components
+            .iter()
+            .find(|holder| holder.id == component_id)
+            .map(|holder| holder.name.as_str())
+            .expect("Invalid component id.")
     }
 
-    #[test]
-    fn test_modify_component_ordering() {
-        let mut runner = Runner::default();
-        runner.add_component(Box::new(TestComponent()));
-        runner.add_component(Box::new(TestComponent()));
-        runner.add_component(Box::new(TestComponent()));
-        runner.add_component(Box::new(TestComponent()));
+    fn component_holder_mut(&mut self, component_id: usize) -> &mut ComponentHolder {
+        self.components
+            .iter_mut()
+            .find(|holder| holder.id == component_id)
+            .expect("Invalid component id.")
+    }
 
-        runner.modify_component_ordering(Vec::from([3usize, 2, 1, 0]));
+    /// Set the severity of a lint rule checked during initialization. Rules default to warn.
+    ///
+    /// ### Arguments
+    /// * 'rule' - Lint rule to be configured.
+    /// * 'level' - Severity of the findings of the rule.
+    ///
+    pub fn set_lint_level(&mut self, rule: LintRule, level: LintLevel) {
+        self.lint_config.set_level(rule, level);
+    }
 
-        assert_eq!(runner.components.len(), 4);
-        assert_eq!(runner.components.first().unwrap().id, 3);
-        assert_eq!(runner.components.get(1).unwrap().id, 2);
-        assert_eq!(runner.components.get(2).unwrap().id, 1);
-        assert_eq!(runner.components.last().unwrap().id, 0);
+    /// Set the receiver of every diagnostic produced by the initialization lint pass and by the
+    /// lint rules checked after each tick. While a sink is set diagnostics raised after each tick
+    /// are no longer stored in the tick diagnostic log.
+    ///
+    /// ### Argument
+    /// * 'sink' - Diagnostic sink, a closure taking a &Diagnostic may be used.
+    ///
+    pub fn set_diagnostic_sink(&mut self, sink: Box<dyn DiagnosticSink>) {
+        self.diagnostic_sink = Some(sink);
     }
 
-    #[test]
-    fn test_init() {
-        let mut runner = Runner::default();
-        // Add the components in an order which does not match the computed execution order.
-        runner.add_component(Box::new(TestProducerComponent(
-            "test.producer4".to_string(),
-            Some("test.producer3".to_string()),
-        )));
-        runner.add_component(Box::new(TestProducerComponent(
-            "test.producer3".to_string(),
-            Some("test.producer1".to_string()),
-        )));
-        runner.add_component(Box::new(TestProducerComponent(
-            "test.producer1".to_string(),
-            None,
-        )));
-        runner.add_component(Box::new(TestProducerComponent(
-            "test.producer2".to_string(),
-            Some("test.producer1".to_string()),
-        )));
+    /// Diagnostics of the lint rules checked after each tick, such as stale channels, raised
+    /// since the log was last drained. Used when no diagnostic sink is set. The log is bounded,
+    /// the oldest diagnostics are dropped once it is full.
+    pub fn tick_diagnostics(&self) -> &EventLog<Diagnostic> {
+        &self.tick_diagnostic_log
+    }
 
-        assert!(!runner.init_complete);
-        runner.initialize();
-        assert!(runner.init_complete);
+    /// Remove and return every diagnostic recorded within the tick diagnostic log.
+    pub fn take_tick_diagnostics(&mut self) -> Vec<Diagnostic> {
+        self.tick_diagnostic_log.take()
+    }
 
-        assert_eq!(runner.components.len(), 4);
-        assert_eq!(runner.components.first().unwrap().id, 2);
-        assert_eq!(runner.components.get(1).unwrap().id, 3);
-        assert_eq!(runner.components.get(2).unwrap().id, 1);
-        assert_eq!(runner.components.last().unwrap().id, 0);
+    /// Set the maximum number of diagnostics held by the tick diagnostic log, defaults to
+    /// DEFAULT_EVENT_LOG_CAPACITY.
+    ///
+    /// ### Argument
+    /// * 'capacity' - Maximum number of diagnostics retained until the log is drained.
+    ///
+    pub fn set_tick_diagnostic_log_capacity(&mut self, capacity: usize) {
+        self.tick_diagnostic_log.set_capacity(capacity);
+    }
 
+    /// Initialize the component runner to prepare for runtime. This method must be called
+    /// exactly once after all components have been added and before the first dispatch_components() call.
+    /// Panics if a lint rule with deny severity reports a finding.
+    pub fn initialize(&mut self) {
+        assert!(!self.init_started);
+        self.init_started = true;
+
+        self.register_component_channels();
+        if let Some(denied) = self
+            .run_lint_pass()
+            .into_iter()
+            .find(|diagnostic| diagnostic.level == LintLevel::Deny)
+        {
+            panic!("{}", denied);
+        }
+
+        // Calculate and modify execution order of the inserted components to create an
+        // execution topological sequence.
+        let mut node_order_data = NodeOrderCalc::new(
+            self.channel_store_mut().node_graph.take().unwrap(),
+            &self.components,
+        );
+        let ordering = node_order_data
+            .calculate_topological_order()
+            .unwrap_or_else(|cycles| panic!("{}", self.build_execution_cycle(&cycles[0])));
+        self.modify_component_ordering(ordering);
+        self.node_graph = node_order_data.into_node_graph();
+        self.assign_dispatch_levels();
+        self.write_monitor
+            .configure(&self.channel_store, self.component_counter);
+
+        self.init_complete = true;
+    }
+
+    /// Fallible variant of initialize(). Every registration phase and the ordering step are run to
+    /// completion, collecting all wiring errors and warnings into a single report instead of
+    /// panicking on the first problem. On error the runner is left uninitialized and must not be
+    /// initialized again.
+    pub fn try_initialize(&mut self) -> Result<InitReport, InitReport> {
+        assert!(!self.init_started);
+        self.init_started = true;
+
+        self.channel_store_mut().collected_errors = Some(Vec::new());
+        self.register_component_channels();
+
+        let mut report = InitReport::default();
+        report.errors.extend(
+            self.channel_store_mut()
+                .collected_errors
+                .take()
+                .unwrap()
+                .into_iter()
+                .map(InitError::Channel),
+        );
+        for diagnostic in self.run_lint_pass() {
+            match diagnostic.level {
+                LintLevel::Deny => report.errors.push(InitError::Lint(diagnostic)),
+                _ => report.warnings.push(InitWarning::Lint(diagnostic)),
+            }
+        }
+
+        let mut node_order_data = NodeOrderCalc::new(
+            self.channel_store_mut().node_graph.take().unwrap(),
+            &self.components,
+        );
+        match node_order_data.calculate_topological_order() {
+            Ok(ordering) if report.is_ok() => {
+                self.modify_component_ordering(ordering);
+                self.node_graph = node_order_data.into_node_graph();
+                self.assign_dispatch_levels();
+                self.write_monitor
+                    .configure(&self.channel_store, self.component_counter);
+                self.init_complete = true;
+                Ok(report)
+            }
+            Ok(_) => Err(report),
+            Err(cycles) => {
+                report.errors.extend(
+                    cycles
+                        .iter()
+                        .map(|cycle| InitError::Cycle(self.build_execution_cycle(cycle))),
+                );
+                Err(report)
+            }
+        }
+    }
+
+    /// Check the registered channel wiring against the lint configuration, passing every
+    /// diagnostic to the diagnostic sink.
+    fn run_lint_pass(&mut self) -> Vec<Diagnostic> {
+        let diagnostics = self.lint_config.check(&self.channel_store);
+        if let Some(sink) = self.diagnostic_sink.as_mut() {
+            for diagnostic in diagnostics.iter() {
+                sink.report(diagnostic);
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Report every staleness limit of a dispatched reader which became exceeded this tick and
+    /// every behind channel of a dispatched reader which its owner never wrote. Returns the first
+    /// diagnostic of deny level, which aborts the runner once the tick is complete.
+    fn check_tick_lints(&mut self) -> Option<Diagnostic> {
+        let dispatched_flags = &self.dispatched_flags;
+        let mut findings = Vec::new();
+        if self.lint_config.level(LintRule::StaleChannel) != LintLevel::Allow {
+            findings.extend(
+                self.channel_store
+                    .check_staleness_limits(|reader_id| dispatched_flags[reader_id])
+                    .into_iter()
+                    .map(|finding| (LintRule::StaleChannel, finding)),
+            );
+        }
+        if self.lint_config.level(LintRule::UnwrittenBehindChannel) != LintLevel::Allow {
+            let components = &self.components;
+            findings.extend(
+                self.channel_store
+                    .check_unwritten_behind_channels(
+                        |reader_id| dispatched_flags[reader_id],
+                        |owner_id| {
+                            components.iter().any(|holder| {
+                                holder.id == owner_id && holder.last_dispatch.is_some()
+                            })
+                        },
+                    )
+                    .into_iter()
+                    .map(|finding| (LintRule::UnwrittenBehindChannel, finding)),
+            );
+        }
+
+        let mut denied = None;
+        for (rule, (channel_idx, reader_id)) in findings {
+            let level = self.lint_config.level(rule);
+            let diagnostic = Diagnostic {
+                rule,
+                level,
+                channel: String::from(self.channel_store.channel_name(channel_idx)),
+                component: reader_id,
+            };
+            match self.diagnostic_sink.as_mut() {
+                Some(sink) => sink.report(&diagnostic),
+                None => self.tick_diagnostic_log.push(diagnostic.clone()),
+            }
+            if level == LintLevel::Deny && denied.is_none() {
+                denied = Some(diagnostic);
+            }
+        }
+
+        denied
+    }
+
+    /// Capture every channel value, the tick counter and the state of every component which
+    /// implements Component::save_state(). Must be called between dispatches.
+    pub fn snapshot(&self) -> RunnerSnapshot {
+        assert!(self.init_complete);
+        RunnerSnapshot {
+            store: self.channel_store.snapshot(),
+            tick: self.tick,
+            components: self
+                .components
+                .iter()
+                .map(|holder| ComponentSnapshot {
+                    id: holder.id,
+                    last_dispatch: holder.last_dispatch,
+                    state: holder.component.save_state(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Restore a snapshot produced by snapshot() of this runner or of an identically built
+    /// runner, such as after a warm restart. The snapshot is validated before any state is
+    /// modified. Must be called between dispatches.
+    ///
+    /// ### Argument
+    /// * 'snapshot' - Snapshot to be restored.
+    ///
+    pub fn restore(&mut self, snapshot: &RunnerSnapshot) -> Result<(), SnapshotError> {
+        assert!(self.init_complete);
+        if snapshot.components.len() != self.components.len() {
+            return Err(SnapshotError::ComponentCount {
+                expected: self.components.len(),
+                actual: snapshot.components.len(),
+            });
+        }
+        self.channel_store.restore(&snapshot.store)?;
+
+        self.tick = snapshot.tick;
+        for component_snapshot in snapshot.components.iter() {
+            let holder = self.component_holder_mut(component_snapshot.id);
+            holder.last_dispatch = component_snapshot.last_dispatch;
+            if let Some(state) = component_snapshot.state.as_ref() {
+                holder.component.restore_state(state.as_ref());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Describe every component held by the runner. Components are listed in insertion order
+    /// before initialization and in execution order afterwards.
+    pub fn components(&self) -> impl Iterator<Item = ComponentInfo<'_>> {
+        self.components.iter().map(|holder| ComponentInfo {
+            id: holder.id,
+            name: &holder.name,
+            schedule: holder.schedule,
+        })
+    }
+
+    /// Ids of the components in the execution order computed during initialization.
+    pub fn execution_order(&self) -> Vec<usize> {
+        assert!(self.init_complete);
+        self.components.iter().map(|holder| holder.id).collect()
+    }
+
+    /// Channel store holding every channel registered by the components of the runner.
+    pub fn channel_store(&self) -> &ChannelStore {
+        &self.channel_store
+    }
+
+    /// Obtain a shared handle to the channel store of the initialized runner. Other threads may
+    /// observe channels holding Send and Sync values through reader tokens obtained with
+    /// SharedChannelStore::try_bind_observer() while the runner keeps dispatching components.
+    #[cfg(feature = "concurrent")]
+    pub fn shared_channel_store(&self) -> SharedChannelStore {
+        assert!(self.init_complete);
+        SharedChannelStore::new(self.channel_store.clone())
+    }
+
+    fn channel_store_mut(&mut self) -> &mut ChannelStore {
+        store_mut(&mut self.channel_store)
+    }
+
+    /// Obtain the component wiring of the initialized runner. Components are listed in execution
+    /// order and every channel dependency is included as an edge, including dangling channels and
+    /// behind channels.
+    pub fn execution_graph(&self) -> ExecutionGraph {
+        assert!(self.init_complete);
+
+        let channel_edge = |dep: &NodeDependency, kind: GraphEdgeKind| GraphEdge {
+            producer: Some(dep.owner),
+            consumer: dep.consumer,
+            channel: String::from(self.channel_store.channel_name(dep.channel)),
+            kind,
+        };
+
+        let mut edges: Vec<GraphEdge> = self
+            .node_graph
+            .dependencies()
+            .map(|dep| {
+                let kind = if self.channel_store.is_dangling_channel(dep.channel) {
+                    GraphEdgeKind::Dangling
+                } else {
+                    GraphEdgeKind::Current
+                };
+                channel_edge(dep, kind)
+            })
+            .collect();
+        edges.extend(
+            self.channel_store
+                .behind_dependencies()
+                .iter()
+                .map(|dep| channel_edge(dep, GraphEdgeKind::Behind)),
+        );
+        edges.extend(
+            self.channel_store
+                .query_unowned_dangling_channels()
+                .into_iter()
+                .map(|(channel, reader)| GraphEdge {
+                    producer: None,
+                    consumer: reader,
+                    channel,
+                    kind: GraphEdgeKind::Dangling,
+                }),
+        );
+
+        ExecutionGraph {
+            components: self
+                .components
+                .iter()
+                .map(|holder| GraphNode {
+                    id: holder.id,
+                    name: holder.name.clone(),
+                })
+                .collect(),
+            edges,
+        }
+    }
+
+    /// Render the component wiring of the initialized runner as a diagram or document.
+    ///
+    /// ### Argument
+    /// * 'format' - Output format of the graph.
+    ///
+    pub fn export_graph(&self, format: GraphFormat) -> String {
+        self.execution_graph().render(format)
+    }
+
+    /// Resolve the channel and component names of a dependency cycle found by the ordering step.
+    fn build_execution_cycle(&self, cycle: &[NodeDependency]) -> ExecutionCycle {
+        ExecutionCycle {
+            edges: cycle
+                .iter()
+                .map(|dep| CycleEdge {
+                    producer: dep.owner,
+                    producer_name: String::from(self.component_name(dep.owner)),
+                    consumer: dep.consumer,
+                    consumer_name: String::from(self.component_name(dep.consumer)),
+                    channel: String::from(self.channel_store.channel_name(dep.channel)),
+                    // Consumers which are not current readers registered the channel as dangling.
+                    kind: if self
+                        .channel_store
+                        .channel_readers(dep.channel)
+                        .iter()
+                        .any(|reader| {
+                            reader.id == dep.consumer && reader.kind == ReaderKind::Current
+                        }) {
+                        CycleEdgeKind::Current
+                    } else {
+                        CycleEdgeKind::DanglingOwnership
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    /// Run the dangling, write and read channel registration phases of every component.
+    fn register_component_channels(&mut self) {
+        let channel_store = store_mut(&mut self.channel_store);
+
+        // Register dangling channels before write channels as dangling channels may be owned during write channel register.
+        for component_holder in self.components.iter_mut() {
+            let write_builder = ChannelDanglingBuilder::new(component_holder.id);
+            component_holder
+                .component
+                .register_dangling_channels(write_builder, channel_store);
+        }
+
+        // Register component write channels with the channel store. Write channels must be registered before
+        // read channels.
+        for component_holder in self.components.iter_mut() {
+            let write_builder = ChannelWriteBuilder::new(component_holder.id);
+            component_holder
+                .component
+                .register_write_channels(write_builder, channel_store);
+        }
+
+        // Register component read channels with the channel store. Read channels are registered to existing channels,
+        // as such, read channels are registered after write channels.
+        for component_holder in self.components.iter_mut() {
+            let read_builder = ChannelReadBuilder::new(component_holder.id);
+            component_holder
+                .component
+                .register_read_channels(read_builder, channel_store);
+        }
+    }
+
+    /// Dispatch all included components into the runner object.
+    ///
+    /// A lint rule checked after each tick, such as LintRule::StaleChannel, which is set to deny
+    /// and broken aborts the runner: the tick is completed, the diagnostic is reported, then this
+    /// call panics with the diagnostic message.
+    pub fn dispatch_components(&mut self) {
+        assert!(self.init_complete);
+
+        let timestamp = sample_clock(self.clock.as_deref());
+        self.channel_store.clock.advance(self.tick, timestamp);
+
+        self.dispatched_flags.resize(self.component_counter, false);
+        for component_holder in self.components.iter_mut() {
+            let is_due = component_holder.schedule.is_due(self.tick);
+            self.dispatched_flags[component_holder.id] = is_due;
+            if is_due {
+                let context = component_holder.next_dispatch_context(self.tick, timestamp);
+                self.write_monitor
+                    .begin_dispatch(component_holder.id, &self.channel_store);
+                if !self.timing.enabled {
+                    dispatch_or_replay(
+                        component_holder,
+                        &mut self.replays,
+                        &self.channel_store,
+                        &context,
+                    );
+                    self.write_monitor.end_dispatch(
+                        component_holder.id,
+                        self.tick,
+                        &self.channel_store,
+                    );
+                    continue;
+                }
+
+                let start = sample_clock(self.clock.as_deref());
+                dispatch_or_replay(
+                    component_holder,
+                    &mut self.replays,
+                    &self.channel_store,
+                    &context,
+                );
+                let duration = sample_clock(self.clock.as_deref()).saturating_sub(start);
+                self.write_monitor.end_dispatch(
+                    component_holder.id,
+                    self.tick,
+                    &self.channel_store,
+                );
+
+                component_holder.timing.record(duration);
+                if let Some(budget) = component_holder.budget.filter(|budget| duration > *budget) {
+                    self.timing.report_overrun(Overrun::ComponentBudget {
+                        component_id: component_holder.id,
+                        tick: self.tick,
+                        duration,
+                        budget,
+                    });
+                }
+            }
+        }
+
+        self.finish_tick(timestamp);
+    }
+
+    /// Dispatch all included components, running components of the same dependency level
+    /// concurrently on 'worker_threads' worker threads. Levels are dispatched in order, as such
+    /// channel values observed by every component match those of dispatch_components().
+    ///
+    /// Worker threads are spawned by the first call and kept by the runner for the following
+    /// calls, they are only spawned again when 'worker_threads' changes. Levels holding a single
+    /// due component are dispatched on the calling thread.
+    ///
+    /// Every component must have been added with add_parallel_component(). Per component
+    /// execution time is not measured, the tick duration is measured when timing is enabled.
+    /// Lint rules of deny level checked after each tick abort the runner as with
+    /// dispatch_components().
+    ///
+    /// ### Argument
+    /// * 'worker_threads' - Maximum number of threads dispatching components of a level, must be non-zero.
+    ///
+    #[cfg(feature = "std")]
+    pub fn dispatch_components_parallel(&mut self, worker_threads: usize) {
+        assert!(self.init_complete);
+        assert!(
+            worker_threads > 0,
+            "Parallel dispatch requires at least one worker thread."
+        );
+        assert!(
+            self.components
+                .iter()
+                .all(|holder| holder.component.is_parallel()),
+            "Parallel dispatch requires every component to be added with add_parallel_component()."
+        );
+
+        /// Component scheduled for dispatch on a worker thread.
+        struct ParallelDispatch<'a> {
+            id: usize,
+            component: &'a mut dyn ParallelComponent,
+            context: DispatchContext,
+        }
+
+        if self
+            .worker_pool
+            .as_ref()
+            .is_none_or(|pool| pool.threads() != worker_threads)
+        {
+            self.worker_pool = Some(WorkerPool::new(worker_threads));
+        }
+
+        let timestamp = sample_clock(self.clock.as_deref());
+        self.channel_store.clock.advance(self.tick, timestamp);
+
+        self.dispatched_flags.resize(self.component_counter, false);
+        let mut levels: Vec<Vec<ParallelDispatch>> = Vec::new();
+        let mut due_ids: Vec<usize> = Vec::new();
+        for component_holder in self.components.iter_mut() {
+            let is_due = component_holder.schedule.is_due(self.tick);
+            self.dispatched_flags[component_holder.id] = is_due;
+            if is_due {
+                due_ids.push(component_holder.id);
+                let context = component_holder.next_dispatch_context(self.tick, timestamp);
+                self.write_monitor
+                    .begin_dispatch(component_holder.id, &self.channel_store);
+                if levels.len() <= component_holder.level {
+                    levels.resize_with(component_holder.level + 1, Vec::new);
+                }
+                levels[component_holder.level].push(ParallelDispatch {
+                    id: component_holder.id,
+                    component: component_holder
+                        .component
+                        .as_parallel_mut()
+                        .expect("Parallel components must be held as such."),
+                    context,
+                });
+            }
+        }
+
+        let channel_store = &self.channel_store;
+        let sync_store = SyncChannelStore::new(channel_store);
+        let worker_pool = self.worker_pool.as_ref().unwrap();
+        for level in levels.iter_mut() {
+            // Replayed components are substituted on the dispatching thread before the level is
+            // dispatched, components of the same level never read each other's channels.
+            level.retain(|dispatch| {
+                match self
+                    .replays
+                    .iter_mut()
+                    .find(|replay| replay.component_id == dispatch.id)
+                {
+                    Some(replay) => {
+                        replay.apply(dispatch.context.tick(), channel_store);
+                        false
+                    }
+                    None => true,
+                }
+            });
+
+            let chunk_size = level.len().div_ceil(worker_threads).max(1);
+            let mut jobs: Vec<_> = level
+                .chunks_mut(chunk_size)
+                .map(|chunk| {
+                    move || {
+                        for dispatch in chunk.iter_mut() {
+                            dispatch
+                                .component
+                                .dispatch_parallel(&sync_store, &dispatch.context);
+                        }
+                    }
+                })
+                .collect();
+            match jobs.as_mut_slice() {
+                [job] => job(),
+                jobs => worker_pool.run(
+                    &mut jobs
+                        .iter_mut()
+                        .map(|job| job as &mut (dyn FnMut() + Send))
+                        .collect::<Vec<_>>(),
+                ),
+            }
+        }
+
+        // Owners are the only writers of their channels, as such policies are checked once
+        // every level was dispatched, in execution order including replayed components.
+        for component_id in due_ids {
+            self.write_monitor
+                .end_dispatch(component_id, self.tick, &self.channel_store);
+        }
+
+        self.finish_tick(timestamp);
+    }
+
+    /// Record selected channels, feed telemetry sinks, update behind registers of dispatched
+    /// readers, record history rings, check the lint rules of each tick and record the tick
+    /// duration. Panics once the tick is complete if a lint rule of deny level was broken.
+    fn finish_tick(&mut self, timestamp: Duration) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.record_tick(self.tick, timestamp, &self.channel_store);
+        }
+        for sink in self.telemetry_sinks.iter_mut() {
+            sink.record_tick(self.tick, timestamp, &self.channel_store);
+        }
+
+        let dispatched_flags = &self.dispatched_flags;
+        self.channel_store
+            .update_active_behind_registers(|reader_id| dispatched_flags[reader_id]);
+        self.channel_store.update_history_registers();
+        let denied = self.check_tick_lints();
+
+        if self.timing.enabled {
+            let duration = sample_clock(self.clock.as_deref()).saturating_sub(timestamp);
+            self.timing.tick_stats.record(duration);
+            if let Some(deadline) = self
+                .timing
+                .tick_deadline
+                .filter(|deadline| duration > *deadline)
+            {
+                self.timing.report_overrun(Overrun::TickDeadline {
+                    tick: self.tick,
+                    duration,
+                    deadline,
+                });
+            }
+        }
+        self.tick += 1;
+
+        if let Some(diagnostic) = denied {
+            panic!("{}", diagnostic);
+        }
+    }
+
+    /// Assign each component its dependency level, one above the highest level of the owners of
+    /// the channels it reads. Components must be in execution order.
+    fn assign_dispatch_levels(&mut self) {
+        let mut levels: Vec<usize> = vec![0; self.component_counter];
+        for component_holder in self.components.iter_mut() {
+            component_holder.level = levels[component_holder.id];
+            for dep in self.node_graph.consumers_of(component_holder.id) {
+                levels[dep.consumer] = levels[dep.consumer].max(component_holder.level + 1);
+            }
+        }
+    }
+
+    /// Re-layout component ordering following provided topology.
+    fn modify_component_ordering(&mut self, ordering: Vec<usize>) {
+        let mut order_positions: Vec<usize> = Vec::new();
+        order_positions.resize(self.component_counter, usize::MAX);
+        for (insert_idx, component_id) in ordering.iter().enumerate() {
+            order_positions[*component_id] = insert_idx;
+        }
+
+        self.components
+            .sort_by_key(|comp_holder| order_positions[comp_holder.id]);
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use alloc::{
+        boxed::Box,
+        rc::Rc,
+        string::{String, ToString},
+        vec::Vec,
+    };
+
+    use core::{any::Any, cell::RefCell, time::Duration};
+
+    use crate::{
+        channel::{
+            error::ChannelError,
+            snapshot::SnapshotError,
+            store::{ChannelReader, ReaderKind, RegViewProducer, WritePolicy},
+            token::{ChannelBehindToken, ChannelOwnerToken, ChannelReaderToken},
+        },
+        system::{
+            clock::SimClock,
+            component::{Component, ComponentInfo, ComponentState, DispatchContext},
+            graph::{GraphEdge, GraphEdgeKind, GraphFormat},
+            lint::{Diagnostic, LintLevel, LintRule},
+            report::{CycleEdge, CycleEdgeKind, ExecutionCycle, InitError, InitWarning},
+            schedule::RateSchedule,
+            timing::Overrun,
+            write_policy::WriteViolation,
+        },
+    };
+
+    #[cfg(feature = "concurrent")]
+    use crate::channel::store::SharedChannelStore;
+    #[cfg(feature = "std")]
+    use crate::{
+        channel::store::SyncChannelStore,
+        system::{
+            component::ParallelComponent,
+            record::{RecordBuffer, RecordChannels, Recorder},
+        },
+    };
+
+    use super::Runner;
+
+    struct TestComponent();
+    impl Component for TestComponent {
+        fn dispatch(
+            &mut self,
+            _channel_store: &crate::channel::store::ChannelStore,
+            _context: &crate::system::component::DispatchContext,
+        ) {
+        }
+    }
+
+    struct TestProducerComponent(String, Option<String>);
+    impl Component for TestProducerComponent {
+        fn dispatch(
+            &mut self,
+            _channel_store: &crate::channel::store::ChannelStore,
+            _context: &crate::system::component::DispatchContext,
+        ) {
+        }
+
+        fn register_write_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelWriteBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            channel_builder.register_write_channel(channel_store, self.0.clone(), 0u8);
+        }
+
+        fn register_read_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelReadBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            if let Some(name) = self.1.as_ref() {
+                channel_builder.bind_read_channel::<u8>(channel_store, name.clone());
+            }
+        }
+    }
+
+    /// Test component renamed for diagnostics, channel registration is forwarded to the inner component.
+    struct TestRenamedComponent<C>(&'static str, C);
+    impl<C: Component> Component for TestRenamedComponent<C> {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn dispatch(
+            &mut self,
+            channel_store: &crate::channel::store::ChannelStore,
+            context: &crate::system::component::DispatchContext,
+        ) {
+            self.1.dispatch(channel_store, context);
+        }
+
+        fn register_write_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelWriteBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            self.1
+                .register_write_channels(channel_builder, channel_store);
+        }
+
+        fn register_read_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelReadBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            self.1
+                .register_read_channels(channel_builder, channel_store);
+        }
+    }
+
+    struct TestMismatchComponent(String);
+    impl Component for TestMismatchComponent {
+        fn dispatch(
+            &mut self,
+            _channel_store: &crate::channel::store::ChannelStore,
+            _context: &crate::system::component::DispatchContext,
+        ) {
+        }
+
+        fn register_dangling_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelDanglingBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            channel_builder.register_dangling_channel(
+                channel_store,
+                "test.dangling".to_string(),
+                0u8,
+            );
+        }
+
+        fn register_read_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelReadBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            channel_builder.bind_read_channel::<u16>(channel_store, self.0.clone());
+        }
+    }
+
+    #[test]
+    fn test_runner_insertion() {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestComponent()));
+        runner.add_component(Box::new(TestComponent()));
+        runner.add_component(Box::new(TestComponent()));
+        runner.add_component(Box::new(TestComponent()));
+
+        assert_eq!(runner.components.len(), 4);
+        assert_eq!(runner.components.first().unwrap().id, 0);
+        assert_eq!(runner.components.get(1).unwrap().id, 1);
+        assert_eq!(runner.components.get(2).unwrap().id, 2);
+        assert_eq!(runner.components.last().unwrap().id, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: self.init_complete")]
+    fn test_init_not_complete() {
+        let mut runner = Runner::default();
+        runner.dispatch_components();
+    }
+
+    #[test]
+    fn test_empty_runner() {
+        let mut runner = Runner::default();
+        runner.initialize();
+        runner.dispatch_components();
+    }
+
+    #[test]
+    fn test_modify_component_ordering() {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestComponent()));
+        runner.add_component(Box::new(TestComponent()));
+        runner.add_component(Box::new(TestComponent()));
+        runner.add_component(Box::new(TestComponent()));
+
+        runner.modify_component_ordering(Vec::from([3usize, 2, 1, 0]));
+
+        assert_eq!(runner.components.len(), 4);
+        assert_eq!(runner.components.first().unwrap().id, 3);
+        assert_eq!(runner.components.get(1).unwrap().id, 2);
+        assert_eq!(runner.components.get(2).unwrap().id, 1);
+        assert_eq!(runner.components.last().unwrap().id, 0);
+    }
+
+    #[test]
+    fn test_init() {
+        let mut runner = Runner::default();
+        // Add the components in an order which does not match the computed execution order.
+        runner.add_component(Box::new(TestProducerComponent(
+            "test.producer4".to_string(),
+            Some("test.producer3".to_string()),
+        )));
+        runner.add_component(Box::new(TestProducerComponent(
+            "test.producer3".to_string(),
+            Some("test.producer1".to_string()),
+        )));
+        runner.add_component(Box::new(TestProducerComponent(
+            "test.producer1".to_string(),
+            None,
+        )));
+        runner.add_component(Box::new(TestProducerComponent(
+            "test.producer2".to_string(),
+            Some("test.producer1".to_string()),
+        )));
+
+        assert!(!runner.init_complete);
+        runner.initialize();
+        assert!(runner.init_complete);
+
+        assert_eq!(runner.components.len(), 4);
+        assert_eq!(runner.components.first().unwrap().id, 2);
+        assert_eq!(runner.components.get(1).unwrap().id, 3);
+        assert_eq!(runner.components.get(2).unwrap().id, 1);
+        assert_eq!(runner.components.last().unwrap().id, 0);
+
+        runner.dispatch_components();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cycle detected in execution order: Estimator --[test.producer1]--> Controller --[test.producer2]--> Estimator. Consider binding channel [test.producer2] in component Estimator with bind_read_behind_channel() to break the cycle."
+    )]
+    fn test_init_cycle() {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestRenamedComponent(
+            "Estimator",
+            TestProducerComponent(
+                "test.producer1".to_string(),
+                Some("test.producer2".to_string()),
+            ),
+        )));
+        runner.add_component(Box::new(TestRenamedComponent(
+            "Controller",
+            TestProducerComponent(
+                "test.producer2".to_string(),
+                Some("test.producer1".to_string()),
+            ),
+        )));
+
+        runner.initialize();
+    }
+
+    #[test]
+    fn test_try_init() {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestProducerComponent(
+            "test.producer1".to_string(),
+            None,
+        )));
+        runner.add_component(Box::new(TestProducerComponent(
+            "test.producer2".to_string(),
+            Some("test.producer1".to_string()),
+        )));
+
+        let report = runner.try_initialize().unwrap();
+        assert!(report.is_ok());
+        assert_eq!(
+            report.warnings,
+            Vec::from([InitWarning::Lint(Diagnostic {
+                rule: LintRule::UnreadChannel,
+                level: LintLevel::Warn,
+                channel: "test.producer2".to_string(),
+                component: 1
+            })])
+        );
+        assert!(runner.init_complete);
+        runner.dispatch_components();
+    }
+
+    #[test]
+    fn test_try_init_collects_errors() {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestRenamedComponent(
+            "Estimator",
+            TestProducerComponent(
+                "test.producer1".to_string(),
+                Some("test.producer2".to_string()),
+            ),
+        )));
+        runner.add_component(Box::new(TestRenamedComponent(
+            "Controller",
+            TestProducerComponent(
+                "test.producer2".to_string(),
+                Some("test.producer1".to_string()),
+            ),
+        )));
+        runner.add_component(Box::new(TestProducerComponent(
+            "test.producer3".to_string(),
+            Some("test.missing".to_string()),
+        )));
+        runner.add_component(Box::new(TestMismatchComponent(
+            "test.producer3".to_string(),
+        )));
+
+        let report = runner.try_initialize().unwrap_err();
+        assert!(!runner.init_complete);
+        assert_eq!(
+            report.errors,
+            Vec::from([
+                InitError::Channel(ChannelError::NotFound {
+                    name: "test.missing".to_string(),
+                    requester: 2
+                }),
+                InitError::Channel(ChannelError::TypeMismatch {
+                    name: "test.producer3".to_string(),
+                    requester: 3,
+                    expected: "u16",
+                    actual: "u8"
+                }),
+                InitError::Cycle(ExecutionCycle {
+                    edges: Vec::from([
+                        CycleEdge {
+                            producer: 0,
+                            producer_name: "Estimator".to_string(),
+                            consumer: 1,
+                            consumer_name: "Controller".to_string(),
+                            channel: "test.producer1".to_string(),
+                            kind: CycleEdgeKind::Current
+                        },
+                        CycleEdge {
+                            producer: 1,
+                            producer_name: "Controller".to_string(),
+                            consumer: 0,
+                            consumer_name: "Estimator".to_string(),
+                            channel: "test.producer2".to_string(),
+                            kind: CycleEdgeKind::Current
+                        },
+                    ])
+                }),
+            ])
+        );
+        assert_eq!(
+            report.warnings,
+            Vec::from([
+                InitWarning::Lint(Diagnostic {
+                    rule: LintRule::UnownedDanglingChannel,
+                    level: LintLevel::Warn,
+                    channel: "test.dangling".to_string(),
+                    component: 3
+                }),
+                InitWarning::Lint(Diagnostic {
+                    rule: LintRule::UnreadChannel,
+                    level: LintLevel::Warn,
+                    channel: "test.producer3".to_string(),
+                    component: 2
+                })
+            ])
+        );
+    }
+
+    struct TestCounterComponent(ChannelOwnerToken<u64>);
+    impl Component for TestCounterComponent {
+        fn register_write_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelWriteBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            self.0 = channel_builder.register_write_channel(
+                channel_store,
+                "test.counter".to_string(),
+                0,
+            );
+        }
+
+        fn dispatch(
+            &mut self,
+            channel_store: &crate::channel::store::ChannelStore,
+            _context: &crate::system::component::DispatchContext,
+        ) {
+            channel_store.grab(&self.0).update(|value| *value += 1);
+        }
+    }
+
+    struct TestSlowReaderComponent {
+        read_tok: ChannelReaderToken<u64>,
+        behind_tok: ChannelBehindToken<u64>,
+        observed: Rc<RefCell<Vec<(u64, u64)>>>,
+    }
+    impl Component for TestSlowReaderComponent {
+        fn register_read_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelReadBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            self.read_tok =
+                channel_builder.bind_read_channel(channel_store, "test.counter".to_string());
+            self.behind_tok =
+                channel_builder.bind_read_behind_channel(channel_store, "test.counter".to_string());
+        }
+
+        fn dispatch(
+            &mut self,
+            channel_store: &crate::channel::store::ChannelStore,
+            _context: &crate::system::component::DispatchContext,
+        ) {
+            self.observed.borrow_mut().push((
+                channel_store.grab(&self.read_tok).get(),
+                channel_store.grab(&self.behind_tok).get(),
+            ));
+        }
+    }
+
+    struct TestSilentComponent(ChannelOwnerToken<u64>);
+    impl Component for TestSilentComponent {
+        fn register_write_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelWriteBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            self.0 = channel_builder.register_write_channel(
+                channel_store,
+                "test.counter".to_string(),
+                0,
+            );
+        }
+
+        fn dispatch(
+            &mut self,
+            _channel_store: &crate::channel::store::ChannelStore,
+            _context: &crate::system::component::DispatchContext,
+        ) {
+        }
+    }
+
+    #[test]
+    fn test_unwritten_behind_channel() {
+        let mut runner = Runner::default();
+        let reader_id = runner.add_component(Box::new(TestSlowReaderComponent {
+            read_tok: ChannelReaderToken::default(),
+            behind_tok: ChannelBehindToken::default(),
+            observed: Rc::new(RefCell::new(Vec::new())),
+        }));
+        runner.add_component_with_rate(
+            Box::new(TestSilentComponent(ChannelOwnerToken::default())),
+            2,
+            1,
+        );
+        runner.initialize();
+
+        // The owner is first dispatched on tick 1, the finding is reported once.
+        runner.dispatch_components();
+        assert!(runner.tick_diagnostics().is_empty());
+        for _ in 0..4 {
+            runner.dispatch_components();
+        }
+        assert_eq!(
+            runner.take_tick_diagnostics(),
+            Vec::from([Diagnostic {
+                rule: LintRule::UnwrittenBehindChannel,
+                level: LintLevel::Warn,
+                channel: "test.counter".to_string(),
+                component: reader_id,
+            }])
+        );
+
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestSlowReaderComponent {
+            read_tok: ChannelReaderToken::default(),
+            behind_tok: ChannelBehindToken::default(),
+            observed: Rc::new(RefCell::new(Vec::new())),
+        }));
+        runner.add_component(Box::new(TestCounterComponent(ChannelOwnerToken::default())));
+        runner.initialize();
+        for _ in 0..4 {
+            runner.dispatch_components();
+        }
+        assert!(runner.tick_diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_multi_rate_dispatch() {
+        let observed = Rc::new(RefCell::new(Vec::new()));
+        let mut runner = Runner::default();
+        runner.add_component_with_rate(
+            Box::new(TestSlowReaderComponent {
+                read_tok: ChannelReaderToken::default(),
+                behind_tok: ChannelBehindToken::default(),
+                observed: observed.clone(),
+            }),
+            3,
+            1,
+        );
+        runner.add_component(Box::new(TestCounterComponent(ChannelOwnerToken::default())));
+        runner.initialize();
+
+        for _ in 0..9 {
+            runner.dispatch_components();
+        }
+
+        // The reader is dispatched on ticks 1, 4 and 7 after the counter. The behind value is the
+        // counter value at the end of the reader's previous dispatch tick.
+        assert_eq!(*observed.borrow(), Vec::from([(2, 0), (5, 2), (8, 5)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Component period must be at least one tick.")]
+    fn test_zero_period() {
+        let mut runner = Runner::default();
+        runner.add_component_with_rate(Box::new(TestComponent()), 0, 0);
+    }
+
+    struct TestContextComponent(Rc<RefCell<Vec<DispatchContext>>>);
+    impl Component for TestContextComponent {
+        fn dispatch(
+            &mut self,
+            _channel_store: &crate::channel::store::ChannelStore,
+            context: &DispatchContext,
+        ) {
+            self.0.borrow_mut().push(*context);
+        }
+    }
+
+    #[test]
+    fn test_dispatch_context() {
+        let observed = Rc::new(RefCell::new(Vec::new()));
+        let clock = SimClock::default();
+        let mut runner = Runner::default();
+        runner.set_clock(Box::new(clock.clone()));
+        runner.add_component_with_rate(Box::new(TestContextComponent(observed.clone())), 2, 0);
+        runner.initialize();
+
+        for _ in 0..5 {
+            runner.dispatch_components();
+            clock.step(Duration::from_millis(10));
+        }
+
+        assert_eq!(
+            *observed.borrow(),
+            Vec::from([
+                DispatchContext::new(0, Duration::ZERO, Duration::ZERO),
+                DispatchContext::new(2, Duration::from_millis(20), Duration::from_millis(20)),
+                DispatchContext::new(4, Duration::from_millis(40), Duration::from_millis(20)),
+            ])
+        );
+    }
+
+    /// Component which advances the simulation clock by a fixed amount on each dispatch,
+    /// simulating the dispatch execution time.
+    struct TestBusyComponent(SimClock, Vec<Duration>, usize);
+    impl Component for TestBusyComponent {
+        fn dispatch(
+            &mut self,
+            _channel_store: &crate::channel::store::ChannelStore,
+            _context: &DispatchContext,
+        ) {
+            self.0.step(self.1[self.2 % self.1.len()]);
+            self.2 += 1;
+        }
+    }
+
+    #[test]
+    fn test_dispatch_timing() {
+        let clock = SimClock::default();
+        let mut runner = Runner::default();
+        runner.set_clock(Box::new(clock.clone()));
+        runner.set_timing_enabled(true);
+        let fast_id = runner.add_component(Box::new(TestBusyComponent(
+            clock.clone(),
+            Vec::from([Duration::from_micros(10), Duration::from_micros(30)]),
+            0,
+        )));
+        let slow_id = runner.add_component(Box::new(TestBusyComponent(
+            clock.clone(),
+            Vec::from([Duration::from_micros(100)]),
+            0,
+        )));
+        runner.set_component_budget(fast_id, Some(Duration::from_micros(20)));
+        runner.set_tick_deadline(Some(Duration::from_micros(135)));
+        runner.initialize();
+
+        for _ in 0..4 {
+            runner.dispatch_components();
+        }
+
+        let fast_timing = runner.component_timing(fast_id);
+        assert_eq!(fast_timing.count(), 4);
+        assert_eq!(fast_timing.min(), Duration::from_micros(10));
+        assert_eq!(fast_timing.max(), Duration::from_micros(30));
+        assert_eq!(fast_timing.mean(), Duration::from_micros(20));
+        assert_eq!(
+            runner.component_timing(slow_id).mean(),
+            Duration::from_micros(100)
+        );
+        assert_eq!(runner.tick_timing().max(), Duration::from_micros(130));
+
+        assert_eq!(
+            runner.take_overruns(),
+            Vec::from([
+                Overrun::ComponentBudget {
+                    component_id: fast_id,
+                    tick: 1,
+                    duration: Duration::from_micros(30),
+                    budget: Duration::from_micros(20),
+                },
+                Overrun::ComponentBudget {
+                    component_id: fast_id,
+                    tick: 3,
+                    duration: Duration::from_micros(30),
+                    budget: Duration::from_micros(20),
+                },
+            ])
+        );
+        assert!(runner.overruns().is_empty());
+    }
+
+    #[test]
+    fn test_overrun_log_capacity() {
+        let clock = SimClock::default();
+        let mut runner = Runner::default();
+        runner.set_clock(Box::new(clock.clone()));
+        runner.set_timing_enabled(true);
+        let busy_id = runner.add_component(Box::new(TestBusyComponent(
+            clock.clone(),
+            Vec::from([Duration::from_micros(30)]),
+            0,
+        )));
+        runner.set_component_budget(busy_id, Some(Duration::from_micros(20)));
+        runner.set_overrun_log_capacity(2);
+        runner.initialize();
+
+        for _ in 0..5 {
+            runner.dispatch_components();
+        }
+
+        assert_eq!(runner.overruns().len(), 2);
+        assert_eq!(runner.overruns().dropped(), 3);
+        let ticks: Vec<u64> = runner
+            .take_overruns()
+            .iter()
+            .map(|overrun| match overrun {
+                Overrun::ComponentBudget { tick, .. } => *tick,
+                Overrun::TickDeadline { tick, .. } => *tick,
+            })
+            .collect();
+        assert_eq!(ticks, Vec::from([3, 4]));
+        assert_eq!(runner.overruns().dropped(), 0);
+    }
+
+    #[test]
+    fn test_overrun_callback() {
+        let overruns = Rc::new(RefCell::new(Vec::new()));
+        let clock = SimClock::default();
+        let mut runner = Runner::default();
+        runner.set_clock(Box::new(clock.clone()));
+        runner.set_timing_enabled(true);
+        runner.add_component(Box::new(TestBusyComponent(
+            clock.clone(),
+            Vec::from([Duration::from_millis(2)]),
+            0,
+        )));
+        runner.set_tick_deadline(Some(Duration::from_millis(1)));
+        let callback_overruns = overruns.clone();
+        runner.set_overrun_callback(Box::new(move |overrun| {
+            callback_overruns.borrow_mut().push(*overrun)
+        }));
+        runner.initialize();
+        runner.dispatch_components();
+
+        assert!(runner.overruns().is_empty());
+        assert_eq!(
+            *overruns.borrow(),
+            Vec::from([Overrun::TickDeadline {
+                tick: 0,
+                duration: Duration::from_millis(2),
+                deadline: Duration::from_millis(1),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_timing_disabled() {
+        let clock = SimClock::default();
+        let mut runner = Runner::default();
+        runner.set_clock(Box::new(clock.clone()));
+        let id = runner.add_component(Box::new(TestBusyComponent(
+            clock.clone(),
+            Vec::from([Duration::from_millis(2)]),
+            0,
+        )));
+        runner.set_component_budget(id, Some(Duration::from_millis(1)));
+        runner.initialize();
+        runner.dispatch_components();
+
+        assert_eq!(runner.component_timing(id).count(), 0);
+        assert!(runner.overruns().is_empty());
+    }
+
+    struct TestDanglingComponent();
+    impl Component for TestDanglingComponent {
+        fn dispatch(
+            &mut self,
+            _channel_store: &crate::channel::store::ChannelStore,
+            _context: &DispatchContext,
+        ) {
+        }
+
+        fn register_dangling_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelDanglingBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            channel_builder.register_dangling_channel(
+                channel_store,
+                "test.dangling".to_string(),
+                0u8,
+            );
+        }
+    }
+
+    #[test]
+    fn test_execution_graph() {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestSlowReaderComponent {
+            read_tok: ChannelReaderToken::default(),
+            behind_tok: ChannelBehindToken::default(),
+            observed: Rc::new(RefCell::new(Vec::new())),
+        }));
+        runner.add_component(Box::new(TestCounterComponent(ChannelOwnerToken::default())));
+        runner.add_component(Box::new(TestDanglingComponent()));
+        runner.initialize();
+
+        let graph = runner.execution_graph();
+        assert_eq!(
+            graph
+                .components
+                .iter()
+                .map(|node| node.id)
+                .collect::<Vec<usize>>(),
+            Vec::from([2, 1, 0])
+        );
+        assert_eq!(
+            graph.edges,
+            Vec::from([
+                GraphEdge {
+                    producer: Some(1),
+                    consumer: 0,
+                    channel: "test.counter".to_string(),
+                    kind: GraphEdgeKind::Current,
+                },
+                GraphEdge {
+                    producer: Some(1),
+                    consumer: 0,
+                    channel: "test.counter".to_string(),
+                    kind: GraphEdgeKind::Behind,
+                },
+                GraphEdge {
+                    producer: None,
+                    consumer: 2,
+                    channel: "test.dangling".to_string(),
+                    kind: GraphEdgeKind::Dangling,
+                },
+            ])
+        );
+        assert_eq!(
+            runner.export_graph(GraphFormat::Json),
+            graph.render(GraphFormat::Json)
+        );
+    }
+
+    struct TestNamedComponent(ChannelReaderToken<u64>);
+    impl Component for TestNamedComponent {
+        fn name(&self) -> &str {
+            "test.named"
+        }
+
+        fn register_read_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelReadBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            self.0 = channel_builder.bind_read_channel(channel_store, "test.counter".to_string());
+        }
+
+        fn dispatch(
+            &mut self,
+            _channel_store: &crate::channel::store::ChannelStore,
+            _context: &DispatchContext,
+        ) {
+        }
+    }
+
+    #[test]
+    fn test_introspection() {
+        let mut runner = Runner::default();
+        runner.add_component_with_rate(
+            Box::new(TestNamedComponent(ChannelReaderToken::default())),
+            2,
+            1,
+        );
+        runner.add_component(Box::new(TestCounterComponent(ChannelOwnerToken::default())));
+        runner.initialize();
+
+        assert_eq!(runner.execution_order(), Vec::from([1, 0]));
+        assert_eq!(
+            runner.components().collect::<Vec<ComponentInfo>>(),
+            Vec::from([
+                ComponentInfo {
+                    id: 1,
+                    name: "comet::system::runner::unit_tests::TestCounterComponent",
+                    schedule: RateSchedule::default(),
+                },
+                ComponentInfo {
+                    id: 0,
+                    name: "test.named",
+                    schedule: RateSchedule::new(2, 1),
+                },
+            ])
+        );
+
+        let channel = runner.channel_store().channels().next().unwrap();
+        assert_eq!(channel.name, "test.counter");
+        assert_eq!(channel.owner, Some(1));
+        assert_eq!(
+            channel.readers,
+            &[ChannelReader {
+                id: 0,
+                kind: ReaderKind::Current
+            }]
+        );
+        assert_eq!(channel.type_name, "u64");
+    }
+
+    struct TestPolicyComponent(ChannelOwnerToken<u8>, ChannelOwnerToken<u8>);
+    impl Component for TestPolicyComponent {
+        fn register_write_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelWriteBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            self.0 = channel_builder.register_write_channel_with_policy(
+                channel_store,
+                "test.every".to_string(),
+                0,
+                WritePolicy::EveryDispatch,
+            );
+            self.1 = channel_builder.register_write_channel_with_policy(
+                channel_store,
+                "test.once".to_string(),
+                0,
+                WritePolicy::AtMostOnce,
+            );
+        }
+
+        fn dispatch(
+            &mut self,
+            channel_store: &crate::channel::store::ChannelStore,
+            context: &crate::system::component::DispatchContext,
+        ) {
+            if context.tick().is_multiple_of(2) {
+                channel_store.grab(&self.0).set(1);
+            }
+            channel_store.grab(&self.1).set(1);
+            if context.tick() == 1 {
+                channel_store.grab(&self.1).update(|value| *value += 1);
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl ParallelComponent for TestPolicyComponent {
+        fn dispatch_parallel(
+            &mut self,
+            channel_store: &SyncChannelStore,
+            context: &DispatchContext,
+        ) {
+            if context.tick().is_multiple_of(2) {
+                channel_store.grab(&self.0).set(1);
+            }
+            channel_store.grab(&self.1).set(1);
+            if context.tick() == 1 {
+                channel_store.grab(&self.1).update(|value| *value += 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_policy() {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestPolicyComponent(
+            ChannelOwnerToken::default(),
+            ChannelOwnerToken::default(),
+        )));
+        runner.initialize();
+
+        runner.dispatch_components();
+        assert!(runner.write_violations().is_empty());
+        runner.dispatch_components();
+        runner.dispatch_components();
+        runner.dispatch_components();
+
+        assert_eq!(
+            runner.take_write_violations(),
+            Vec::from([
+                WriteViolation {
+                    component_id: 0,
+                    channel: "test.every".to_string(),
+                    tick: 1,
+                    policy: WritePolicy::EveryDispatch,
+                    writes: 0,
+                },
+                WriteViolation {
+                    component_id: 0,
+                    channel: "test.once".to_string(),
+                    tick: 1,
+                    policy: WritePolicy::AtMostOnce,
+                    writes: 2,
+                },
+                WriteViolation {
+                    component_id: 0,
+                    channel: "test.every".to_string(),
+                    tick: 3,
+                    policy: WritePolicy::EveryDispatch,
+                    writes: 0,
+                },
+            ])
+        );
+        assert!(runner.write_violations().is_empty());
+
+        let violations = Rc::new(RefCell::new(Vec::new()));
+        let callback_violations = violations.clone();
+        runner.set_write_violation_callback(Box::new(move |violation| {
+            callback_violations.borrow_mut().push(violation.tick)
+        }));
+        runner.dispatch_components();
+        runner.dispatch_components();
+        assert_eq!(*violations.borrow(), Vec::from([5]));
+        assert!(runner.write_violations().is_empty());
+    }
+
+    #[test]
+    fn test_write_violation_log_capacity() {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestPolicyComponent(
+            ChannelOwnerToken::default(),
+            ChannelOwnerToken::default(),
+        )));
+        runner.set_write_violation_log_capacity(1);
+        runner.initialize();
+
+        for _ in 0..4 {
+            runner.dispatch_components();
+        }
+
+        assert_eq!(runner.write_violations().dropped(), 2);
+        assert_eq!(
+            runner
+                .take_write_violations()
+                .iter()
+                .map(|violation| (violation.channel.as_str(), violation.tick))
+                .collect::<Vec<(&str, u64)>>(),
+            Vec::from([("test.every", 3)])
+        );
+    }
+
+    struct TestStatefulComponent(ChannelOwnerToken<u64>, u64);
+    impl Component for TestStatefulComponent {
+        fn register_write_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelWriteBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            self.0 = channel_builder.register_write_channel(
+                channel_store,
+                "test.stateful".to_string(),
+                0,
+            );
+        }
+
+        fn dispatch(
+            &mut self,
+            channel_store: &crate::channel::store::ChannelStore,
+            _context: &crate::system::component::DispatchContext,
+        ) {
+            self.1 += 1;
+            channel_store.grab(&self.0).set(self.1 * 10);
+        }
+
+        fn save_state(&self) -> Option<ComponentState> {
+            Some(Box::new(self.1))
+        }
+
+        fn restore_state(&mut self, state: &(dyn Any + Send)) {
+            self.1 = *state.downcast_ref::<u64>().unwrap();
+        }
+    }
+
+    fn stateful_runner() -> Runner {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestStatefulComponent(
+            ChannelOwnerToken::default(),
+            0,
+        )));
+        runner.add_component(Box::new(TestCounterComponent(ChannelOwnerToken::default())));
+        runner.initialize();
+        runner
+    }
+
+    #[test]
+    fn test_runner_snapshot_restore() {
+        let mut runner = stateful_runner();
+        for _ in 0..3 {
+            runner.dispatch_components();
+        }
+        let snapshot = runner.snapshot();
+        assert_eq!(snapshot.tick(), 3);
+
+        runner.dispatch_components();
+        runner.dispatch_components();
+        runner.restore(&snapshot).unwrap();
+        runner.dispatch_components();
+
+        let stateful = runner
+            .channel_store()
+            .try_bind_observer::<u64>("test.stateful")
+            .unwrap();
+        let counter = runner
+            .channel_store()
+            .try_bind_observer::<u64>("test.counter")
+            .unwrap();
+        assert_eq!(runner.channel_store().grab(&stateful).get(), 40);
+        assert_eq!(runner.channel_store().grab(&counter).get(), 4);
+
+        // A rebuilt runner resumes from the snapshot as after a warm restart.
+        let mut restarted_runner = stateful_runner();
+        restarted_runner.restore(&snapshot).unwrap();
+        restarted_runner.dispatch_components();
+        let stateful = restarted_runner
+            .channel_store()
+            .try_bind_observer::<u64>("test.stateful")
+            .unwrap();
+        assert_eq!(restarted_runner.channel_store().grab(&stateful).get(), 40);
+
+        let mut other_runner = Runner::default();
+        other_runner.add_component(Box::new(TestCounterComponent(ChannelOwnerToken::default())));
+        other_runner.initialize();
+        assert_eq!(
+            other_runner.restore(&snapshot).err(),
+            Some(SnapshotError::ComponentCount {
+                expected: 1,
+                actual: 2,
+            })
+        );
+    }
+
+    type FreshnessObservations = Rc<RefCell<Vec<(bool, Option<u64>)>>>;
+
+    struct TestFreshnessComponent(ChannelReaderToken<u64>, FreshnessObservations);
+    impl Component for TestFreshnessComponent {
+        fn register_read_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelReadBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            self.0 = channel_builder.bind_read_channel(channel_store, "test.counter".to_string());
+            channel_builder.set_staleness_limit(channel_store, "test.counter".to_string(), 1);
+        }
+
+        fn dispatch(
+            &mut self,
+            channel_store: &crate::channel::store::ChannelStore,
+            _context: &crate::system::component::DispatchContext,
+        ) {
+            let view = channel_store.grab(&self.0);
+            self.1
+                .borrow_mut()
+                .push((view.is_fresh(), view.age_ticks()));
+        }
+    }
+
+    #[test]
+    fn test_channel_freshness() {
+        let observations = Rc::new(RefCell::new(Vec::new()));
+        let diagnostics = Rc::new(RefCell::new(Vec::new()));
+        let sink_diagnostics = diagnostics.clone();
+        let mut runner = Runner::default();
+        runner.add_component_with_rate(
+            Box::new(TestCounterComponent(ChannelOwnerToken::default())),
+            3,
+            0,
+        );
+        runner.add_component(Box::new(TestFreshnessComponent(
+            ChannelReaderToken::default(),
+            observations.clone(),
+        )));
+        runner.set_diagnostic_sink(Box::new(move |diagnostic: &Diagnostic| {
+            sink_diagnostics.borrow_mut().push(diagnostic.clone())
+        }));
+        runner.initialize();
+
+        for _ in 0..6 {
+            runner.dispatch_components();
+        }
+
+        assert_eq!(
+            *observations.borrow(),
+            Vec::from([
+                (true, Some(0)),
+                (false, Some(1)),
+                (false, Some(2)),
+                (true, Some(0)),
+                (false, Some(1)),
+                (false, Some(2)),
+            ])
+        );
+        // The limit is reported once per stale period.
+        let stale = Diagnostic {
+            rule: LintRule::StaleChannel,
+            level: LintLevel::Warn,
+            channel: "test.counter".to_string(),
+            component: 1,
+        };
+        assert_eq!(*diagnostics.borrow(), Vec::from([stale.clone(), stale]));
+        assert!(runner.tick_diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_stale_channel_log() {
+        let mut runner = Runner::default();
+        runner.add_component_with_rate(
+            Box::new(TestCounterComponent(ChannelOwnerToken::default())),
+            3,
+            0,
+        );
+        runner.add_component(Box::new(TestFreshnessComponent(
+            ChannelReaderToken::default(),
+            Rc::new(RefCell::new(Vec::new())),
+        )));
+        runner.initialize();
+
+        for _ in 0..6 {
+            runner.dispatch_components();
+        }
+
+        let stale = Diagnostic {
+            rule: LintRule::StaleChannel,
+            level: LintLevel::Warn,
+            channel: "test.counter".to_string(),
+            component: 1,
+        };
+        assert_eq!(
+            runner.take_tick_diagnostics(),
+            Vec::from([stale.clone(), stale])
+        );
+        assert!(runner.tick_diagnostics().is_empty());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Channel [test.counter] read by component [1] exceeded its staleness limit (stale_channel)."
+    )]
+    fn test_channel_staleness_deny() {
+        let mut runner = Runner::default();
+        runner.add_component_with_rate(
+            Box::new(TestCounterComponent(ChannelOwnerToken::default())),
+            3,
+            0,
+        );
+        runner.add_component(Box::new(TestFreshnessComponent(
+            ChannelReaderToken::default(),
+            Rc::new(RefCell::new(Vec::new())),
+        )));
+        runner.set_lint_level(LintRule::StaleChannel, LintLevel::Deny);
+        runner.initialize();
+
+        for _ in 0..3 {
+            runner.dispatch_components();
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_channel_staleness_deny_completes_tick() {
+        let mut runner = Runner::default();
+        runner.add_component_with_rate(
+            Box::new(TestCounterComponent(ChannelOwnerToken::default())),
+            3,
+            0,
+        );
+        runner.add_component(Box::new(TestFreshnessComponent(
+            ChannelReaderToken::default(),
+            Rc::new(RefCell::new(Vec::new())),
+        )));
+        runner.set_lint_level(LintRule::StaleChannel, LintLevel::Deny);
+        runner.initialize();
+
+        runner.dispatch_components();
         runner.dispatch_components();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            runner.dispatch_components();
+        }));
+        assert!(result.is_err());
+        // The tick is completed and the violation logged before the runner aborts.
+        assert_eq!(runner.tick, 3);
+        assert_eq!(runner.tick_diagnostics().len(), 1);
+        runner.dispatch_components();
+    }
+
+    #[test]
+    fn test_lint_diagnostic_sink() {
+        let diagnostics = Rc::new(RefCell::new(Vec::new()));
+        let sink_diagnostics = diagnostics.clone();
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestCounterComponent(ChannelOwnerToken::default())));
+        runner.add_component(Box::new(TestDanglingComponent()));
+        runner.set_diagnostic_sink(Box::new(move |diagnostic: &Diagnostic| {
+            sink_diagnostics.borrow_mut().push(diagnostic.clone())
+        }));
+        runner.initialize();
+
+        assert_eq!(
+            *diagnostics.borrow(),
+            Vec::from([
+                Diagnostic {
+                    rule: LintRule::UnownedDanglingChannel,
+                    level: LintLevel::Warn,
+                    channel: "test.dangling".to_string(),
+                    component: 1,
+                },
+                Diagnostic {
+                    rule: LintRule::UnreadChannel,
+                    level: LintLevel::Warn,
+                    channel: "test.counter".to_string(),
+                    component: 0,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Dangling channel [test.dangling] registered by component [0] has no owner (unowned_dangling_channel)."
+    )]
+    fn test_lint_deny() {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestDanglingComponent()));
+        runner.set_lint_level(LintRule::UnownedDanglingChannel, LintLevel::Deny);
+        runner.initialize();
+    }
+
+    #[test]
+    fn test_try_init_lint_deny() {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestCounterComponent(ChannelOwnerToken::default())));
+        runner.set_lint_level(LintRule::UnreadChannel, LintLevel::Deny);
+
+        let report = runner.try_initialize().unwrap_err();
+        assert_eq!(
+            report.errors,
+            Vec::from([InitError::Lint(Diagnostic {
+                rule: LintRule::UnreadChannel,
+                level: LintLevel::Deny,
+                channel: "test.counter".to_string(),
+                component: 0,
+            })])
+        );
+        assert!(!runner.init_complete);
+    }
+
+    #[test]
+    fn test_dispatch_levels() {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestProducerComponent(
+            "test.producer4".to_string(),
+            Some("test.producer3".to_string()),
+        )));
+        runner.add_component(Box::new(TestProducerComponent(
+            "test.producer3".to_string(),
+            Some("test.producer1".to_string()),
+        )));
+        runner.add_component(Box::new(TestProducerComponent(
+            "test.producer1".to_string(),
+            None,
+        )));
+        runner.add_component(Box::new(TestProducerComponent(
+            "test.producer2".to_string(),
+            Some("test.producer1".to_string()),
+        )));
+        runner.initialize();
+
+        let mut levels: Vec<(usize, usize)> = runner
+            .components
+            .iter()
+            .map(|holder| (holder.id, holder.level))
+            .collect();
+        levels.sort();
+        assert_eq!(levels, Vec::from([(0, 2), (1, 1), (2, 0), (3, 1)]));
+    }
+
+    /// Dispatch observations of (output channel, tick, written value).
+    #[cfg(feature = "std")]
+    type SharedObservations = std::sync::Arc<std::sync::Mutex<Vec<(&'static str, u64, u64)>>>;
+
+    /// Component writing the sum of its inputs, its own previous output and one.
+    #[cfg(feature = "std")]
+    struct TestSumComponent {
+        output: (&'static str, ChannelOwnerToken<u64>),
+        inputs: Vec<(&'static str, ChannelReaderToken<u64>)>,
+        behind: Option<(&'static str, ChannelBehindToken<u64>)>,
+        observed: SharedObservations,
+    }
+
+    #[cfg(feature = "std")]
+    impl TestSumComponent {
+        fn boxed(
+            output: &'static str,
+            inputs: &[&'static str],
+            behind: Option<&'static str>,
+            observed: &SharedObservations,
+        ) -> Box<Self> {
+            Box::new(Self {
+                output: (output, ChannelOwnerToken::default()),
+                inputs: inputs
+                    .iter()
+                    .map(|name| (*name, ChannelReaderToken::default()))
+                    .collect(),
+                behind: behind.map(|name| (name, ChannelBehindToken::default())),
+                observed: observed.clone(),
+            })
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl ParallelComponent for TestSumComponent {
+        fn dispatch_parallel(
+            &mut self,
+            channel_store: &SyncChannelStore,
+            context: &DispatchContext,
+        ) {
+            let mut sum: u64 = self
+                .inputs
+                .iter()
+                .map(|(_, token)| channel_store.grab(token).get())
+                .sum();
+            if let Some((_, token)) = self.behind.as_ref() {
+                sum += channel_store.grab(token).get();
+            }
+            channel_store.grab(&self.output.1).set(sum + 1);
+            self.observed
+                .lock()
+                .unwrap()
+                .push((self.output.0, context.tick(), sum + 1));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl Component for TestSumComponent {
+        fn register_write_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelWriteBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            self.output.1 =
+                channel_builder.register_write_channel(channel_store, self.output.0.to_string(), 0);
+        }
+
+        fn register_read_channels(
+            &mut self,
+            channel_builder: crate::channel::store::ChannelReadBuilder,
+            channel_store: &mut crate::channel::store::ChannelStore,
+        ) {
+            for (name, token) in self.inputs.iter_mut() {
+                *token = channel_builder.bind_read_channel(channel_store, name.to_string());
+            }
+            if let Some((name, token)) = self.behind.as_mut() {
+                *token = channel_builder.bind_read_behind_channel(channel_store, name.to_string());
+            }
+        }
+
+        fn dispatch(
+            &mut self,
+            channel_store: &crate::channel::store::ChannelStore,
+            context: &DispatchContext,
+        ) {
+            self.dispatch_parallel(&SyncChannelStore::new(channel_store), context);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parallel_dispatch() {
+        let build_runner = |observed| {
+            let mut runner = Runner::default();
+            runner.add_parallel_component(TestSumComponent::boxed(
+                "test.sink",
+                &["test.left", "test.right"],
+                None,
+                observed,
+            ));
+            runner.add_parallel_component(TestSumComponent::boxed(
+                "test.source",
+                &[],
+                Some("test.sink"),
+                observed,
+            ));
+            runner.add_parallel_component_with_rate(
+                TestSumComponent::boxed("test.left", &["test.source"], None, observed),
+                2,
+                1,
+            );
+            runner.add_parallel_component(TestSumComponent::boxed(
+                "test.right",
+                &["test.source"],
+                Some("test.right"),
+                observed,
+            ));
+            runner.add_parallel_component(TestSumComponent::boxed(
+                "test.other",
+                &[],
+                None,
+                observed,
+            ));
+            runner.initialize();
+            runner
+        };
+
+        let sequential_observed = SharedObservations::default();
+        let mut sequential_runner = build_runner(&sequential_observed);
+        let parallel_observed = SharedObservations::default();
+        let mut parallel_runner = build_runner(&parallel_observed);
+        for _ in 0..10 {
+            sequential_runner.dispatch_components();
+            parallel_runner.dispatch_components_parallel(4);
+        }
+
+        let mut sequential_observed = sequential_observed.lock().unwrap().clone();
+        let mut parallel_observed = parallel_observed.lock().unwrap().clone();
+        assert_eq!(sequential_observed.len(), 45);
+        sequential_observed.sort();
+        parallel_observed.sort();
+        assert_eq!(sequential_observed, parallel_observed);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic(
+        expected = "Parallel dispatch requires every component to be added with add_parallel_component()."
+    )]
+    fn test_parallel_dispatch_non_send() {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestComponent()));
+        runner.initialize();
+        runner.dispatch_components_parallel(2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parallel_dispatch_replay_write_policy() {
+        let build_runner = || {
+            let mut runner = Runner::default();
+            runner.add_parallel_component(Box::new(TestPolicyComponent(
+                ChannelOwnerToken::default(),
+                ChannelOwnerToken::default(),
+            )));
+            runner.initialize();
+            runner
+        };
+        let every_channel = || {
+            let mut channels = RecordChannels::default();
+            channels.add::<u8>("test.every");
+            channels
+        };
+
+        let mut recorded_runner = build_runner();
+        let buffer = RecordBuffer::default();
+        recorded_runner
+            .set_recorder(Recorder::new(every_channel(), Box::new(buffer.clone())))
+            .unwrap();
+        for _ in 0..4 {
+            recorded_runner.dispatch_components();
+        }
+        let log = buffer.parse().unwrap();
+
+        let mut sequential_runner = build_runner();
+        sequential_runner
+            .replay_component(0, &every_channel(), log.clone())
+            .unwrap();
+        let mut parallel_runner = build_runner();
+        parallel_runner
+            .replay_component(0, &every_channel(), log)
+            .unwrap();
+        for _ in 0..4 {
+            sequential_runner.dispatch_components();
+            parallel_runner.dispatch_components_parallel(2);
+        }
+
+        // The replay only writes the values recorded on even ticks, replayed components are
+        // checked against their write policies by both dispatch methods.
+        let violations = sequential_runner.take_write_violations();
+        assert_eq!(
+            violations
+                .iter()
+                .map(|violation| violation.tick)
+                .collect::<Vec<u64>>(),
+            [1, 3]
+        );
+        assert_eq!(parallel_runner.take_write_violations(), violations);
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "concurrent"))]
+    fn test_channel_store_sync() {
+        fn assert_sync<T: Sync + Send>() {}
+        assert_sync::<crate::channel::store::SyncChannelStore>();
+        #[cfg(feature = "concurrent")]
+        assert_sync::<SharedChannelStore>();
+    }
+
+    #[test]
+    #[cfg(all(feature = "concurrent", feature = "std"))]
+    fn test_shared_channel_store() {
+        let mut runner = Runner::default();
+        runner.add_component(Box::new(TestCounterComponent(ChannelOwnerToken::default())));
+        runner.initialize();
+
+        let channel_store = runner.shared_channel_store();
+        let counter_tok = channel_store
+            .try_bind_observer::<u64>("test.counter")
+            .unwrap();
+        let (held_sender, held_receiver) = std::sync::mpsc::channel();
+        let (done_sender, done_receiver) = std::sync::mpsc::channel();
+        let observer = std::thread::spawn(move || {
+            // The runner keeps dispatching while the observer holds a borrow of the counter.
+            let guard = channel_store.grab(&counter_tok).borrow();
+            held_sender.send(*guard).unwrap();
+            done_receiver.recv().unwrap();
+            let held = *guard;
+            drop(guard);
+
+            let mut last = 0;
+            while last < 2000 {
+                let value = channel_store.grab(&counter_tok).get();
+                assert!(value >= last);
+                last = value;
+            }
+            held
+        });
+
+        let held = held_receiver.recv().unwrap();
+        for _ in 0..1000 {
+            runner.dispatch_components();
+        }
+        done_sender.send(()).unwrap();
+        for _ in 0..1000 {
+            runner.dispatch_components();
+        }
+        assert_eq!(observer.join().unwrap(), held);
     }
 }
@@ -0,0 +1,63 @@
+/// Execution rate of a component expressed in runner ticks. A tick is a single call to
+/// Runner::dispatch_components().
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RateSchedule {
+    /// Number of ticks between two dispatches of the component.
+    pub period_ticks: u64,
+    /// Tick within the period at which the component is dispatched.
+    pub phase_offset: u64,
+}
+
+impl Default for RateSchedule {
+    fn default() -> Self {
+        Self {
+            period_ticks: 1,
+            phase_offset: 0,
+        }
+    }
+}
+
+impl RateSchedule {
+    pub fn new(period_ticks: u64, phase_offset: u64) -> Self {
+        assert!(
+            period_ticks > 0,
+            "Component period must be at least one tick."
+        );
+        assert!(
+            phase_offset < period_ticks,
+            "Component phase offset must be less than the period."
+        );
+        Self {
+            period_ticks,
+            phase_offset,
+        }
+    }
+
+    /// Returns true if a component following this schedule is due for dispatch on the given tick.
+    pub fn is_due(&self, tick: u64) -> bool {
+        tick % self.period_ticks == self.phase_offset
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use alloc::vec::Vec;
+
+    use super::RateSchedule;
+
+    #[test]
+    fn test_schedule_due() {
+        let schedule = RateSchedule::default();
+        assert!((0..10).all(|tick| schedule.is_due(tick)));
+
+        let schedule = RateSchedule::new(4, 1);
+        let due_ticks: Vec<u64> = (0..12).filter(|tick| schedule.is_due(*tick)).collect();
+        assert_eq!(due_ticks, Vec::from([1, 5, 9]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Component phase offset must be less than the period.")]
+    fn test_schedule_offset() {
+        RateSchedule::new(4, 4);
+    }
+}
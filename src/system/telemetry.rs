@@ -0,0 +1,148 @@
+use core::{fmt, time::Duration};
+
+use alloc::{string::String, vec::Vec};
+
+use crate::channel::store::ChannelStore;
+
+/// Receiver of channel values at the end of every runner tick, such as a log file exporter.
+/// Sinks are attached to an initialized runner with Runner::add_telemetry_sink().
+pub trait TelemetrySink {
+    /// Called once when the sink is attached, used to select and describe the logged channels.
+    fn attach(&mut self, channel_store: &ChannelStore) -> Result<(), TelemetryError>;
+
+    /// Called at the end of every tick once every component was dispatched.
+    ///
+    /// ### Arguments
+    /// * 'tick' - Index of the completed tick.
+    /// * 'timestamp' - Runner clock timestamp sampled at the start of the tick.
+    /// * 'channel_store' - Channel store of the runner.
+    ///
+    fn record_tick(&mut self, tick: u64, timestamp: Duration, channel_store: &ChannelStore);
+
+    /// Complete the output of the sink, reporting the first error raised while recording.
+    fn finish(&mut self) -> Result<(), TelemetryError> {
+        Ok(())
+    }
+}
+
+/// Error raised by a telemetry sink.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TelemetryError {
+    /// A channel selected by the sink cannot be logged.
+    Channel { name: String, reason: String },
+    /// The sink failed to write its output.
+    Write { message: String },
+}
+
+impl fmt::Display for TelemetryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TelemetryError::Channel { name, reason } => {
+                write!(f, "Channel [{}] cannot be logged: {}", name, reason)
+            }
+            TelemetryError::Write { message } => {
+                write!(f, "Telemetry write failed: {}", message)
+            }
+        }
+    }
+}
+
+impl core::error::Error for TelemetryError {}
+
+/// Selection of channels by name. Patterns match whole channel names and may contain '*'
+/// wildcards matching any sequence of characters, such as "nav.*" or "*.debug".
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ChannelFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl ChannelFilter {
+    /// Include channels matching the pattern. Every channel is included while no include
+    /// pattern is set.
+    ///
+    /// ### Argument
+    /// * 'pattern' - Channel name pattern.
+    ///
+    pub fn include(mut self, pattern: &str) -> Self {
+        self.include.push(String::from(pattern));
+        self
+    }
+
+    /// Exclude channels matching the pattern, exclusions take precedence over inclusions.
+    ///
+    /// ### Argument
+    /// * 'pattern' - Channel name pattern.
+    ///
+    pub fn exclude(mut self, pattern: &str) -> Self {
+        self.exclude.push(String::from(pattern));
+        self
+    }
+
+    /// Returns true if the channel with the given name is selected.
+    pub fn matches(&self, name: &str) -> bool {
+        (self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern| matches_pattern(pattern, name)))
+            && !self
+                .exclude
+                .iter()
+                .any(|pattern| matches_pattern(pattern, name))
+    }
+}
+
+/// Match a name against a pattern where '*' matches any sequence of characters.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    // The first part is anchored at the start of the name.
+    let Some(prefix) = parts.next() else {
+        return name.is_empty();
+    };
+    let Some(mut rest) = name.strip_prefix(prefix) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((suffix, middle)) = parts.split_last() else {
+        // No wildcard, the pattern must match the whole name.
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    // The last part is anchored at the end of the name.
+    rest.ends_with(suffix)
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::{ChannelFilter, matches_pattern};
+
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("nav.speed", "nav.speed"));
+        assert!(!matches_pattern("nav.speed", "nav.speed.x"));
+        assert!(matches_pattern("nav.*", "nav.speed"));
+        assert!(matches_pattern("*.debug", "nav.debug"));
+        assert!(matches_pattern("nav.*.x", "nav.speed.x"));
+        assert!(!matches_pattern("nav.*.x", "nav.speed.y"));
+        assert!(matches_pattern("*", ""));
+        assert!(!matches_pattern("a*a", "a"));
+    }
+
+    #[test]
+    fn test_channel_filter() {
+        let filter = ChannelFilter::default();
+        assert!(filter.matches("nav.speed"));
+
+        let filter = ChannelFilter::default().include("nav.*").exclude("*.debug");
+        assert!(filter.matches("nav.speed"));
+        assert!(!filter.matches("nav.debug"));
+        assert!(!filter.matches("motor.speed"));
+    }
+}
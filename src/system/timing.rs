@@ -0,0 +1,156 @@
+use core::time::Duration;
+
+use alloc::boxed::Box;
+
+use super::event_log::EventLog;
+
+/// Number of buckets within a timing histogram.
+pub const HISTOGRAM_BUCKETS: usize = 16;
+
+/// Execution time statistics of a component dispatch or of a whole runner tick.
+///
+/// The histogram uses power of two microsecond buckets. Bucket 0 counts durations below 1 us,
+/// bucket i counts durations within [2^(i-1) us, 2^i us) and the last bucket counts every
+/// duration of at least 2^(HISTOGRAM_BUCKETS - 2) us.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct TimingStats {
+    count: u64,
+    min: Duration,
+    max: Duration,
+    total: Duration,
+    histogram: [u64; HISTOGRAM_BUCKETS],
+}
+
+impl TimingStats {
+    /// Include a measured duration into the statistics.
+    pub fn record(&mut self, duration: Duration) {
+        if self.count == 0 || duration < self.min {
+            self.min = duration;
+        }
+        if duration > self.max {
+            self.max = duration;
+        }
+        self.count += 1;
+        self.total += duration;
+        self.histogram[Self::bucket_index(duration)] += 1;
+    }
+
+    fn bucket_index(duration: Duration) -> usize {
+        let micros = duration.as_micros();
+        if micros == 0 {
+            return 0;
+        }
+
+        let bucket = (u128::BITS - micros.leading_zeros()) as usize;
+        bucket.min(HISTOGRAM_BUCKETS - 1)
+    }
+
+    /// Number of measurements recorded.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Shortest measured duration, zero if nothing was measured.
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+
+    /// Longest measured duration, zero if nothing was measured.
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Mean of all measured durations, zero if nothing was measured.
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+
+        Duration::from_nanos((self.total.as_nanos() / self.count as u128) as u64)
+    }
+
+    /// Histogram bucket counts of all measured durations.
+    pub fn histogram(&self) -> &[u64; HISTOGRAM_BUCKETS] {
+        &self.histogram
+    }
+}
+
+/// Time budget violation detected by the runner.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Overrun {
+    /// A component dispatch took longer than the budget assigned to the component.
+    ComponentBudget {
+        component_id: usize,
+        tick: u64,
+        duration: Duration,
+        budget: Duration,
+    },
+    /// A whole runner tick took longer than the tick deadline.
+    TickDeadline {
+        tick: u64,
+        duration: Duration,
+        deadline: Duration,
+    },
+}
+
+/// Callback invoked by the runner for every detected overrun.
+pub type OverrunCallback = Box<dyn FnMut(&Overrun)>;
+
+/// Runner level timing configuration and results.
+#[derive(Default)]
+pub(crate) struct TimingMonitor {
+    /// Enables measurement of every component dispatch.
+    pub enabled: bool,
+    pub tick_deadline: Option<Duration>,
+    pub tick_stats: TimingStats,
+    /// Overruns are passed to the callback if set, otherwise stored in the overrun log.
+    pub overrun_callback: Option<OverrunCallback>,
+    pub overrun_log: EventLog<Overrun>,
+}
+
+impl TimingMonitor {
+    pub(crate) fn report_overrun(&mut self, overrun: Overrun) {
+        match self.overrun_callback.as_mut() {
+            Some(callback) => callback(&overrun),
+            None => self.overrun_log.push(overrun),
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use core::time::Duration;
+
+    use super::{HISTOGRAM_BUCKETS, TimingStats};
+
+    #[test]
+    fn test_timing_stats() {
+        let mut stats = TimingStats::default();
+        assert_eq!(stats.mean(), Duration::ZERO);
+
+        stats.record(Duration::from_micros(30));
+        stats.record(Duration::from_micros(10));
+        stats.record(Duration::from_micros(20));
+
+        assert_eq!(stats.count(), 3);
+        assert_eq!(stats.min(), Duration::from_micros(10));
+        assert_eq!(stats.max(), Duration::from_micros(30));
+        assert_eq!(stats.mean(), Duration::from_micros(20));
+    }
+
+    #[test]
+    fn test_timing_histogram() {
+        let mut stats = TimingStats::default();
+        stats.record(Duration::from_nanos(500));
+        stats.record(Duration::from_micros(1));
+        stats.record(Duration::from_micros(3));
+        stats.record(Duration::from_secs(10));
+
+        let histogram = stats.histogram();
+        assert_eq!(histogram[0], 1);
+        assert_eq!(histogram[1], 1);
+        assert_eq!(histogram[2], 1);
+        assert_eq!(histogram[HISTOGRAM_BUCKETS - 1], 1);
+        assert_eq!(histogram.iter().sum::<u64>(), 4);
+    }
+}
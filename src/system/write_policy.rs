@@ -0,0 +1,132 @@
+use core::fmt;
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::channel::store::{ChannelStore, WritePolicy};
+
+use super::event_log::EventLog;
+
+/// Dispatch of a channel owner which broke the write policy of the channel.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WriteViolation {
+    /// Id of the component owning the channel.
+    pub component_id: usize,
+    /// Name of the channel.
+    pub channel: String,
+    /// Tick of the dispatch.
+    pub tick: u64,
+    pub policy: WritePolicy,
+    /// Number of writes made to the channel during the dispatch.
+    pub writes: u64,
+}
+
+impl fmt::Display for WriteViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Component [{}] wrote channel [{}] {} time(s) during tick [{}], violating its write policy ({:?}).",
+            self.component_id, self.channel, self.writes, self.tick, self.policy
+        )
+    }
+}
+
+/// Callback invoked by the runner for every detected write policy violation.
+pub type WriteViolationCallback = Box<dyn FnMut(&WriteViolation)>;
+
+/// Channel with a write policy owned by a component.
+struct WriteCheck {
+    channel_idx: usize,
+    policy: WritePolicy,
+    /// Write sequence of the channel sampled before the dispatch of the owner.
+    sequence: u64,
+}
+
+/// Runner level write policy enforcement.
+#[derive(Default)]
+pub(crate) struct WriteMonitor {
+    /// Checks indexed by the id of the owning component.
+    checks: Vec<Vec<WriteCheck>>,
+    /// Violations are passed to the callback if set, otherwise stored in the violation log.
+    pub violation_callback: Option<WriteViolationCallback>,
+    pub violation_log: EventLog<WriteViolation>,
+}
+
+impl WriteMonitor {
+    /// Collect the write policies of every channel within the store, called once channels are
+    /// registered.
+    pub(crate) fn configure(&mut self, channel_store: &ChannelStore, component_count: usize) {
+        self.checks.clear();
+        self.checks.resize_with(component_count, Vec::new);
+        for (channel_idx, owner, policy) in channel_store.write_policies() {
+            self.checks[owner].push(WriteCheck {
+                channel_idx,
+                policy,
+                sequence: 0,
+            });
+        }
+    }
+
+    /// Sample the write sequence of the channels of a component before it is dispatched.
+    pub(crate) fn begin_dispatch(&mut self, component_id: usize, channel_store: &ChannelStore) {
+        for check in self.checks[component_id].iter_mut() {
+            check.sequence = channel_store.channel_write_sequence(check.channel_idx);
+        }
+    }
+
+    /// Compare the write sequence of the channels of a component after it was dispatched
+    /// against their policies, reporting every violation.
+    pub(crate) fn end_dispatch(
+        &mut self,
+        component_id: usize,
+        tick: u64,
+        channel_store: &ChannelStore,
+    ) {
+        for check in self.checks[component_id].iter() {
+            let writes = channel_store.channel_write_sequence(check.channel_idx) - check.sequence;
+            let violated = match check.policy {
+                WritePolicy::Optional => false,
+                WritePolicy::EveryDispatch => writes == 0,
+                WritePolicy::AtMostOnce => writes > 1,
+            };
+            if !violated {
+                continue;
+            }
+
+            let violation = WriteViolation {
+                component_id,
+                channel: String::from(channel_store.channel_name(check.channel_idx)),
+                tick,
+                policy: check.policy,
+                writes,
+            };
+            match self.violation_callback.as_mut() {
+                Some(callback) => callback(&violation),
+                None => self.violation_log.push(violation),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use alloc::string::{String, ToString};
+
+    use crate::channel::store::WritePolicy;
+
+    use super::WriteViolation;
+
+    #[test]
+    fn test_write_violation_display() {
+        let violation = WriteViolation {
+            component_id: 2,
+            channel: String::from("test.every"),
+            tick: 7,
+            policy: WritePolicy::EveryDispatch,
+            writes: 0,
+        };
+        assert_eq!(
+            violation.to_string(),
+            "Component [2] wrote channel [test.every] 0 time(s) during tick [7], violating its write policy (EveryDispatch)."
+        );
+    }
+}
@@ -27,7 +27,11 @@ impl Component for TestProducer {
         );
     }
 
-    fn dispatch(&mut self, channel_store: &comet::channel::store::ChannelStore) {
+    fn dispatch(
+        &mut self,
+        channel_store: &comet::channel::store::ChannelStore,
+        _context: &comet::system::component::DispatchContext,
+    ) {
         channel_store
             .grab(&self.channel_tok)
             .set(self.channel_value);
@@ -45,11 +49,15 @@ impl Component for TestModifier {
         channel_store: &mut comet::channel::store::ChannelStore,
     ) {
         let mut dangle_names = channel_builder.query_unowned_dangling_channel_names(channel_store);
-        self.channel_tok = channel_builder
-            .try_obtain_channel_ownership(channel_store, dangle_names.pop().unwrap());
+        self.channel_tok =
+            channel_builder.obtain_channel_ownership(channel_store, dangle_names.pop().unwrap());
     }
 
-    fn dispatch(&mut self, channel_store: &comet::channel::store::ChannelStore) {
+    fn dispatch(
+        &mut self,
+        channel_store: &comet::channel::store::ChannelStore,
+        _context: &comet::system::component::DispatchContext,
+    ) {
         let value = channel_store.grab(&self.channel_tok).get();
         channel_store.grab(&self.channel_tok).set(value + 1);
     }
@@ -99,7 +107,11 @@ impl Component for TestAdder {
             channel_builder.bind_read_channel(channel_store, self.input_channel_name.to_string());
     }
 
-    fn dispatch(&mut self, channel_store: &comet::channel::store::ChannelStore) {
+    fn dispatch(
+        &mut self,
+        channel_store: &comet::channel::store::ChannelStore,
+        _context: &comet::system::component::DispatchContext,
+    ) {
         let input_value = channel_store.grab(&self.input_channel_tok).get();
         let current_count = channel_store.grab(&self.output_channel_tok).get();
         let mod_value = channel_store.grab(&self.mod_channel_tok).get();
@@ -155,7 +167,11 @@ impl Component for TestCycleRW {
         }
     }
 
-    fn dispatch(&mut self, channel_store: &comet::channel::store::ChannelStore) {
+    fn dispatch(
+        &mut self,
+        channel_store: &comet::channel::store::ChannelStore,
+        _context: &comet::system::component::DispatchContext,
+    ) {
         let assert_values = [34i64, 100];
         if self.as_behind {
             assert_eq!(